@@ -1,4 +1,4 @@
-use std::io::Write;
+use clap::Parser;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,139 +25,14 @@ use std::io::Write;
 // SOFTWARE.
 // ============================================================================
 
-use clap::Parser;
 use rayon::prelude::*;
-use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::thread;
-use std::time::Instant;
+use std::sync::atomic::AtomicUsize;
 
-use multiqueue::util::termination_detection::{try_do, TerminationData};
+use multiqueue::bfs::{multiqueue_bfs, verify_distance};
+use multiqueue::sssp::{write_distance, QueueOptions};
 use multiqueue::util::Graph;
-use multiqueue::MultiQueue;
-
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
-struct ValType(usize, usize);
-
-impl Ord for ValType {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.cmp(&self.0)
-    }
-}
-
-impl PartialOrd for ValType {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.0.cmp(&self.0))
-    }
-}
-
-struct SharedData {
-    shortest_distance: Vec<AtomicUsize>,
-    term_data: TerminationData,
-}
-
-fn process_node(val: ValType, graph: &Graph, data: &SharedData, pq: &MultiQueue<ValType>) {
-    let (dist, src) = (val.0, val.1);
-
-    if data.shortest_distance[src].load(Ordering::Relaxed) < dist {
-        return;
-    }
-
-    let new_distance = dist + 1;
-    for i in graph.nodes[src]..graph.nodes[src + 1] {
-        let target = graph.edges[i].target;
-        let mut old_distance = data.shortest_distance[target].load(Ordering::Relaxed);
-
-        while new_distance < old_distance {
-            match data.shortest_distance[target].compare_exchange_weak(
-                old_distance,
-                new_distance,
-                Ordering::SeqCst,
-                Ordering::Relaxed,
-            ) {
-                Ok(_) => {
-                    pq.push(ValType(new_distance, target));
-                    break;
-                }
-                Err(x) => old_distance = x,
-            }
-        }
-    }
-}
-
-fn main_loop(graph: &Graph, pq: &MultiQueue<ValType>, data: &SharedData) {
-    while let Ok(val) = try_do(
-        &|| {
-            if let Some(val) = pq.pop() {
-                Ok(val)
-            } else {
-                Err(())
-            }
-        },
-        &data.term_data,
-    ) {
-        process_node(val, graph, data, pq);
-    }
-}
-
-fn launch_threads_and_wait(
-    graph: &Graph,
-    num_threads: usize,
-    pq: &MultiQueue<ValType>,
-    data: &SharedData,
-    start_node: usize,
-) {
-    pq.push(ValType(0, start_node));
-    data.shortest_distance[start_node].store(0, Ordering::Relaxed);
-
-    thread::scope(|s| {
-        for _ in 0..num_threads {
-            s.spawn(|| main_loop(graph, pq, data));
-        }
-    });
-}
-
-// Verification method adopted from Galois
-fn verify_distance(graph: &Graph, distance: &[AtomicUsize]) {
-    let mut failed = false;
-    let max = AtomicUsize::new(0);
-    distance.iter().enumerate().for_each(|(v, x)| {
-        let dist = x.load(Ordering::Relaxed);
-        if dist != usize::MAX {
-            for i in graph.nodes[v]..graph.nodes[v + 1] {
-                let target = graph.edges[i].target;
-                let target_dist = distance[target].load(Ordering::Relaxed);
-                if target_dist > dist + 1 {
-                    failed = true;
-                }
-            }
-            let mut cur_max = max.load(Ordering::Relaxed);
-            while dist > cur_max {
-                match max.compare_exchange_weak(cur_max, dist, Ordering::SeqCst, Ordering::Relaxed)
-                {
-                    Ok(_) => {
-                        break;
-                    }
-                    Err(new_max) => cur_max = new_max,
-                }
-            }
-        }
-    });
-
-    if failed {
-        println!("Verification Failed");
-    } else {
-        println!("Verification Success");
-    }
-    println!("max distance: {:.6}", max.load(Ordering::Relaxed));
-}
-
-fn write_distance<P: AsRef<Path>>(path: P, distance: &[AtomicUsize]) {
-    let mut file = std::fs::File::create(path).unwrap();
-    distance.iter().for_each(|x| {
-        writeln!(file, "{}", x.load(Ordering::Relaxed)).unwrap();
-    });
-}
+use multiqueue::QueueSelection;
+use rpb_bench::{BenchReport, ReportArgs, Samples};
 
 #[derive(Parser)]
 struct Args {
@@ -174,50 +49,76 @@ struct Args {
     start_node: Option<usize>,
     #[arg(short, long)]
     verify: bool,
+    /// the number of internal queues per thread
+    #[arg(short = 'c', long, default_value_t = multiqueue::DEFAULT_C)]
+    queues_per_thread: usize,
+    /// the number of core groups (e.g. sockets) to split the queues into
+    #[arg(long, default_value = "1")]
+    numa_groups: usize,
+    /// the probability of picking a queue outside the thread's group
+    #[arg(long, default_value = "0.1")]
+    steal_prob: f64,
+    /// pin the i-th thread to the i-th core
+    #[arg(long)]
+    pin: bool,
+    #[command(flatten)]
+    report: ReportArgs,
+}
+
+impl Args {
+    fn queue_options(&self) -> QueueOptions {
+        QueueOptions {
+            threads: self.threads,
+            queues_per_thread: self.queues_per_thread,
+            selection: if self.numa_groups > 1 {
+                QueueSelection::Grouped {
+                    groups: self.numa_groups,
+                    steal_prob: self.steal_prob,
+                }
+            } else {
+                QueueSelection::Uniform
+            },
+            pin: self.pin,
+        }
+    }
 }
 
 fn main() {
     let args = Args::parse();
-    let graph = Graph::from_file(args.file);
+    let graph = Graph::read(&args.file).expect("cannot read input file");
 
-    let mut data = SharedData {
-        shortest_distance: (0..graph.num_nodes())
-            .into_par_iter()
-            .map(|_| AtomicUsize::new(usize::MAX))
-            .collect(),
-        term_data: TerminationData::new(args.threads),
-    };
-    let mut times = vec![];
+    let opts = args.queue_options();
+
+    let mut dist = vec![];
+    let mut samples = Samples::default();
     for _ in 0..args.rounds {
         // initialization
-        let pq = MultiQueue::new(args.threads);
-        data.shortest_distance = (0..graph.num_nodes())
+        dist = (0..graph.num_vertices())
             .into_par_iter()
             .map(|_| AtomicUsize::new(usize::MAX))
             .collect();
-        data.term_data = TerminationData::new(args.threads);
 
         // run
-        let start = Instant::now();
-        launch_threads_and_wait(
-            &graph,
-            args.threads,
-            &pq,
-            &data,
-            args.start_node.unwrap_or(0),
-        );
-        let e = start.elapsed();
-        println!("bfs:\t{:.6}", e.as_secs_f64());
-        times.push(e);
+        let run = multiqueue_bfs(&graph, &dist, args.start_node.unwrap_or(0), &opts);
+        println!("bfs:\t{:.6}", run.elapsed.as_secs_f64());
+        samples.record(run.elapsed, None, run.perf);
     }
 
-    let mean = times.iter().sum::<std::time::Duration>() / times.len() as u32;
-    println!("mean: {:.6}s", mean.as_secs_f64());
+    println!("mean: {:.6}s", samples.mean().as_secs_f64());
+    if let Some(p) = samples.perf_per_round() {
+        println!("perf:\t{p} per round");
+    }
     if let Some(output) = args.output {
-        write_distance(output, &data.shortest_distance);
+        write_distance(output, &dist);
     }
 
-    if args.verify {
-        verify_distance(&graph, &data.shortest_distance);
-    }
+    let verdict = args.verify.then(|| verify_distance(&graph, &dist));
+    args.report.write(&BenchReport {
+        algorithm: "multiqueue".to_string(),
+        features: multiqueue::features(),
+        threads: args.threads,
+        input: args.file.clone(),
+        check: verdict,
+        ..BenchReport::new(env!("CARGO_BIN_NAME"), &samples)
+    });
 }
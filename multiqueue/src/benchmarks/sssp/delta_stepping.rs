@@ -0,0 +1,99 @@
+use clap::Parser;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+use std::time::Instant;
+
+use multiqueue::sssp::{delta_stepping, verify_distance, write_distance};
+use multiqueue::util::WghGraph as Graph;
+use rpb_bench::{BenchReport, ReportArgs, Samples};
+
+#[derive(Parser)]
+struct Args {
+    file: String,
+    #[arg(short, long)]
+    output: Option<String>,
+    #[arg(short, long, default_value = "1")]
+    rounds: usize,
+    /// the number of rayon threads (all the cores by default)
+    #[arg(short, long)]
+    threads: Option<usize>,
+    #[arg(long)]
+    start_node: Option<usize>,
+    #[arg(short, long)]
+    verify: bool,
+    /// the width of the distance buckets
+    #[arg(short, long, default_value = "32")]
+    delta: usize,
+    #[command(flatten)]
+    report: ReportArgs,
+}
+
+fn main() {
+    let args = Args::parse();
+    assert!(args.delta > 0);
+    let graph = Graph::read(&args.file).expect("cannot read input file");
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads.unwrap_or(0))
+        .build()
+        .unwrap();
+
+    let mut times = vec![];
+    let mut dist = vec![usize::MAX; graph.num_vertices()];
+    for _ in 0..args.rounds {
+        // initialization
+        dist.par_iter_mut().for_each(|x| *x = usize::MAX);
+
+        // run
+        let start = Instant::now();
+        pool.install(|| {
+            delta_stepping(&graph, &mut dist, args.start_node.unwrap_or(0), args.delta)
+        });
+        let e = start.elapsed();
+        println!("sssp:\t{:.6}", e.as_secs_f64());
+        times.push(e);
+    }
+
+    let samples = Samples {
+        rounds: times,
+        ..Samples::default()
+    };
+    println!("mean: {:.6}s", samples.mean().as_secs_f64());
+    if let Some(output) = args.output {
+        write_distance(output, &dist);
+    }
+
+    let verdict = args.verify.then(|| verify_distance(&graph, &dist));
+    args.report.write(&BenchReport {
+        algorithm: "delta_stepping".to_string(),
+        features: multiqueue::features(),
+        threads: pool.current_num_threads(),
+        input: args.file.clone(),
+        check: verdict,
+        ..BenchReport::new(env!("CARGO_BIN_NAME"), &samples)
+    });
+}
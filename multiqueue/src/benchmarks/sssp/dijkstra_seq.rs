@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use clap::Parser;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,48 +25,11 @@ use std::cmp::Ordering;
 // SOFTWARE.
 // ============================================================================
 
-use clap::Parser;
-use std::collections::BinaryHeap;
-use std::io::Write;
-use std::path::Path;
 use std::time::Instant;
 
+use multiqueue::sssp::{dijkstra, write_distance};
 use multiqueue::util::WghGraph as Graph;
-
-#[derive(Eq, PartialEq, Debug)]
-struct ValType(usize, usize);
-
-impl Ord for ValType {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.0.cmp(&self.0)
-    }
-}
-
-impl PartialOrd for ValType {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.0.cmp(&self.0))
-    }
-}
-
-fn dijkstra(graph: &Graph, pq: &mut BinaryHeap<ValType>, dist: &mut Vec<usize>) {
-    while let Some(val) = pq.pop() {
-        for i in graph.nodes[val.1]..graph.nodes[val.1 + 1] {
-            let target = graph.edges[i].target;
-            let new_distance = val.0 + graph.edges[i].weight;
-            if new_distance < dist[target] {
-                dist[target] = new_distance;
-                pq.push(ValType(new_distance, target));
-            }
-        }
-    }
-}
-
-fn write_distance<P: AsRef<Path>>(path: P, distance: &[usize]) {
-    let mut file = std::fs::File::create(path).unwrap();
-    distance
-        .iter()
-        .for_each(|x| writeln!(file, "{}", x).unwrap());
-}
+use rpb_bench::{BenchReport, ReportArgs, Samples};
 
 #[derive(Parser)]
 struct Args {
@@ -79,35 +42,42 @@ struct Args {
     solution: Option<String>,
     #[arg(long)]
     start_node: Option<usize>,
+    #[command(flatten)]
+    report: ReportArgs,
 }
 
 fn main() {
     let args = Args::parse();
-    let graph = Graph::from_file(args.file);
+    let graph = Graph::read(&args.file).expect("cannot read input file");
 
     let mut times = vec![];
-    let mut dist = vec![usize::MAX; graph.num_nodes()];
+    let mut dist = vec![usize::MAX; graph.num_vertices()];
     for _ in 0..args.rounds {
         // initialization
-        let mut pq = BinaryHeap::new();
         dist.iter_mut().for_each(|x| *x = usize::MAX);
 
-        // first node
-        let start_node = args.start_node.unwrap_or(0);
-        pq.push(ValType(0, start_node));
-        dist[start_node] = 0;
-
         // run
         let start = Instant::now();
-        dijkstra(&graph, &mut pq, &mut dist);
+        dijkstra(&graph, &mut dist, args.start_node.unwrap_or(0));
         let e = start.elapsed();
         println!("sssp:\t{:.6}", e.as_secs_f64());
         times.push(e);
     }
 
-    let mean = times.iter().sum::<std::time::Duration>() / times.len() as u32;
-    println!("mean: {:.6}s", mean.as_secs_f64());
+    let samples = Samples {
+        rounds: times,
+        ..Samples::default()
+    };
+    println!("mean: {:.6}s", samples.mean().as_secs_f64());
     if let Some(output) = args.output {
         write_distance(output, &dist);
     }
+
+    args.report.write(&BenchReport {
+        algorithm: "dijkstra".to_string(),
+        features: multiqueue::features(),
+        threads: 1,
+        input: args.file.clone(),
+        ..BenchReport::new(env!("CARGO_BIN_NAME"), &samples)
+    });
 }
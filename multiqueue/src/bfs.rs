@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::sssp::{QueueOptions, ValType};
+#[cfg(feature = "stats")]
+use crate::util::stats::WorkStats;
+use crate::util::Graph;
+use crate::{MultiQueue, PriorityExecutor, Pusher, RunStats};
+
+struct SharedData<'a> {
+    shortest_distance: &'a [AtomicUsize],
+    #[cfg(feature = "stats")]
+    stats: WorkStats,
+}
+
+fn process_node(
+    val: ValType,
+    graph: &Graph,
+    data: &SharedData,
+    pq: &Pusher<ValType, MultiQueue<ValType>>,
+) {
+    let (dist, src) = (val.0, val.1);
+
+    if data.shortest_distance[src].load(Ordering::Relaxed) < dist {
+        #[cfg(feature = "stats")]
+        data.stats.stale.incr();
+        return;
+    }
+    #[cfg(feature = "stats")]
+    data.stats.processed.incr();
+
+    let new_distance = dist + 1;
+    for &target in graph.index(src).neighbors {
+        let target = target as usize;
+        let mut old_distance = data.shortest_distance[target].load(Ordering::Relaxed);
+
+        while new_distance < old_distance {
+            match data.shortest_distance[target].compare_exchange_weak(
+                old_distance,
+                new_distance,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    pq.push(ValType(new_distance, target));
+                    #[cfg(feature = "stats")]
+                    data.stats.pushed.incr();
+                    break;
+                }
+                Err(x) => old_distance = x,
+            }
+        }
+    }
+}
+
+/// Breadth-first search on a multiqueue, prioritized by depth. `dist` must
+/// be all `usize::MAX`. Returns the statistics of the traversal.
+pub fn multiqueue_bfs(
+    graph: &Graph,
+    dist: &[AtomicUsize],
+    start_node: usize,
+    opts: &QueueOptions,
+) -> RunStats {
+    let pq = MultiQueue::with_config(opts.threads, opts.queues_per_thread, opts.selection);
+    let executor = PriorityExecutor::with_queue(opts.threads, pq).pin_threads(opts.pin);
+    let data = SharedData {
+        shortest_distance: dist,
+        #[cfg(feature = "stats")]
+        stats: WorkStats::new(opts.threads),
+    };
+    dist[start_node].store(0, Ordering::Relaxed);
+
+    let run = executor.run([ValType(0, start_node)], |val, pusher| {
+        process_node(val, graph, &data, pusher)
+    });
+    #[cfg(feature = "stats")]
+    println!("{}\n{}\n{}", executor.queue().stats(), data.stats, run);
+    run
+}
+
+// Verification method adopted from Galois
+pub fn verify_distance(graph: &Graph, distance: &[AtomicUsize]) -> bool {
+    let mut failed = false;
+    let max = AtomicUsize::new(0);
+    distance.iter().enumerate().for_each(|(v, x)| {
+        let dist = x.load(Ordering::Relaxed);
+        if dist != usize::MAX {
+            for &target in graph.index(v).neighbors {
+                let target = target as usize;
+                let target_dist = distance[target].load(Ordering::Relaxed);
+                if target_dist > dist + 1 {
+                    failed = true;
+                }
+            }
+            let mut cur_max = max.load(Ordering::Relaxed);
+            while dist > cur_max {
+                match max.compare_exchange_weak(cur_max, dist, Ordering::SeqCst, Ordering::Relaxed)
+                {
+                    Ok(_) => {
+                        break;
+                    }
+                    Err(new_max) => cur_max = new_max,
+                }
+            }
+        }
+    });
+
+    if failed {
+        println!("Verification Failed");
+    } else {
+        println!("Verification Success");
+    }
+    println!("max distance: {:.6}", max.load(Ordering::Relaxed));
+    !failed
+}
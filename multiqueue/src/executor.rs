@@ -0,0 +1,321 @@
+use std::cell::Cell;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Barrier;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rpb_bench::{PerfCounters, PerfStats};
+
+use crate::keyed_multiqueue::KeyedMultiQueue;
+use crate::multiqueue::MultiQueue;
+#[cfg(feature = "stats")]
+use crate::util::stats::QueueStats;
+use crate::util::termination_detection::{BackoffPolicy, TerminationDetector};
+
+/// A concurrent priority queue that a `PriorityExecutor` can schedule from.
+pub trait TaskQueue<T>: Sync {
+    fn push(&self, task: T);
+    fn pop(&self) -> Option<T>;
+    /// Called once by every worker thread before it touches the queue.
+    fn register_thread(&self, tid: usize);
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> &QueueStats;
+}
+
+impl<T: Ord + Copy + Send> TaskQueue<T> for MultiQueue<T> {
+    fn push(&self, task: T) {
+        MultiQueue::push(self, task);
+    }
+    fn pop(&self) -> Option<T> {
+        MultiQueue::pop(self)
+    }
+    fn register_thread(&self, tid: usize) {
+        MultiQueue::register_thread(self, tid);
+    }
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> &QueueStats {
+        MultiQueue::stats(self)
+    }
+}
+
+/// Tasks are `(key, priority)` pairs; pushing a queued key decreases its
+/// priority instead of adding a second task.
+impl<P: Ord + Copy + Send> TaskQueue<(usize, P)> for KeyedMultiQueue<P> {
+    fn push(&self, task: (usize, P)) {
+        self.push_or_decrease(task.0, task.1);
+    }
+    fn pop(&self) -> Option<(usize, P)> {
+        KeyedMultiQueue::pop(self)
+    }
+    fn register_thread(&self, tid: usize) {
+        KeyedMultiQueue::register_thread(self, tid);
+    }
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> &QueueStats {
+        KeyedMultiQueue::stats(self)
+    }
+}
+
+/// The handle a task uses to push new tasks. Every worker has its own.
+pub struct Pusher<'a, T, Q> {
+    queue: &'a Q,
+    pushed: Cell<u64>,
+    _task: PhantomData<fn(T)>,
+}
+
+impl<T, Q: TaskQueue<T>> Pusher<'_, T, Q> {
+    #[inline(always)]
+    pub fn push(&self, task: T) {
+        self.queue.push(task);
+        self.pushed.set(self.pushed.get() + 1);
+    }
+}
+
+/// The statistics of one `PriorityExecutor::run`.
+pub struct RunStats {
+    pub elapsed: Duration,
+    /// the number of tasks processed by each thread.
+    pub processed: Vec<u64>,
+    /// the number of tasks pushed by each thread (initial tasks excluded).
+    pub pushed: Vec<u64>,
+    /// the hardware events of the worker threads, with the `perf` feature.
+    pub perf: Option<PerfStats>,
+}
+
+impl RunStats {
+    pub fn total_processed(&self) -> u64 {
+        self.processed.iter().sum()
+    }
+
+    pub fn total_pushed(&self) -> u64 {
+        self.pushed.iter().sum()
+    }
+}
+
+impl fmt::Display for RunStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max = self.processed.iter().copied().max().unwrap_or(0);
+        let mean = self.total_processed() as f64 / self.processed.len().max(1) as f64;
+        writeln!(f, "tasks:\t{}", self.total_processed())?;
+        writeln!(f, "pushed tasks:\t{}", self.total_pushed())?;
+        write!(f, "imbalance:\t{:.2}", max as f64 / mean.max(1.0))
+    }
+}
+
+/// The processed and pushed tasks and the hardware events of one worker.
+type WorkerStats = ((u64, u64), Option<PerfStats>);
+
+/// Runs priority-scheduled tasks on `num_threads` threads until no task is
+/// left: every thread repeatedly pops a task from the shared queue and hands
+/// it to `process`, which may push more tasks.
+pub struct PriorityExecutor<T, Q = MultiQueue<T>> {
+    queue: Q,
+    num_threads: usize,
+    pin_threads: bool,
+    backoff: BackoffPolicy,
+    _task: PhantomData<fn(T)>,
+}
+
+impl<T: Ord + Copy + Send> PriorityExecutor<T> {
+    /// Creates an executor over a default `MultiQueue`.
+    pub fn new(num_threads: usize) -> Self {
+        Self::with_queue(num_threads, MultiQueue::new(num_threads))
+    }
+}
+
+impl<T: Send, Q: TaskQueue<T>> PriorityExecutor<T, Q> {
+    /// Creates an executor over `queue`, which must be set up for (at least)
+    /// `num_threads` threads.
+    pub fn with_queue(num_threads: usize, queue: Q) -> Self {
+        assert!(num_threads > 0);
+        Self {
+            queue,
+            num_threads,
+            pin_threads: false,
+            backoff: BackoffPolicy::Spin,
+            _task: PhantomData,
+        }
+    }
+
+    /// Pins the `i`-th thread to the `i`-th core.
+    pub fn pin_threads(mut self, pin: bool) -> Self {
+        self.pin_threads = pin;
+        self
+    }
+
+    /// Sets what idle threads do while waiting for work or termination.
+    pub fn backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.backoff = policy;
+        self
+    }
+
+    pub fn queue(&self) -> &Q {
+        &self.queue
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Pins the calling thread, thread `tid` of the run, if `pin_threads`.
+    fn pin(&self, tid: usize) {
+        if self.pin_threads {
+            let core = tid % affinity::get_core_num();
+            if let Err(e) = affinity::set_thread_affinity([core]) {
+                eprintln!("Warning: can not pin thread {tid} to core {core}: {e}");
+            }
+        }
+    }
+
+    fn main_loop<F>(&self, tid: usize, termination: &TerminationDetector, process: &F) -> (u64, u64)
+    where
+        F: Fn(T, &Pusher<T, Q>) + Sync,
+    {
+        self.queue.register_thread(tid);
+
+        let pusher = Pusher {
+            queue: &self.queue,
+            pushed: Cell::new(0),
+            _task: PhantomData,
+        };
+        let mut processed = 0;
+        while let Some(task) = termination.try_do(|| self.queue.pop()) {
+            process(task, &pusher);
+            processed += 1;
+        }
+        (processed, pusher.pushed.get())
+    }
+
+    /// Times pushing the `initial` tasks and running the threads, and returns
+    /// the counts of `main_loop` and the hardware events of each thread.
+    /// Every thread pins itself and opens its own counters before the clock
+    /// starts.
+    fn launch_threads_and_wait<I, F>(&self, initial: I, process: &F) -> (Duration, Vec<WorkerStats>)
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(T, &Pusher<T, Q>) + Sync,
+    {
+        let termination = TerminationDetector::new(self.num_threads).with_backoff(self.backoff);
+        let opened = Barrier::new(self.num_threads + 1);
+        let pushed = Barrier::new(self.num_threads + 1);
+        thread::scope(|s| {
+            let handles: Vec<_> = (0..self.num_threads)
+                .map(|tid| {
+                    let (termination, opened, pushed) = (&termination, &opened, &pushed);
+                    s.spawn(move || {
+                        self.pin(tid);
+                        let mut counters = PerfCounters::this_thread();
+                        opened.wait();
+                        pushed.wait();
+                        counters.start();
+                        let counts = self.main_loop(tid, termination, process);
+                        (counts, counters.stop())
+                    })
+                })
+                .collect();
+            opened.wait();
+            let start = Instant::now();
+            initial.into_iter().for_each(|task| self.queue.push(task));
+            pushed.wait();
+            let threads = handles.into_iter().map(|h| h.join().unwrap()).collect();
+            (start.elapsed(), threads)
+        })
+    }
+
+    /// Pushes the `initial` tasks and processes tasks until none is left.
+    /// The queue is empty again afterwards, so `run` can be called again.
+    pub fn run<I, F>(&self, initial: I, process: F) -> RunStats
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(T, &Pusher<T, Q>) + Sync,
+    {
+        let (elapsed, threads) = self.launch_threads_and_wait(initial, &process);
+        RunStats {
+            elapsed,
+            processed: threads.iter().map(|t| t.0 .0).collect(),
+            pushed: threads.iter().map(|t| t.0 .1).collect(),
+            perf: threads.iter().filter_map(|t| t.1).reduce(|mut a, b| {
+                a.merge(b);
+                a
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod executor_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn processes_all_tasks() {
+        // every task n > 0 spawns two tasks n - 1.
+        let sum = AtomicU64::new(0);
+        let exec = PriorityExecutor::<u32>::new(4);
+        let stats = exec.run([10], |n, pusher| {
+            sum.fetch_add(n as u64, Ordering::Relaxed);
+            if n > 0 {
+                pusher.push(n - 1);
+                pusher.push(n - 1);
+            }
+        });
+        assert_eq!(stats.total_processed(), (1 << 11) - 1);
+        assert_eq!(stats.total_pushed(), (1 << 11) - 2);
+        let expected: u64 = (0..=10).map(|n| n << (10 - n)).sum();
+        assert_eq!(sum.load(Ordering::Relaxed), expected);
+        assert_eq!(exec.queue().pop(), None);
+
+        // the executor can be reused.
+        let stats = exec.run(0..100, |_, _| {});
+        assert_eq!(stats.total_processed(), 100);
+    }
+
+    #[test]
+    fn keyed_queue() {
+        let exec = PriorityExecutor::with_queue(2, KeyedMultiQueue::<u32>::new(2, 10));
+        // key 0 reaches every other key twice, and the smaller distance replaces
+        // the larger one if it is still in the queue. The other thread may pop
+        // the larger one first, so a key is processed once or twice, but always
+        // with the smaller distance in the end.
+        let dist: Vec<_> = (0..10).map(|_| AtomicU64::new(u64::MAX)).collect();
+        let stats = exec.run([(0, 0)], |(key, d), pusher| {
+            dist[key].fetch_min(d as u64, Ordering::Relaxed);
+            if key == 0 {
+                for k in 1..10 {
+                    pusher.push((k, 100));
+                    pusher.push((k, 1));
+                }
+            }
+        });
+        let dist: Vec<_> = dist.into_iter().map(AtomicU64::into_inner).collect();
+        assert_eq!(dist, [0, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert!((10..=19).contains(&stats.total_processed()));
+    }
+}
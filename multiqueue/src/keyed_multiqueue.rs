@@ -0,0 +1,342 @@
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::{Mutex, MutexGuard};
+
+use crate::multiqueue::{QueueSelection, DEFAULT_C};
+#[cfg(feature = "stats")]
+use crate::util::stats::{QueueStats, RANK_SAMPLE_RATE};
+use crate::util::Padded;
+
+/// Marks a key without a live entry in `owner`.
+const NO_QUEUE: usize = usize::MAX;
+
+/// A binary min-heap of `(priority, key)` entries, which records the position
+/// of every entry in `pos` so that its priority can be decreased in place.
+type Heap<P> = Vec<(P, usize)>;
+
+/// A relaxed min-priority queue over the keys `0..num_keys`, which holds at
+/// most one live entry per key.
+///
+/// Every key has two atomic slots: the internal queue that holds its entry and
+/// the entry's position inside that queue's heap. Both slots are only written
+/// while holding the lock of the queue that holds (or receives) the entry.
+pub struct KeyedMultiQueue<P> {
+    pq_list: Vec<Padded<Mutex<Heap<P>>>>,
+    pq_list_size: usize,
+    num_empty: AtomicU32,
+    num_threads: usize,
+    selection: QueueSelection,
+    owner: Vec<AtomicUsize>,
+    pos: Vec<AtomicUsize>,
+    #[cfg(feature = "stats")]
+    stats: QueueStats,
+}
+
+impl<P: Ord + Copy> KeyedMultiQueue<P> {
+    pub fn new(num_threads: usize, num_keys: usize) -> Self {
+        Self::with_config(num_threads, num_keys, DEFAULT_C, QueueSelection::Uniform)
+    }
+
+    /// Creates a keyed MultiQueue with `queues_per_thread` internal queues per
+    /// thread that are picked according to `selection`.
+    pub fn with_config(
+        num_threads: usize,
+        num_keys: usize,
+        queues_per_thread: usize,
+        selection: QueueSelection,
+    ) -> Self {
+        assert!(num_threads > 0);
+        assert!(queues_per_thread > 0);
+        let pq_list_size = num_threads * queues_per_thread;
+        selection.validate(pq_list_size);
+        Self {
+            pq_list: (0..pq_list_size)
+                .map(|_| Padded::new(Mutex::new(Vec::new())))
+                .collect(),
+            pq_list_size,
+            num_empty: AtomicU32::new(pq_list_size as u32),
+            num_threads,
+            selection,
+            owner: (0..num_keys).map(|_| AtomicUsize::new(NO_QUEUE)).collect(),
+            pos: (0..num_keys).map(|_| AtomicUsize::new(0)).collect(),
+            #[cfg(feature = "stats")]
+            stats: QueueStats::new(num_threads),
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &QueueStats {
+        &self.stats
+    }
+
+    /// See `MultiQueue::register_thread`.
+    pub fn register_thread(&self, tid: usize) {
+        self.selection.register_thread(tid, self.num_threads);
+    }
+
+    #[allow(mismatched_lifetime_syntaxes)]
+    fn lock_a_queue(&self, steal: bool) -> (MutexGuard<Heap<P>>, usize) {
+        loop {
+            let index = self.selection.pick(self.pq_list_size, steal);
+            if let Ok(pq) = self.pq_list[index].try_lock() {
+                return (pq, index);
+            }
+        }
+    }
+
+    #[allow(mismatched_lifetime_syntaxes)]
+    fn lock_a_queue_except(&self, except: usize, steal: bool) -> (MutexGuard<Heap<P>>, usize) {
+        loop {
+            let index = self.selection.pick(self.pq_list_size, steal);
+            if index == except {
+                continue;
+            } else if let Ok(pq) = self.pq_list[index].try_lock() {
+                return (pq, index);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn set(&self, heap: &mut Heap<P>, i: usize, entry: (P, usize)) {
+        heap[i] = entry;
+        self.pos[entry.1].store(i, Ordering::Relaxed);
+    }
+
+    fn sift_up(&self, heap: &mut Heap<P>, mut i: usize) {
+        let entry = heap[i];
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if heap[parent].0 <= entry.0 {
+                break;
+            }
+            self.set(heap, i, heap[parent]);
+            i = parent;
+        }
+        self.set(heap, i, entry);
+    }
+
+    fn sift_down(&self, heap: &mut Heap<P>, mut i: usize) {
+        let entry = heap[i];
+        loop {
+            let mut child = 2 * i + 1;
+            if child >= heap.len() {
+                break;
+            }
+            if child + 1 < heap.len() && heap[child + 1].0 < heap[child].0 {
+                child += 1;
+            }
+            if entry.0 <= heap[child].0 {
+                break;
+            }
+            self.set(heap, i, heap[child]);
+            i = child;
+        }
+        self.set(heap, i, entry);
+    }
+
+    /// Inserts `key` with `priority`, or lowers the priority of its live
+    /// entry if `priority` is smaller. Returns `false` if the key already had
+    /// an entry with a priority that is not larger than `priority`.
+    pub fn push_or_decrease(&self, key: usize, priority: P) -> bool {
+        loop {
+            let owner = self.owner[key].load(Ordering::Acquire);
+            if owner == NO_QUEUE {
+                let (mut pq, index) = self.lock_a_queue(false);
+                if self.owner[key]
+                    .compare_exchange(NO_QUEUE, index, Ordering::AcqRel, Ordering::Relaxed)
+                    .is_err()
+                {
+                    // someone else inserted the key in the meantime.
+                    continue;
+                }
+                if pq.is_empty() {
+                    self.num_empty.fetch_sub(1, Ordering::Relaxed);
+                }
+                pq.push((priority, key));
+                let last = pq.len() - 1;
+                self.sift_up(&mut pq, last);
+                #[cfg(feature = "stats")]
+                self.stats.pushes.incr();
+                return true;
+            }
+
+            let mut pq = self.pq_list[owner].lock().unwrap();
+            if self.owner[key].load(Ordering::Acquire) != owner {
+                // the entry was popped before we got the lock.
+                continue;
+            }
+            let i = self.pos[key].load(Ordering::Relaxed);
+            debug_assert_eq!(pq[i].1, key);
+            if priority < pq[i].0 {
+                pq[i].0 = priority;
+                self.sift_up(&mut pq, i);
+                return true;
+            }
+            return false;
+        }
+    }
+
+    /// Counts the entries in all the queues that are better than `priority`.
+    #[cfg(feature = "stats")]
+    fn rank_of(&self, priority: &P) -> u64 {
+        self.pq_list
+            .iter()
+            .map(|q| q.lock().unwrap().iter().filter(|e| e.0 < *priority).count() as u64)
+            .sum()
+    }
+
+    /// Removes and returns the `(key, priority)` of an entry with a small
+    /// priority, or `None` when all the internal queues are empty.
+    pub fn pop(&self) -> Option<(usize, P)> {
+        let mut steal = false;
+        loop {
+            let (q_1, idx_1) = self.lock_a_queue(steal);
+            let val_1 = q_1.first().map(|e| e.0);
+            drop(q_1);
+
+            let (q_2, idx_2) = self.lock_a_queue_except(idx_1, steal);
+            let val_2 = q_2.first().map(|e| e.0);
+            drop(q_2);
+
+            let selected = match (val_1, val_2) {
+                (None, None) => {
+                    if self.num_empty.load(Ordering::Relaxed) == self.pq_list_size as u32 {
+                        #[cfg(feature = "stats")]
+                        self.stats.empty_pops.incr();
+                        return None;
+                    } else {
+                        #[cfg(feature = "stats")]
+                        self.stats.empty_retries.incr();
+                        steal = true;
+                        continue;
+                    }
+                }
+                (Some(val_1), Some(val_2)) => {
+                    if val_1 < val_2 {
+                        idx_1
+                    } else {
+                        idx_2
+                    }
+                }
+                (Some(_), None) => idx_1,
+                (None, Some(_)) => idx_2,
+            };
+
+            let mut q = self.pq_list[selected].lock().unwrap();
+            let Some(&(priority, key)) = q.first() else {
+                continue;
+            };
+            let last = q.pop().unwrap();
+            if !q.is_empty() {
+                self.set(&mut q, 0, last);
+                self.sift_down(&mut q, 0);
+            } else {
+                self.num_empty.fetch_add(1, Ordering::Relaxed);
+            }
+            self.owner[key].store(NO_QUEUE, Ordering::Release);
+            #[cfg(feature = "stats")]
+            if self.stats.pops.incr().is_multiple_of(RANK_SAMPLE_RATE) {
+                drop(q);
+                self.stats.add_rank_sample(self.rank_of(&priority));
+            }
+            return Some((key, priority));
+        }
+    }
+}
+
+#[cfg(test)]
+mod keyed_multiqueue_tests {
+    use super::*;
+
+    #[test]
+    fn single_thread() {
+        let pq = KeyedMultiQueue::<u32>::new(1, 10);
+        assert!(pq.push_or_decrease(1, 30));
+        assert!(pq.push_or_decrease(2, 20));
+        assert!(pq.push_or_decrease(1, 10));
+        assert!(!pq.push_or_decrease(2, 25));
+
+        let mut rets = vec![];
+        while let Some(elem) = pq.pop() {
+            rets.push(elem);
+        }
+        rets.sort();
+        assert_eq!(rets, vec![(1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn one_entry_per_key() {
+        let num_keys = 100;
+        let pq = KeyedMultiQueue::<u32>::new(4, num_keys);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let pq = &pq;
+                s.spawn(move || {
+                    pq.register_thread(t);
+                    for p in (0..50).rev() {
+                        for k in 0..num_keys {
+                            pq.push_or_decrease(k, 4 * p + t as u32);
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut rets = vec![];
+        while let Some(elem) = pq.pop() {
+            rets.push(elem);
+        }
+        rets.sort();
+        assert_eq!(rets, (0..num_keys).map(|k| (k, 0)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn decrease_keeps_heaps_and_positions() {
+        let pq = KeyedMultiQueue::<u32>::with_config(1, 1000, 2, QueueSelection::Uniform);
+        for k in 0..1000 {
+            pq.push_or_decrease(k, (k as u32 * 7919) % 1000);
+        }
+        for k in 0..1000 {
+            pq.push_or_decrease(k, (k as u32 * 7919) % 1000 / 2);
+        }
+        let heaps_ok = pq.pq_list.iter().all(|q| {
+            let q = q.lock().unwrap();
+            (1..q.len()).all(|i| q[(i - 1) / 2].0 <= q[i].0)
+                && q.iter()
+                    .enumerate()
+                    .all(|(i, e)| pq.pos[e.1].load(Ordering::Relaxed) == i)
+        });
+        assert!(heaps_ok);
+        let mut count = 0;
+        while pq.pop().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1000);
+    }
+}
@@ -25,6 +25,28 @@ mod multiqueue;
 // SOFTWARE.
 // ============================================================================
 
-pub use crate::multiqueue::MultiQueue;
+mod executor;
+mod keyed_multiqueue;
 
+pub use crate::executor::{PriorityExecutor, Pusher, RunStats, TaskQueue};
+pub use crate::keyed_multiqueue::KeyedMultiQueue;
+pub use crate::multiqueue::{MultiQueue, QueueSelection, DEFAULT_C};
+
+pub mod bfs;
+pub mod sssp;
+pub mod suite;
 pub mod util;
+
+/// The features of this crate that are enabled, for benchmark reports.
+pub fn features() -> Vec<String> {
+    [
+        ("sng_ind_safe", cfg!(feature = "sng_ind_safe")),
+        ("sng_ind_unsafe", cfg!(feature = "sng_ind_unsafe")),
+        ("stats", cfg!(feature = "stats")),
+        ("perf", cfg!(feature = "perf")),
+    ]
+    .into_iter()
+    .filter(|(_, on)| *on)
+    .map(|(f, _)| f.to_string())
+    .collect()
+}
@@ -25,40 +25,139 @@ use std::collections::BinaryHeap;
 // SOFTWARE.
 // ============================================================================
 
+use std::cell::Cell;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Mutex, MutexGuard};
 
 #[allow(deprecated)]
 use rand::{thread_rng, Rng};
 
+#[cfg(feature = "stats")]
+use crate::util::stats::{self, QueueStats, RANK_SAMPLE_RATE};
 use crate::util::Padded;
 
-const C: usize = 4;
+/// The default number of internal queues per thread.
+pub const DEFAULT_C: usize = 4;
+
+/// How `push` and `pop` pick the internal queues they lock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueueSelection {
+    /// Every internal queue is equally likely to be picked.
+    Uniform,
+    /// The internal queues are split into `groups` contiguous blocks and every
+    /// thread owns the block of its group (see `MultiQueue::register_thread`).
+    /// A pick stays in the owned block, except with probability `steal_prob`
+    /// where it is made uniformly over all the queues. `pop` also falls back to
+    /// a uniform pick after it finds the owned block empty.
+    /// A thread that never registers counts as a member of group 0.
+    Grouped { groups: usize, steal_prob: f64 },
+}
+
+thread_local! {
+    static THREAD_GROUP: Cell<usize> = const { Cell::new(0) };
+}
+
+impl QueueSelection {
+    /// Checks that `num_queues` internal queues can be picked from.
+    pub(crate) fn validate(&self, num_queues: usize) {
+        // `pop` needs two distinct queues to choose from.
+        assert!(num_queues > 1);
+        if let QueueSelection::Grouped { groups, steal_prob } = *self {
+            assert!(groups > 0 && num_queues >= 2 * groups);
+            assert!((0.0..=1.0).contains(&steal_prob));
+        }
+    }
+
+    /// Assigns the calling thread, the `tid`-th of `num_threads`, to a group.
+    pub(crate) fn register_thread(&self, tid: usize, num_threads: usize) {
+        debug_assert!(tid < num_threads);
+        #[cfg(feature = "stats")]
+        stats::set_thread_slot(tid);
+        if let QueueSelection::Grouped { groups, .. } = *self {
+            THREAD_GROUP.with(|g| g.set(tid * groups / num_threads));
+        }
+    }
+
+    /// Picks one of `num_queues` internal queues for the calling thread.
+    /// `steal` forces a uniform pick.
+    #[allow(deprecated)]
+    pub(crate) fn pick(&self, num_queues: usize, steal: bool) -> usize {
+        match *self {
+            QueueSelection::Uniform => thread_rng().gen_range(0..num_queues),
+            QueueSelection::Grouped { groups, steal_prob } => {
+                let mut rng = thread_rng();
+                if steal || (steal_prob > 0.0 && rng.gen_bool(steal_prob)) {
+                    return rng.gen_range(0..num_queues);
+                }
+                let group = THREAD_GROUP.with(Cell::get) % groups;
+                let lo = group * num_queues / groups;
+                let hi = (group + 1) * num_queues / groups;
+                rng.gen_range(lo..hi)
+            }
+        }
+    }
+}
 
 pub struct MultiQueue<PQElem> {
     pq_list: Vec<Padded<Mutex<BinaryHeap<PQElem>>>>,
     pq_list_size: usize,
     num_empty: AtomicU32,
+    num_threads: usize,
+    selection: QueueSelection,
+    #[cfg(feature = "stats")]
+    stats: QueueStats,
 }
 
 impl<PQElem: Ord + Copy> MultiQueue<PQElem> {
     pub fn new(num_threads: usize) -> Self {
+        Self::with_config(num_threads, DEFAULT_C, QueueSelection::Uniform)
+    }
+
+    /// Creates a MultiQueue with `queues_per_thread` internal queues per
+    /// thread that are picked according to `selection`.
+    /// With `QueueSelection::Grouped`, every thread that pushes or pops should
+    /// call `register_thread` first; the others all share group 0.
+    pub fn with_config(
+        num_threads: usize,
+        queues_per_thread: usize,
+        selection: QueueSelection,
+    ) -> Self {
         assert!(num_threads > 0);
-        let pq_list_size = num_threads * C;
+        assert!(queues_per_thread > 0);
+        let pq_list_size = num_threads * queues_per_thread;
+        selection.validate(pq_list_size);
         Self {
             pq_list: (0..pq_list_size)
                 .map(|_| Padded::new(Mutex::new(BinaryHeap::new())))
                 .collect::<Vec<_>>(),
             pq_list_size,
             num_empty: AtomicU32::new(pq_list_size as u32),
+            num_threads,
+            selection,
+            #[cfg(feature = "stats")]
+            stats: QueueStats::new(num_threads),
         }
     }
 
-    #[allow(mismatched_lifetime_syntaxes, deprecated)]
-    fn lock_a_queue(&self) -> (MutexGuard<BinaryHeap<PQElem>>, usize) {
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &QueueStats {
+        &self.stats
+    }
+
+    /// Assigns the calling thread, the `tid`-th of `num_threads`, to a group.
+    /// Threads are spread over the groups compactly, so consecutive thread ids
+    /// (which are usually pinned to neighbouring cores) share a group.
+    /// A thread that never calls it stays in group 0, so with many such
+    /// threads group 0 gets all their load.
+    pub fn register_thread(&self, tid: usize) {
+        self.selection.register_thread(tid, self.num_threads);
+    }
+
+    #[allow(mismatched_lifetime_syntaxes)]
+    fn lock_a_queue(&self, steal: bool) -> (MutexGuard<BinaryHeap<PQElem>>, usize) {
         let mut index;
         let q = loop {
-            index = thread_rng().gen_range(0..self.pq_list_size);
+            index = self.selection.pick(self.pq_list_size, steal);
             if let Ok(pq) = self.pq_list[index].try_lock() {
                 break pq;
             }
@@ -66,11 +165,15 @@ impl<PQElem: Ord + Copy> MultiQueue<PQElem> {
         (q, index)
     }
 
-    #[allow(mismatched_lifetime_syntaxes, deprecated)]
-    fn lock_a_queue_except(&self, except: usize) -> (MutexGuard<BinaryHeap<PQElem>>, usize) {
+    #[allow(mismatched_lifetime_syntaxes)]
+    fn lock_a_queue_except(
+        &self,
+        except: usize,
+        steal: bool,
+    ) -> (MutexGuard<BinaryHeap<PQElem>>, usize) {
         let mut index;
         let q = loop {
-            index = thread_rng().gen_range(0..self.pq_list_size);
+            index = self.selection.pick(self.pq_list_size, steal);
             if index == except {
                 continue;
             } else if let Ok(pq) = self.pq_list[index].try_lock() {
@@ -81,59 +184,71 @@ impl<PQElem: Ord + Copy> MultiQueue<PQElem> {
     }
 
     pub fn push(&self, elem: PQElem) {
-        let (mut pq, _) = self.lock_a_queue();
+        let (mut pq, _) = self.lock_a_queue(false);
         if pq.is_empty() {
             self.num_empty.fetch_sub(1, Ordering::Relaxed);
         }
         pq.push(elem);
+        #[cfg(feature = "stats")]
+        self.stats.pushes.incr();
+    }
+
+    /// Counts the elements in all the queues that are better than `elem`.
+    /// The queues are locked one at a time, so the result is only a snapshot.
+    #[cfg(feature = "stats")]
+    fn rank_of(&self, elem: &PQElem) -> u64 {
+        self.pq_list
+            .iter()
+            .map(|q| q.lock().unwrap().iter().filter(|e| *e > elem).count() as u64)
+            .sum()
     }
 
     // TODO: Mark suggested trying ray's pop.
     pub fn pop(&self) -> Option<PQElem> {
+        let mut steal = false;
         loop {
-            let (mut val_1, mut val_2) = unsafe {
-                (
-                    std::mem::MaybeUninit::<PQElem>::uninit().assume_init(),
-                    std::mem::MaybeUninit::<PQElem>::uninit().assume_init(),
-                )
-            };
-
-            let (q_1, idx_1) = self.lock_a_queue();
-            let empty_1 = q_1.is_empty();
-            if !empty_1 {
-                val_1 = *q_1.peek().unwrap();
-            };
+            let (q_1, idx_1) = self.lock_a_queue(steal);
+            let val_1 = q_1.peek().copied();
             drop(q_1);
 
-            let (q_2, idx_2) = self.lock_a_queue_except(idx_1);
-            let empty_2 = q_2.is_empty();
-            if !empty_2 {
-                val_2 = *q_2.peek().unwrap();
-            };
+            let (q_2, idx_2) = self.lock_a_queue_except(idx_1, steal);
+            let val_2 = q_2.peek().copied();
             drop(q_2);
 
-            let selected;
-            if empty_1 && empty_2 {
-                if self.num_empty.load(Ordering::Relaxed) == self.pq_list_size as u32 {
-                    return None;
-                } else {
-                    continue;
+            let selected = match (val_1, val_2) {
+                (None, None) => {
+                    if self.num_empty.load(Ordering::Relaxed) == self.pq_list_size as u32 {
+                        #[cfg(feature = "stats")]
+                        self.stats.empty_pops.incr();
+                        return None;
+                    } else {
+                        #[cfg(feature = "stats")]
+                        self.stats.empty_retries.incr();
+                        steal = true;
+                        continue;
+                    }
                 }
-            } else if !empty_1 {
-                if !empty_2 {
-                    selected = if val_1 > val_2 { idx_1 } else { idx_2 }
-                } else {
-                    selected = idx_1;
+                (Some(val_1), Some(val_2)) => {
+                    if val_1 > val_2 {
+                        idx_1
+                    } else {
+                        idx_2
+                    }
                 }
-            } else {
-                selected = idx_2;
-            }
+                (Some(_), None) => idx_1,
+                (None, Some(_)) => idx_2,
+            };
 
             let mut q = self.pq_list[selected].lock().unwrap();
             if let Some(ret) = q.pop() {
                 if q.is_empty() {
                     self.num_empty.fetch_add(1, Ordering::Relaxed);
                 }
+                #[cfg(feature = "stats")]
+                if self.stats.pops.incr().is_multiple_of(RANK_SAMPLE_RATE) {
+                    drop(q);
+                    self.stats.add_rank_sample(self.rank_of(&ret));
+                }
                 return Some(ret);
             } else {
                 continue;
@@ -203,4 +318,38 @@ mod multiqueue_tests {
         rets1.sort();
         assert_eq!(rets1, vec![1, 2, 3, 4, 5, 6]);
     }
+
+    #[test]
+    fn grouped_without_stealing() {
+        let selection = QueueSelection::Grouped {
+            groups: 2,
+            steal_prob: 0.0,
+        };
+        let pq = MultiQueue::<u32>::with_config(2, 2, selection);
+
+        // the first thread only ever touches the first group ...
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                pq.register_thread(0);
+                (0..100).for_each(|i| pq.push(i));
+            });
+        });
+        assert!(pq.pq_list[..2]
+            .iter()
+            .all(|q| !q.lock().unwrap().is_empty()));
+        assert!(pq.pq_list[2..].iter().all(|q| q.lock().unwrap().is_empty()));
+
+        // ... but the second still finds them once its own group runs dry.
+        let mut rets = vec![];
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                pq.register_thread(1);
+                while let Some(elem) = pq.pop() {
+                    rets.push(elem);
+                }
+            });
+        });
+        rets.sort();
+        assert_eq!(rets, (0..100).collect::<Vec<_>>());
+    }
 }
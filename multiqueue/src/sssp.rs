@@ -0,0 +1,360 @@
+use std::io::Write;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use enhanced_rayon::prelude::*;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "stats")]
+use crate::util::stats::{QueueStats, WorkStats};
+use crate::util::WghGraph;
+use crate::{
+    KeyedMultiQueue, MultiQueue, PriorityExecutor, Pusher, QueueSelection, RunStats, TaskQueue,
+};
+
+/// A tentative distance, either owned by one thread or shared by all of them.
+pub trait Distance: Sync {
+    fn get(&self) -> usize;
+}
+
+impl Distance for usize {
+    fn get(&self) -> usize {
+        *self
+    }
+}
+
+impl Distance for AtomicUsize {
+    fn get(&self) -> usize {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+// Verification method adopted from Galois
+pub fn verify_distance<D: Distance>(graph: &WghGraph, distance: &[D]) -> bool {
+    let mut failed = false;
+    let max = AtomicUsize::new(0);
+    distance.iter().enumerate().for_each(|(v, x)| {
+        let dist = x.get();
+        if dist != usize::MAX {
+            let node = graph.index(v);
+            for (&target, &weight) in node.neighbors.iter().zip(node.weights) {
+                let target_dist = distance[target as usize].get();
+                if target_dist > dist + weight as usize {
+                    failed = true;
+                }
+            }
+            let mut cur_max = max.load(Ordering::Relaxed);
+            while dist > cur_max {
+                match max.compare_exchange_weak(cur_max, dist, Ordering::SeqCst, Ordering::Relaxed)
+                {
+                    Ok(_) => {
+                        break;
+                    }
+                    Err(new_max) => cur_max = new_max,
+                }
+            }
+        }
+    });
+
+    if failed {
+        println!("Verification Failed");
+    } else {
+        println!("Verification Success");
+    }
+    println!("max distance: {:.6}", max.load(Ordering::Relaxed));
+    !failed
+}
+
+pub fn write_distance<P: AsRef<Path>, D: Distance>(path: P, distance: &[D]) {
+    let mut file = std::fs::File::create(path).unwrap();
+    distance.iter().for_each(|x| {
+        writeln!(file, "{}", x.get()).unwrap();
+    });
+}
+
+/// Relaxes all the out-edges of `frontier` and returns the (unique) nodes
+/// whose distance improved.
+///
+/// The relaxations are first collected as `(target, distance)` requests and
+/// the best request per target is then written through a single indirection,
+/// so no atomics are needed.
+pub fn relax_frontier(graph: &WghGraph, dist: &mut [usize], frontier: &[usize]) -> Vec<usize> {
+    let mut requests: Vec<(usize, usize)> = {
+        let dist = &*dist;
+        frontier
+            .par_iter()
+            .flat_map_iter(|&u| {
+                let du = dist[u];
+                let node = graph.index(u);
+                node.neighbors
+                    .iter()
+                    .zip(node.weights)
+                    .map(move |(&t, &w)| (t as usize, du + w as usize))
+                    .filter(move |&(t, d)| d < dist[t])
+            })
+            .collect()
+    };
+    requests.par_sort_unstable();
+
+    // the first request of every target is its best one.
+    let (targets, new_dist): (Vec<usize>, Vec<usize>) = (0..requests.len())
+        .into_par_iter()
+        .filter(|&i| i == 0 || requests[i - 1].0 != requests[i].0)
+        .map(|i| requests[i])
+        .unzip();
+
+    dist.par_ind_iter_mut(&targets)
+        .zip(new_dist.par_iter())
+        .for_each(|(d, &nd)| *d = nd);
+    targets
+}
+
+/// A node and its tentative distance, ordered so that the smallest distance
+/// is popped first.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub(crate) struct ValType(pub usize, pub usize);
+
+impl Ord for ValType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for ValType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Keeps at most one task per node: a relaxation of a queued node lowers the
+/// priority of its task instead of pushing a new one.
+impl TaskQueue<ValType> for KeyedMultiQueue<usize> {
+    fn push(&self, val: ValType) {
+        self.push_or_decrease(val.1, val.0);
+    }
+    fn pop(&self) -> Option<ValType> {
+        KeyedMultiQueue::pop(self).map(|(node, dist)| ValType(dist, node))
+    }
+    fn register_thread(&self, tid: usize) {
+        KeyedMultiQueue::register_thread(self, tid);
+    }
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> &QueueStats {
+        KeyedMultiQueue::stats(self)
+    }
+}
+
+/// The queue and the threads of a multiqueue-driven graph traversal.
+#[derive(Clone, Copy, Debug)]
+pub struct QueueOptions {
+    pub threads: usize,
+    /// the number of internal queues per thread.
+    pub queues_per_thread: usize,
+    pub selection: QueueSelection,
+    /// pin the i-th thread to the i-th core.
+    pub pin: bool,
+}
+
+/// Sequential Dijkstra with a binary heap.
+pub fn dijkstra(graph: &WghGraph, dist: &mut [usize], start_node: usize) {
+    let mut pq = BinaryHeap::new();
+    pq.push(ValType(0, start_node));
+    dist[start_node] = 0;
+    while let Some(val) = pq.pop() {
+        let node = graph.index(val.1);
+        for (&target, &weight) in node.neighbors.iter().zip(node.weights) {
+            let target = target as usize;
+            let new_distance = val.0 + weight as usize;
+            if new_distance < dist[target] {
+                dist[target] = new_distance;
+                pq.push(ValType(new_distance, target));
+            }
+        }
+    }
+}
+
+/// Frontier-based Bellman-Ford: every round relaxes, in parallel, the edges
+/// of the nodes whose distance improved in the previous round.
+pub fn bellman_ford(graph: &WghGraph, dist: &mut [usize], start_node: usize) {
+    dist[start_node] = 0;
+    let mut frontier = vec![start_node];
+    while !frontier.is_empty() {
+        frontier = relax_frontier(graph, dist, &frontier);
+    }
+}
+
+/// Delta-stepping: nodes are kept in buckets of width `delta` by their
+/// tentative distance, and the buckets are settled in increasing order. The
+/// nodes of a bucket are relaxed in parallel, until the bucket stays empty.
+/// Only the non-empty buckets are kept, so the work does not grow with the
+/// number of empty buckets below the largest distance.
+pub fn delta_stepping(graph: &WghGraph, dist: &mut [usize], start_node: usize, delta: usize) {
+    dist[start_node] = 0;
+    let mut buckets: BTreeMap<usize, Vec<usize>> = BTreeMap::from([(0, vec![start_node])]);
+
+    while let Some((i, mut frontier)) = buckets.pop_first() {
+        while !frontier.is_empty() {
+            // a node can be in a bucket several times, or have moved to a
+            // smaller bucket since it was inserted.
+            frontier = frontier
+                .into_par_iter()
+                .filter(|&v| dist[v] / delta == i)
+                .collect();
+            frontier.par_sort_unstable();
+            frontier.dedup();
+
+            let improved = relax_frontier(graph, dist, &frontier);
+            let (current, later): (Vec<usize>, Vec<usize>) = improved
+                .into_par_iter()
+                .partition(|&v| dist[v] / delta == i);
+            for v in later {
+                buckets.entry(dist[v] / delta).or_default().push(v);
+            }
+            frontier = current;
+        }
+    }
+}
+
+struct SharedData<'a> {
+    shortest_distance: &'a [AtomicUsize],
+    #[cfg(feature = "stats")]
+    stats: WorkStats,
+}
+
+fn process_node<Q: TaskQueue<ValType>>(
+    val: ValType,
+    graph: &WghGraph,
+    data: &SharedData,
+    pq: &Pusher<ValType, Q>,
+) {
+    let current_distance = data.shortest_distance[val.1].load(Ordering::Relaxed);
+    if val.0 > current_distance {
+        #[cfg(feature = "stats")]
+        data.stats.stale.incr();
+        return;
+    }
+    #[cfg(feature = "stats")]
+    data.stats.processed.incr();
+    let node = graph.index(val.1);
+    for (&target, &weight) in node.neighbors.iter().zip(node.weights) {
+        let target = target as usize;
+        let new_distance = val.0 + weight as usize;
+        let mut old_distance = data.shortest_distance[target].load(Ordering::Relaxed);
+
+        while new_distance < old_distance {
+            match data.shortest_distance[target].compare_exchange_weak(
+                old_distance,
+                new_distance,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    pq.push(ValType(new_distance, target));
+                    #[cfg(feature = "stats")]
+                    data.stats.pushed.incr();
+                    break;
+                }
+                Err(x) => old_distance = x,
+            }
+        }
+    }
+}
+
+fn timed_run<Q: TaskQueue<ValType>>(
+    graph: &WghGraph,
+    dist: &[AtomicUsize],
+    start_node: usize,
+    opts: &QueueOptions,
+    queue: Q,
+) -> RunStats {
+    let executor = PriorityExecutor::with_queue(opts.threads, queue).pin_threads(opts.pin);
+    let data = SharedData {
+        shortest_distance: dist,
+        #[cfg(feature = "stats")]
+        stats: WorkStats::new(opts.threads),
+    };
+    dist[start_node].store(0, Ordering::Relaxed);
+
+    let run = executor.run([ValType(0, start_node)], |val, pusher| {
+        process_node(val, graph, &data, pusher)
+    });
+    #[cfg(feature = "stats")]
+    println!("{}\n{}\n{}", executor.queue().stats(), data.stats, run);
+    run
+}
+
+/// Relaxed Dijkstra on a multiqueue, or on a keyed multiqueue that keeps at
+/// most one entry per node (decrease-key instead of re-push) if `keyed`.
+/// `dist` must be all `usize::MAX`. Returns the statistics of the traversal.
+pub fn multiqueue_dijkstra(
+    graph: &WghGraph,
+    dist: &[AtomicUsize],
+    start_node: usize,
+    opts: &QueueOptions,
+    keyed: bool,
+) -> RunStats {
+    if keyed {
+        let pq = KeyedMultiQueue::with_config(
+            opts.threads,
+            graph.num_vertices(),
+            opts.queues_per_thread,
+            opts.selection,
+        );
+        timed_run(graph, dist, start_node, opts, pq)
+    } else {
+        let pq = MultiQueue::with_config(opts.threads, opts.queues_per_thread, opts.selection);
+        timed_run(graph, dist, start_node, opts, pq)
+    }
+}
+
+#[cfg(test)]
+mod sssp_tests {
+    use super::*;
+
+    #[test]
+    fn delta_stepping_with_heavy_edges() {
+        let heavy = 1 << 30;
+        let edges = vec![
+            (0, 1, heavy),
+            (1, 2, heavy),
+            (0, 2, 3 * heavy),
+            (2, 3, 1),
+            (0, 3, 1),
+        ];
+        let graph = WghGraph::from_edges(4, edges);
+        let mut expected = vec![usize::MAX; 4];
+        dijkstra(&graph, &mut expected, 0);
+        for delta in [1, 1000, heavy as usize] {
+            let mut dist = vec![usize::MAX; 4];
+            delta_stepping(&graph, &mut dist, 0, delta);
+            assert_eq!(dist, expected);
+        }
+        assert_eq!(expected, vec![0, heavy as usize, 2 * heavy as usize, 1]);
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::atomic::AtomicUsize;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::time::Instant;
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use rpb_bench::{algorithm_names, Benchmark, RunError, RunOutcome, RunSpec, Warmup};
+
+use crate::sssp::{self, QueueOptions};
+use crate::util::{Graph, WghGraph};
+use crate::{bfs, QueueSelection, DEFAULT_C};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum SsspAlgs {
+    #[value(name = "multiqueue")]
+    MultiQueue,
+    #[value(name = "keyed_multiqueue")]
+    KeyedMultiQueue,
+    #[value(name = "dijkstra")]
+    Dijkstra,
+    #[value(name = "delta_stepping")]
+    DeltaStepping,
+    #[value(name = "bellman_ford")]
+    BellmanFord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum BfsAlgs {
+    #[value(name = "multiqueue")]
+    MultiQueue,
+}
+
+/// Returns the benchmarks of this crate: `sssp`, whose algorithms are the
+/// `sssp*` binaries, and `bfs`.
+pub fn benchmarks() -> Vec<Benchmark> {
+    vec![
+        Benchmark {
+            name: "sssp",
+            algorithms: algorithm_names(SsspAlgs::MultiQueue),
+            params: &[
+                "start_node",
+                "queues_per_thread",
+                "numa_groups",
+                "steal_prob",
+                "pin",
+                "delta",
+            ],
+            features: crate::features,
+            warmup: Warmup::DEFAULT,
+            run: run_sssp,
+        },
+        Benchmark {
+            name: "bfs",
+            algorithms: algorithm_names(BfsAlgs::MultiQueue),
+            params: &[
+                "start_node",
+                "queues_per_thread",
+                "numa_groups",
+                "steal_prob",
+                "pin",
+            ],
+            features: crate::features,
+            warmup: Warmup::DEFAULT,
+            run: run_bfs,
+        },
+    ]
+}
+
+fn queue_options(spec: &RunSpec) -> Result<QueueOptions, RunError> {
+    let numa_groups = spec.param("numa_groups", 1)?;
+    Ok(QueueOptions {
+        threads: spec.threads,
+        queues_per_thread: spec.param("queues_per_thread", DEFAULT_C)?,
+        selection: if numa_groups > 1 {
+            QueueSelection::Grouped {
+                groups: numa_groups,
+                steal_prob: spec.param("steal_prob", 0.1)?,
+            }
+        } else {
+            QueueSelection::Uniform
+        },
+        pin: spec.param("pin", false)?,
+    })
+}
+
+fn name<A: ValueEnum>(alg: A) -> String {
+    alg.to_possible_value().unwrap().get_name().to_string()
+}
+
+fn run_sssp(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(SsspAlgs::MultiQueue)?;
+    let start_node = spec.param("start_node", 0)?;
+    let graph = WghGraph::read(&spec.input).map_err(RunError::input)?;
+
+    let (samples, check) = match alg {
+        SsspAlgs::MultiQueue | SsspAlgs::KeyedMultiQueue => {
+            let opts = queue_options(spec)?;
+            let mut dist = vec![];
+            let samples = spec.time.run_rounds(|| {
+                dist = (0..graph.num_vertices())
+                    .into_par_iter()
+                    .map(|_| AtomicUsize::new(usize::MAX))
+                    .collect();
+                let keyed = alg == SsspAlgs::KeyedMultiQueue;
+                let run = sssp::multiqueue_dijkstra(&graph, &dist, start_node, &opts, keyed);
+                (run.elapsed, run.perf)
+            });
+            (
+                samples,
+                spec.check.then(|| sssp::verify_distance(&graph, &dist)),
+            )
+        }
+        SsspAlgs::Dijkstra | SsspAlgs::DeltaStepping | SsspAlgs::BellmanFord => {
+            let delta = spec.param("delta", 32)?;
+            if delta == 0 {
+                return Err(RunError::Param {
+                    name: "delta".to_string(),
+                    reason: "it must be positive".to_string(),
+                });
+            }
+            let mut dist = vec![usize::MAX; graph.num_vertices()];
+            let samples = spec.time.run_rounds(|| {
+                dist.par_iter_mut().for_each(|x| *x = usize::MAX);
+                let start = Instant::now();
+                match alg {
+                    SsspAlgs::Dijkstra => sssp::dijkstra(&graph, &mut dist, start_node),
+                    SsspAlgs::DeltaStepping => {
+                        sssp::delta_stepping(&graph, &mut dist, start_node, delta)
+                    }
+                    _ => sssp::bellman_ford(&graph, &mut dist, start_node),
+                }
+                (start.elapsed(), None)
+            });
+            (
+                samples,
+                spec.check.then(|| sssp::verify_distance(&graph, &dist)),
+            )
+        }
+    };
+    Ok(RunOutcome {
+        algorithm: name(alg),
+        samples,
+        check,
+    })
+}
+
+fn run_bfs(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(BfsAlgs::MultiQueue)?;
+    let start_node = spec.param("start_node", 0)?;
+    let opts = queue_options(spec)?;
+    let graph = Graph::read(&spec.input).map_err(RunError::input)?;
+
+    let mut dist = vec![];
+    let samples = spec.time.run_rounds(|| {
+        dist = (0..graph.num_vertices())
+            .into_par_iter()
+            .map(|_| AtomicUsize::new(usize::MAX))
+            .collect();
+        let run = bfs::multiqueue_bfs(&graph, &dist, start_node, &opts);
+        (run.elapsed, run.perf)
+    });
+    Ok(RunOutcome {
+        algorithm: name(alg),
+        samples,
+        check: spec.check.then(|| bfs::verify_distance(&graph, &dist)),
+    })
+}
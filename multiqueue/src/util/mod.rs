@@ -1,4 +1,4 @@
-mod graph;
+mod padded;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,11 +25,17 @@ mod graph;
 // SOFTWARE.
 // ============================================================================
 
-pub use graph::Graph;
-mod wgh_graph;
-pub use wgh_graph::Graph as WghGraph;
-
-mod padded;
 pub use padded::Padded;
 
+/// An unweighted CSR graph with 32-bit vertex ids.
+pub type Graph = rpb_graph::Graph<u32>;
+/// A weighted CSR graph with 32-bit vertex ids and weights.
+pub type WghGraph = rpb_graph::WghGraph<u32, u32>;
+/// A binary graph mapped into memory, with 32-bit vertex ids and weights.
+/// Both `Graph::read` and `WghGraph::read` load binary graphs too.
+pub type MappedGraph = rpb_graph::MappedGraph<u32, u32>;
+
+#[cfg(feature = "stats")]
+pub mod stats;
+
 pub mod termination_detection;
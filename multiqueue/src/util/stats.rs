@@ -0,0 +1,176 @@
+use std::cell::Cell;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::util::Padded;
+
+thread_local! {
+    static SLOT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Makes the calling thread, the `tid`-th worker, update its own slot of
+/// every `Counter`.
+pub fn set_thread_slot(tid: usize) {
+    SLOT.with(|s| s.set(tid));
+}
+
+/// A counter with one cache-line padded slot per thread, so that threads
+/// counting concurrently do not contend.
+pub struct Counter {
+    slots: Vec<Padded<AtomicU64>>,
+}
+
+impl Counter {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            slots: (0..num_threads.max(1))
+                .map(|_| Padded::new(AtomicU64::new(0)))
+                .collect(),
+        }
+    }
+
+    /// Adds `v` to the calling thread's slot and returns the slot's old value.
+    #[inline(always)]
+    pub fn add(&self, v: u64) -> u64 {
+        let slot = SLOT.with(Cell::get) % self.slots.len();
+        self.slots[slot].fetch_add(v, Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn incr(&self) -> u64 {
+        self.add(1)
+    }
+
+    pub fn get(&self) -> u64 {
+        self.slots.iter().map(|s| s.load(Relaxed)).sum()
+    }
+}
+
+/// Every this many pops of a thread, the rank error of the popped element is
+/// measured.
+pub const RANK_SAMPLE_RATE: u64 = 1024;
+
+/// The quality counters of a `MultiQueue`.
+pub struct QueueStats {
+    /// the number of pushed elements.
+    pub pushes: Counter,
+    /// the number of popped elements.
+    pub pops: Counter,
+    /// the number of pops that found the whole queue empty.
+    pub empty_pops: Counter,
+    /// the number of times a pop sampled two empty queues and tried again.
+    pub empty_retries: Counter,
+    rank_samples: Counter,
+    rank_error_sum: Counter,
+    rank_error_max: AtomicU64,
+}
+
+impl QueueStats {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            pushes: Counter::new(num_threads),
+            pops: Counter::new(num_threads),
+            empty_pops: Counter::new(num_threads),
+            empty_retries: Counter::new(num_threads),
+            rank_samples: Counter::new(num_threads),
+            rank_error_sum: Counter::new(num_threads),
+            rank_error_max: AtomicU64::new(0),
+        }
+    }
+
+    /// Records that a popped element had `rank` better elements in the queue.
+    pub fn add_rank_sample(&self, rank: u64) {
+        self.rank_samples.incr();
+        self.rank_error_sum.add(rank);
+        self.rank_error_max.fetch_max(rank, Relaxed);
+    }
+
+    pub fn mean_rank_error(&self) -> f64 {
+        let samples = self.rank_samples.get();
+        if samples == 0 {
+            0.0
+        } else {
+            self.rank_error_sum.get() as f64 / samples as f64
+        }
+    }
+
+    pub fn max_rank_error(&self) -> u64 {
+        self.rank_error_max.load(Relaxed)
+    }
+}
+
+impl fmt::Display for QueueStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "pushes:\t{}", self.pushes.get())?;
+        writeln!(f, "pops:\t{}", self.pops.get())?;
+        writeln!(f, "empty pops:\t{}", self.empty_pops.get())?;
+        writeln!(f, "empty retries:\t{}", self.empty_retries.get())?;
+        write!(
+            f,
+            "rank error:\tmean {:.2}, max {} ({} samples)",
+            self.mean_rank_error(),
+            self.max_rank_error(),
+            self.rank_samples.get()
+        )
+    }
+}
+
+/// The work counters of a priority-driven graph algorithm.
+pub struct WorkStats {
+    /// the number of popped nodes whose distance had already improved.
+    pub stale: Counter,
+    /// the number of popped nodes that were relaxed.
+    pub processed: Counter,
+    /// the number of nodes pushed after a successful relaxation.
+    pub pushed: Counter,
+}
+
+impl WorkStats {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            stale: Counter::new(num_threads),
+            processed: Counter::new(num_threads),
+            pushed: Counter::new(num_threads),
+        }
+    }
+}
+
+impl fmt::Display for WorkStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (stale, processed) = (self.stale.get(), self.processed.get());
+        writeln!(f, "stale pops:\t{}", stale)?;
+        writeln!(f, "processed nodes:\t{}", processed)?;
+        writeln!(f, "pushed nodes:\t{}", self.pushed.get())?;
+        write!(
+            f,
+            "wasted work:\t{:.2}%",
+            100.0 * stale as f64 / (stale + processed).max(1) as f64
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering::SeqCst};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,54 +25,222 @@ use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
 // SOFTWARE.
 // ============================================================================
 
-pub struct TerminationData {
-    pub idle_count: AtomicU32,
-    pub no_work_count: AtomicU32,
-    pub num_threads: usize,
+use std::hint;
+use std::thread;
+use std::time::Duration;
+
+use crate::util::Padded;
+
+/// What a waiting thread does once exponential spinning stops paying off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackoffPolicy {
+    /// keep spinning; best when every thread has a dedicated core.
+    Spin,
+    /// give the core to another runnable thread.
+    Yield,
+    /// sleep for (at most) the given duration.
+    Park(Duration),
+}
+
+/// The largest spin is `2^SPIN_LIMIT` iterations.
+const SPIN_LIMIT: u32 = 6;
+/// The number of yields before a `Park` policy starts to park.
+const YIELD_LIMIT: u32 = 4;
+
+/// Exponential backoff for spin-waiting loops.
+pub struct Backoff {
+    step: u32,
+    policy: BackoffPolicy,
+}
+
+impl Backoff {
+    pub fn new(policy: BackoffPolicy) -> Self {
+        Self { step: 0, policy }
+    }
+
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+
+    pub fn snooze(&mut self) {
+        if self.step <= SPIN_LIMIT || self.policy == BackoffPolicy::Spin {
+            for _ in 0..1 << self.step.min(SPIN_LIMIT) {
+                hint::spin_loop();
+            }
+        } else if self.step <= SPIN_LIMIT + YIELD_LIMIT {
+            thread::yield_now();
+        } else if let BackoffPolicy::Park(timeout) = self.policy {
+            thread::park_timeout(timeout);
+        } else {
+            thread::yield_now();
+        }
+        if self.step <= SPIN_LIMIT + YIELD_LIMIT {
+            self.step += 1;
+        }
+    }
 }
 
-impl TerminationData {
+/// Increments a counter for as long as it is alive.
+struct CountGuard<'a>(&'a AtomicU32);
+
+impl<'a> CountGuard<'a> {
+    fn new(counter: &'a AtomicU32) -> (Self, u32) {
+        let count = counter.fetch_add(1, SeqCst) + 1;
+        (Self(counter), count)
+    }
+}
+
+impl Drop for CountGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, SeqCst);
+    }
+}
+
+/// Detects when all the `num_threads` workers of a dynamic work pool have run
+/// out of work at the same time, i.e. when no more work can ever appear.
+///
+/// Every worker keeps calling `try_do` until it returns `None`.
+pub struct TerminationDetector {
+    /// the number of threads that are looking for work.
+    no_work_count: Padded<AtomicU32>,
+    /// the number of threads that are ready to terminate.
+    idle_count: Padded<AtomicU32>,
+    terminated: AtomicBool,
+    num_threads: u32,
+    policy: BackoffPolicy,
+}
+
+impl TerminationDetector {
     pub fn new(num_threads: usize) -> Self {
+        assert!(num_threads > 0);
         Self {
-            idle_count: AtomicU32::new(0),
-            no_work_count: AtomicU32::new(0),
-            num_threads,
+            no_work_count: Padded::new(AtomicU32::new(0)),
+            idle_count: Padded::new(AtomicU32::new(0)),
+            terminated: AtomicBool::new(false),
+            num_threads: num_threads as u32,
+            policy: BackoffPolicy::Spin,
         }
     }
-}
 
-pub fn wait_to_terminate(data: &TerminationData) -> bool {
-    let mut idle_count = data.idle_count.fetch_add(1, Relaxed) + 1;
-    while idle_count < data.num_threads as u32 {
-        if data.no_work_count.load(Relaxed) < data.num_threads as u32 {
-            data.idle_count.fetch_sub(1, Relaxed);
-            return false;
+    pub fn with_backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.num_threads as usize
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.terminated.load(SeqCst)
+    }
+
+    /// Waits until either every thread is idle (returns `true`), or some
+    /// thread found work again (returns `false`).
+    fn wait_to_terminate(&self) -> bool {
+        let (_idle, mut idle_count) = CountGuard::new(&self.idle_count);
+        let mut backoff = Backoff::new(self.policy);
+        loop {
+            if self.terminated.load(SeqCst) {
+                return true;
+            }
+            if idle_count == self.num_threads {
+                self.terminated.store(true, SeqCst);
+                return true;
+            }
+            if self.no_work_count.load(SeqCst) < self.num_threads {
+                return false;
+            }
+            backoff.snooze();
+            idle_count = self.idle_count.load(SeqCst);
+        }
+    }
+
+    /// Calls `f` until it returns some work, or until all the threads have
+    /// failed to find work at the same time, in which case `None` is returned
+    /// and the caller should stop.
+    pub fn try_do<F, RT>(&self, mut f: F) -> Option<RT>
+    where
+        F: FnMut() -> Option<RT>,
+    {
+        if let Some(result) = f() {
+            return Some(result);
         }
-        unsafe {
-            core::arch::x86_64::_mm_pause();
+        let (_no_work, mut num_no_work) = CountGuard::new(&self.no_work_count);
+        let mut backoff = Backoff::new(self.policy);
+        loop {
+            if let Some(result) = f() {
+                return Some(result);
+            }
+            if self.terminated.load(SeqCst)
+                || (num_no_work == self.num_threads && self.wait_to_terminate())
+            {
+                return None;
+            }
+            backoff.snooze();
+            num_no_work = self.no_work_count.load(SeqCst);
         }
-        idle_count = data.idle_count.load(Relaxed);
     }
-    true
 }
 
-pub fn try_do<F, RT>(f: &F, data: &TerminationData) -> Result<RT, ()>
-where
-    F: Fn() -> Result<RT, ()>,
-{
-    if let Ok(result) = f() {
-        return Ok(result);
-    } else {
-        let mut num_no_work = data.no_work_count.fetch_add(1, Relaxed) + 1;
-        loop {
-            if let Ok(result) = f() {
-                data.no_work_count.fetch_sub(1, Relaxed);
-                return Ok(result);
+#[cfg(test)]
+mod termination_detection_tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+
+    /// Runs a work pool where every task `d > 0` spawns two tasks `d - 1`,
+    /// starting from a single task `depth`, and returns the number of
+    /// processed tasks.
+    fn run_pool(num_threads: usize, depth: u32, policy: BackoffPolicy, slow: bool) -> usize {
+        let pool = Mutex::new(vec![depth]);
+        let processed = AtomicUsize::new(0);
+        let term = TerminationDetector::new(num_threads).with_backoff(policy);
+
+        thread::scope(|s| {
+            for _ in 0..num_threads {
+                s.spawn(|| {
+                    while let Some(d) = term.try_do(|| pool.lock().unwrap().pop()) {
+                        if slow {
+                            // keep the task in flight while the others starve.
+                            thread::sleep(Duration::from_micros(50));
+                        }
+                        if d > 0 {
+                            pool.lock().unwrap().extend([d - 1, d - 1]);
+                        }
+                        processed.fetch_add(1, SeqCst);
+                    }
+                });
             }
-            if num_no_work == data.num_threads as u32 && wait_to_terminate(data) {
-                return Err(());
+        });
+
+        assert!(term.is_terminated());
+        assert!(pool.lock().unwrap().is_empty());
+        processed.load(SeqCst)
+    }
+
+    #[test]
+    fn single_thread() {
+        assert_eq!(run_pool(1, 10, BackoffPolicy::Spin, false), (1 << 11) - 1);
+    }
+
+    #[test]
+    fn never_terminates_early() {
+        for policy in [
+            BackoffPolicy::Spin,
+            BackoffPolicy::Yield,
+            BackoffPolicy::Park(Duration::from_micros(10)),
+        ] {
+            for _ in 0..20 {
+                assert_eq!(run_pool(8, 10, policy, false), (1 << 11) - 1);
             }
-            num_no_work = data.no_work_count.load(Relaxed);
+        }
+    }
+
+    #[test]
+    fn never_terminates_early_with_work_in_flight() {
+        for _ in 0..5 {
+            assert_eq!(run_pool(4, 6, BackoffPolicy::Yield, true), (1 << 7) - 1);
         }
     }
 }
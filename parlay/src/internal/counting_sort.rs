@@ -81,7 +81,8 @@ fn seq_write_down_<T: Copy, F: ToPrimitive>(
     keys: &[F],
     offsets: &mut [DefInt],
 ) {
-    inp.iter().zip(keys.iter()).for_each(|(i, k)| {
+    // backwards, so that equal keys keep their order
+    inp.iter().zip(keys.iter()).rev().for_each(|(i, k)| {
         let k = k.to_usize().unwrap();
         offsets[k] -= 1;
         out[offsets[k] as usize] = *i;
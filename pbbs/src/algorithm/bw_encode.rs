@@ -25,10 +25,9 @@ use rayon::prelude::*;
 // SOFTWARE.
 // ============================================================================
 
-mod suffix_array;
+use super::suffix_array;
 
-use crate::{DefChar, DefInt};
-use parlay::maybe_uninit_vec;
+use crate::DefChar;
 use suffix_array::suffix_array;
 
 pub fn bw_encode(s: &[DefChar]) -> Vec<DefChar> {
@@ -39,8 +38,7 @@ pub fn bw_encode(s: &[DefChar]) -> Vec<DefChar> {
         .chain(s.par_iter().cloned())
         .collect();
 
-    let mut sa: Vec<DefInt> = maybe_uninit_vec![0; n+1];
-    suffix_array(&ss, &mut sa);
+    let sa = suffix_array(&ss);
 
     (0..n + 1)
         .into_par_iter()
@@ -27,21 +27,23 @@ use rayon::prelude::*;
 
 use enhanced_rayon::prelude::*;
 
-use crate::range_min::{AtomU32RangeMin, RangeMin};
-use crate::{DefAtomInt, DefChar, DefInt, ORDER};
+use crate::algorithm::range_min::RangeMin;
+use crate::common::shared::SharedArray;
+use crate::{DefChar, DefInt};
 use parlay::primitives::pack_index;
 use parlay::{maybe_uninit_vec, Timer};
 
-#[allow(dead_code)]
-pub fn lcp(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
+/// Computes the lcps of adjacent suffixes, with the lcps that the rounds
+/// fill in in the shared array `A`: `AtomicArray` (safe) or `RawArray`
+/// (unsafe).
+pub fn lcp<A: SharedArray<DefInt>>(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
     let mut t = Timer::new("lcp"); //t.start();
     let mut len = 111;
     let n = sa.len();
     t.next("init");
 
     // compare first len characters of adjacent strings from SA.
-    #[allow(unused_mut)]
-    let mut l: Vec<_> = (0..n - 1)
+    let l: Vec<_> = (0..n - 1)
         .into_par_iter()
         .map(|i| {
             let mut j = 0;
@@ -78,6 +80,7 @@ pub fn lcp(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
     if remain.len() == 0 {
         return l;
     }
+    let l = A::from_vec(l);
 
     // an inverse permutation for SA
     #[allow(unused_mut)]
@@ -102,113 +105,14 @@ pub fn lcp(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
             .filter(|&i| {
                 let i = i as usize;
                 if sa[i] as usize + len >= n {
-                    unsafe {
-                        (l.as_ptr() as *mut DefInt).add(i).write(len as DefInt);
-                    }
+                    l.set(i, len as DefInt);
                     return false;
                 }
                 let i1 = isa[len + sa[i] as usize];
                 let i2 = isa[len + sa[i + 1] as usize];
-                let li = l[rq.query(i1, i2 - 1) as usize];
-                if (li as usize) < len {
-                    unsafe {
-                        (l.as_ptr() as *mut DefInt).add(i).write(len as DefInt + li);
-                    }
-                    return false;
-                } else {
-                    return true;
-                }
-            })
-            .collect();
-        t.next("filter");
-
-        len *= 2;
-        if remain.len() <= 0 {
-            break;
-        }
-    }
-    return l;
-}
-
-#[allow(dead_code)]
-pub fn atomic_lcp(s: &[DefChar], sa: &[DefAtomInt]) -> Vec<DefAtomInt> {
-    let mut t = Timer::new("lcp"); //t.start();
-    let mut len = 111;
-    let n = sa.len();
-    t.next("init");
-
-    // compare first len characters of adjacent strings from SA.
-    let l: Vec<_> = (0..n - 1)
-        .into_par_iter()
-        .map(|i| {
-            let mut j = 0;
-            let max_j = len.min(n - sa[i].load(ORDER) as usize);
-            while j < max_j && {
-                #[cfg(feature = "mem_safe")]
-                {
-                    s[sa[i].load(ORDER) as usize + j] == s[sa[i + 1].load(ORDER) as usize + j]
-                }
-                #[cfg(not(feature = "mem_safe"))]
-                unsafe {
-                    s.as_ptr().add(sa[i].load(ORDER) as usize + j).read()
-                        == s.as_ptr().add(sa[i + 1].load(ORDER) as usize + j).read()
-                }
-            } {
-                j += 1;
-            }
-            DefAtomInt::new((if j < len { j } else { n }) as DefInt)
-        })
-        .collect();
-
-    t.next("head");
-
-    // keep indices for which we do not yet know their LCP (i.e. LCP >= len)
-    let mut remain = Vec::<u32>::new();
-    pack_index(
-        &l.par_iter()
-            .map(|li| li.load(ORDER) as usize == n)
-            .collect::<Vec<bool>>(),
-        &mut remain,
-    );
-    t.next("pack");
-
-    if remain.len() == 0 {
-        return l;
-    }
-
-    // an inverse permutation for SA
-    let isa: Vec<DefAtomInt> = (0..n)
-        .into_par_iter()
-        .map(|i| DefAtomInt::new(i as DefInt))
-        .collect();
-
-    (0..n)
-        .into_par_iter()
-        .for_each(|i| isa[sa[i].load(ORDER) as usize].store(i as DefInt, ORDER));
-    t.next("inverse");
-
-    // repeatedly double len determining LCP by joining next len chars
-    // invariant: before i^th round L contains correct LCPs less than len
-    //            and n for the rest of them
-    //            remain holds indices of the rest of them (i.e., LCP[i] >= len)
-    //      after round, len = 2*len and invariant holds for the new len
-    loop {
-        let rq = AtomU32RangeMin::new(&l, |a, b| a < b, 111);
-        t.next("make range");
-
-        remain = remain
-            .into_par_iter()
-            .filter(|&i| {
-                let i = i as usize;
-                if sa[i].load(ORDER) as usize + len >= n {
-                    l[i].store(len as DefInt, ORDER);
-                    return false;
-                }
-                let i1 = isa[len + sa[i].load(ORDER) as usize].load(ORDER);
-                let i2 = isa[len + sa[i + 1].load(ORDER) as usize].load(ORDER);
-                let li = l[rq.query(i1, i2 - 1) as usize].load(ORDER);
+                let li = l.get(rq.query(i1, i2 - 1) as usize);
                 if (li as usize) < len {
-                    l[i].store(len as DefInt + li, ORDER);
+                    l.set(i, len as DefInt + li);
                     return false;
                 } else {
                     return true;
@@ -222,5 +126,5 @@ pub fn atomic_lcp(s: &[DefChar], sa: &[DefAtomInt]) -> Vec<DefAtomInt> {
             break;
         }
     }
-    return l;
+    return l.into_vec();
 }
@@ -1,5 +1,4 @@
-#[path = "../common/atomics.rs"]
-mod atomics;
+use crate::common::atomics;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -27,13 +26,11 @@ mod atomics;
 // ============================================================================
 
 use std::mem::swap;
-use std::sync::atomic::Ordering;
 
-use crate::{DefAtomIntS, DefIntS};
+use crate::common::shared::SharedArray;
+use crate::DefIntS;
 use atomics::atomic_cas;
 
-static ORD: Ordering = Ordering::Relaxed;
-
 pub struct UnionFind {
     parents: Vec<DefIntS>,
 }
@@ -87,34 +84,35 @@ impl UnionFind {
     }
 }
 
-pub struct AtomicUnionFind {
-    parents: Vec<DefAtomIntS>,
+/// A union-find that parallel tasks search and link at the same time, with
+/// its parents in the shared array `A`.
+pub struct SharedUnionFind<A> {
+    parents: A,
 }
 
-impl AtomicUnionFind {
+impl<A: SharedArray<DefIntS>> SharedUnionFind<A> {
     pub fn new(n: usize) -> Self {
         Self {
-            parents: (0..n).map(|_| DefAtomIntS::new(-1)).collect(),
+            parents: A::new(n, -1),
         }
     }
 
     fn is_root(&self, u: DefIntS) -> bool {
-        self.parents[u as usize].load(ORD) < 0
+        self.parents.get(u as usize) < 0
     }
 
-    pub fn find(&self, u: DefIntS) -> DefIntS {
+    pub fn find(&self, mut u: DefIntS) -> DefIntS {
         if self.is_root(u) {
             return u;
         }
-        let mut p = self.parents[u as usize].load(ORD);
+        let mut p = self.parents.get(u as usize);
         if self.is_root(p) {
             return p;
         }
 
-        let mut u = u;
         loop {
-            let gp = self.parents[p as usize].load(ORD);
-            self.parents[u as usize].store(gp, ORD);
+            let gp = self.parents.get(p as usize);
+            self.parents.set(u as usize, gp);
             u = p;
             p = gp;
             if self.is_root(p) {
@@ -123,23 +121,7 @@ impl AtomicUnionFind {
         }
     }
 
-    pub fn union_roots(&self, u: DefIntS, v: DefIntS) {
-        let (mut u, mut v) = (u as usize, v as usize);
-        if self.parents[u].load(ORD) < self.parents[v].load(ORD) {
-            swap(&mut u, &mut v);
-        };
-        self.parents[u].fetch_add(self.parents[v].load(ORD), ORD);
-        self.parents[v].store(u as DefIntS, ORD);
-    }
-
     pub fn link(&self, u: DefIntS, v: DefIntS) {
-        self.parents[u as usize].store(v, ORD);
-    }
-
-    pub fn try_link(&self, u: DefIntS, v: DefIntS) -> bool {
-        self.parents[u as usize].load(ORD) == -1
-            && self.parents[u as usize]
-                .compare_exchange(-1, v, ORD, ORD)
-                .is_ok()
+        self.parents.set(u as usize, v);
     }
 }
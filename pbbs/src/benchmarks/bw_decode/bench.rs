@@ -0,0 +1,74 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::algorithm::bw_encode::bw_encode;
+use crate::benchmarks::bw_decode::check::BwCheck;
+use crate::benchmarks::bw_decode::list_rank;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::io::chars_from_file;
+use crate::common::shared::{AtomicArray, RawArray};
+use crate::misc::*;
+
+define_algs!(
+    (ListRank, "list-rank"),
+    (ListRankAtomic, "list-rank-atomic"),
+    (ListRankRaw, "list-rank-raw")
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::ListRank;
+
+pub fn run(alg: Algs, opts: &TimeOptions, inp: &[DefChar]) -> (Vec<DefChar>, Samples) {
+    let f = match alg {
+        Algs::ListRank => list_rank::bw_decode,
+        Algs::ListRankAtomic => list_rank::bw_decode_with::<AtomicArray<bool>, AtomicArray<DefInt>>,
+        Algs::ListRankRaw => list_rank::bw_decode_with::<RawArray<bool>, RawArray<DefInt>>,
+    };
+
+    let mut r = vec![];
+
+    let samples = time_loop(
+        "bw",
+        opts,
+        || {},
+        || {
+            r = f(inp);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let arr = chars_from_file(&spec.input, false).map_err(RunError::input)?;
+    let encoded = bw_encode(&arr);
+    let (r, d) = run(alg, &spec.time, &encoded);
+    Ok(outcome(spec, alg, d, || BwCheck.check(&arr, &r)))
+}
@@ -1,4 +1,4 @@
-use clap::Parser;
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,43 +25,13 @@ use clap::Parser;
 // SOFTWARE.
 // ============================================================================
 
-use rayon::prelude::*;
+use pbbs::benchmarks::bw_decode::check::BwCheck;
+use pbbs::define_check_args;
 
-#[path ="../../common/io.rs"] mod io;
-use io::chars_from_file;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// BW results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-}
-
-pub fn check(inp: &[u8], out: &[u8]) -> bool {
-    if inp.len() != out.len() {
-        eprintln!("files' lengthes differ.");
-        return false;
-    }
-    let diff: Vec<_> = (inp, out)
-        .into_par_iter()
-        .filter(|(i, o)| i != o)
-        .collect();
-    if diff.len() == 0 { true }
-    else {
-        eprintln!("different chars: {}", diff.len());
-        false
-    }
-}
+define_check_args!();
 
 fn main() {
     let args = Args::parse();
-    let inp = chars_from_file(&args.ifname, false).unwrap();
-    let out = chars_from_file(&args.rfname, false).unwrap();
-    if check(&inp, &out) { println!("OK"); }
-    else { eprintln!("ERR"); std::process::exit(1); }
+    let checker = BwCheck;
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
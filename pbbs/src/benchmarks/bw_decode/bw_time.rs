@@ -1,4 +1,3 @@
-use std::time::Duration;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,53 +24,28 @@ use std::time::Duration;
 // SOFTWARE.
 // ============================================================================
 
-#[path = "mod.rs"]
-mod bw;
-#[path = "../../algorithm/bw_encode.rs"]
-mod bw_encode;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
+use pbbs::algorithm::bw_encode::bw_encode;
+use pbbs::benchmarks::bw_decode::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::bw_decode::check::BwCheck;
+use pbbs::common::io::{chars_from_file, chars_to_file};
+use pbbs::{check, define_args, finalize, report, sweep};
 
-use bw_encode::bw_encode;
-use io::{chars_from_file, chars_to_file};
-use misc::*;
-
-define_args!(Algs::ListRank);
-
-define_algs!((ListRank, "list-rank"));
-
-pub fn run(alg: Algs, rounds: usize, inp: &[DefChar]) -> (Vec<DefChar>, Duration) {
-    let f = match alg {
-        Algs::ListRank => bw::list_rank::bw_decode,
-    };
-
-    let mut r = vec![];
-
-    let mean = time_loop(
-        "bw",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            r = f(&inp);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG);
 
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let arr = chars_from_file(&args.ifname, false).unwrap();
 
     let encoded = bw_encode(&arr);
 
-    let (r, d) = run(args.algorithm, args.rounds, &encoded);
+    if sweep!(args, |alg| run(alg, &args.time, &encoded).1) {
+        return;
+    }
+
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &encoded));
 
-    finalize!(args, r, d, chars_to_file(&r, args.ofname).unwrap());
+    finalize!(args, r, d, chars_to_file(&r, &args.ofname).unwrap());
+    let verdict = check!(args, BwCheck, &arr, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,63 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::chars_from_file;
+use crate::DefChar;
+
+/// Checks that the decoded text is the original text.
+pub struct BwCheck;
+
+impl Checker for BwCheck {
+    type Input = Vec<DefChar>;
+    type Output = Vec<DefChar>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        if inp.len() != out.len() {
+            return invalid(format!(
+                "decoded {} chars, the text has {}",
+                out.len(),
+                inp.len()
+            ));
+        }
+        match (inp, out).into_par_iter().position_first(|(i, o)| i != o) {
+            Some(i) => invalid(format!(
+                "{} chars differ, the first one at {i}",
+                (inp, out).into_par_iter().filter(|(i, o)| i != o).count()
+            )),
+            None => Ok(()),
+        }
+    }
+}
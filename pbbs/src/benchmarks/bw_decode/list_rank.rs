@@ -1,6 +1,5 @@
 use enhanced_rayon::prelude::*;
 use rayon::prelude::*;
-#[cfg(feature = "AW_safe")]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -26,9 +25,9 @@ use rayon::prelude::*;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 // ============================================================================
-use std::sync::atomic::AtomicBool;
 
-#[cfg(any(feature = "sng_ind_atomic", feature = "AW_safe"))]
+use crate::common::shared::{DefaultArray, SharedArray};
+#[cfg(feature = "sng_ind_atomic")]
 use crate::{DefAtomInt, ORDER};
 use crate::{DefChar, DefInt};
 use parlay::internal::counting_sort::count_sort;
@@ -36,7 +35,11 @@ use parlay::primitives::{flatten, pack_index};
 use parlay::random::Random;
 use parlay::{maybe_uninit_vec, Timer};
 
-fn bw_decode_(s: &[DefChar]) -> Vec<DefChar> {
+fn bw_decode_<F, N>(s: &[DefChar]) -> Vec<DefChar>
+where
+    F: SharedArray<bool>,
+    N: SharedArray<DefInt>,
+{
     let mut t = Timer::new("bw");
     // t.start();
     let n = s.len();
@@ -69,58 +72,25 @@ fn bw_decode_(s: &[DefChar]) -> Vec<DefChar> {
     // links that point to a head are set to their original position + n
     // the overall first character is made to be a head
     let r = Random::new(0);
-    #[allow(unused_mut)]
-    let mut head_flags: Vec<_>;
     let start = links[0].next as usize;
     links[0].next += n as DefInt;
-    #[cfg(feature = "AW_safe")]
-    {
-        // make arrays atomics
-        let atom_head_flags: Vec<_> = (0..n)
-            .into_par_iter()
-            .map(|_| AtomicBool::new(false))
-            .collect();
-        atom_head_flags[start].store(true, ORDER);
-        let atom_link_n: Vec<_> = (&links)
-            .into_par_iter()
-            .map(|l| DefAtomInt::new(l.next))
-            .collect();
-
-        (0..(n / block_size + 2)).into_par_iter().for_each(|i| {
-            let j = r.ith_rand(i as u64) as usize % n;
-            let lnk = atom_link_n[j].load(ORDER);
-            if (lnk as usize) < n {
-                atom_head_flags[lnk as usize].store(true, ORDER);
-                atom_link_n[j].store(lnk + n as DefInt, ORDER);
-            }
-        });
+    let head_flags = F::new(n, false);
+    head_flags.set(start, true);
+    let next = N::from_vec(links.par_iter().map(|l| l.next).collect());
+
+    (0..(n / block_size + 2)).into_par_iter().for_each(|i| {
+        let j = r.ith_rand(i as u64) as usize % n;
+        let lnk = next.get(j);
+        if (lnk as usize) < n {
+            head_flags.set(lnk as usize, true);
+            next.set(j, lnk + n as DefInt);
+        }
+    });
 
-        // retrive arrays
-        (&atom_link_n, &mut links)
-            .into_par_iter()
-            .for_each(|(n, l)| l.next = n.load(ORDER));
-        head_flags = atom_head_flags
-            .into_par_iter()
-            .map(|ahf| ahf.into_inner())
-            .collect();
-    }
-    #[cfg(not(feature = "AW_safe"))]
-    {
-        head_flags = (0..n).into_par_iter().map(|_| false).collect();
-        head_flags[start] = true;
-        (0..(n / block_size + 2)).into_par_iter().for_each(|i| {
-            let j = r.ith_rand(i as u64) as usize % n;
-            let lnk = links[j].next;
-            unsafe {
-                if (lnk as usize) < n {
-                    (head_flags.as_ptr() as *mut bool)
-                        .add(lnk as usize)
-                        .write(true);
-                    (links.as_ptr() as *mut Link).add(j).as_mut().unwrap().next = lnk + n as DefInt;
-                }
-            }
-        });
-    }
+    (next.into_vec(), &mut links)
+        .into_par_iter()
+        .for_each(|(n, l)| l.next = n);
+    let head_flags = head_flags.into_vec();
     t.next("set next");
 
     // indices of heads;
@@ -217,8 +187,42 @@ fn bw_decode_(s: &[DefChar]) -> Vec<DefChar> {
 }
 
 pub fn bw_decode(s: &[DefChar]) -> Vec<DefChar> {
+    bw_decode_with::<DefaultArray<bool>, DefaultArray<DefInt>>(s)
+}
+
+/// Runs with the head flags in `F` and the next links in `N`:
+/// `AtomicArray`s (safe) or `RawArray`s (unsafe).
+pub fn bw_decode_with<F, N>(s: &[DefChar]) -> Vec<DefChar>
+where
+    F: SharedArray<bool>,
+    N: SharedArray<DefInt>,
+{
     if s.len() >= 1 << 32 {
         panic!("current implementation can't handle an input this big.");
     }
-    bw_decode_(s)
+    bw_decode_::<F, N>(s)
+}
+
+#[cfg(test)]
+mod bw_tests {
+    use super::*;
+    use crate::algorithm::bw_encode::bw_encode;
+    use crate::common::shared::{AtomicArray, RawArray};
+
+    #[test]
+    fn atomic_and_raw_agree() {
+        let r = Random::new(1);
+        let s: Vec<_> = (0..20000)
+            .map(|i| b'a' + (r.ith_rand(i) % 26) as u8)
+            .collect();
+        let encoded = bw_encode(&s);
+        assert_eq!(
+            bw_decode_with::<AtomicArray<bool>, AtomicArray<DefInt>>(&encoded),
+            s
+        );
+        assert_eq!(
+            bw_decode_with::<RawArray<bool>, RawArray<DefInt>>(&encoded),
+            s
+        );
+    }
 }
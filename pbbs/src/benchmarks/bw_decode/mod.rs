@@ -24,3 +24,6 @@ pub mod list_rank;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 // ============================================================================
+
+pub mod bench;
+pub mod check;
@@ -0,0 +1,91 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::comparison_sort;
+use crate::benchmarks::comparison_sort::check::SortCheck;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::io::{read_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+define_algs!(
+    (STD, "std"),
+    (RAYON, "rayon"),
+    (MERGE, "merge"),
+    (QUICK, "quick"),
+    (BUCKET, "bucket"),
+    (SAMPLE, "sample");
+    serial: STD
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::MERGE;
+
+pub fn run<T, F>(
+    alg: Algs,
+    opts: &TimeOptions,
+    stable: bool,
+    less: F,
+    inp: &[T],
+) -> (Vec<T>, Samples)
+where
+    T: Copy + Send + Sync + Default,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    let f = match alg {
+        Algs::MERGE => comparison_sort::merge_sort::comp_sort,
+        Algs::QUICK => comparison_sort::quick_sort::comp_sort,
+        Algs::BUCKET => comparison_sort::bucket_sort::comp_sort,
+        Algs::SAMPLE => comparison_sort::sample_sort::comp_sort,
+        Algs::STD => comparison_sort::std::comp_sort,
+        Algs::RAYON => comparison_sort::rayon::comp_sort,
+    };
+
+    let mut r = parlay::maybe_uninit_vec![T::default(); inp.len()];
+    let r_clone = unsafe { (&mut r[..] as *mut [T]).as_mut().unwrap() };
+
+    let samples = time_loop(
+        "sort",
+        opts,
+        || {
+            r_clone.copy_from_slice(inp);
+        },
+        || f(&mut r, less, stable),
+        || {},
+    );
+
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let stable = spec.param("stable", false)?;
+    let arr: Vec<i32> = read_file_to_vec(&spec.input, &ReadOptions::with_header(SEQ_INT_HEADER))
+        .map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, stable, |a: i32, b: i32| a < b, &arr);
+    Ok(outcome(spec, alg, d, || SortCheck::new().check(&arr, &r)))
+}
@@ -0,0 +1,99 @@
+use std::marker::PhantomData;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::{read_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+/// Checks that the result is a sorted permutation of the input.
+pub struct SortCheck<T> {
+    _t: PhantomData<T>,
+}
+
+impl<T> SortCheck<T> {
+    pub fn new() -> Self {
+        Self { _t: PhantomData }
+    }
+}
+
+impl<T> Default for SortCheck<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Checker for SortCheck<T>
+where
+    T: Copy + Ord + Debug + FromStr + Send + Sync,
+    <T as FromStr>::Err: Debug + Send,
+{
+    type Input = Vec<T>;
+    type Output = Vec<T>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_file_to_vec(
+            fname,
+            &ReadOptions::with_header(SEQ_INT_HEADER),
+        )?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        if inp.len() != out.len() {
+            return invalid(format!(
+                "the result has {} elements, the input has {}",
+                out.len(),
+                inp.len()
+            ));
+        }
+        if let Some(i) = out.par_windows(2).position_first(|w| w[0] > w[1]) {
+            return invalid(format!(
+                "out of order at {i}: {:?} > {:?}",
+                out[i],
+                out[i + 1]
+            ));
+        }
+        let mut sorted = inp.clone();
+        sorted.par_sort_unstable();
+        match (&sorted, out)
+            .into_par_iter()
+            .position_first(|(s, o)| s != o)
+        {
+            Some(i) => invalid(format!(
+                "not a permutation of the input: {:?} at {i}, expected {:?}",
+                out[i], sorted[i]
+            )),
+            None => Ok(()),
+        }
+    }
+}
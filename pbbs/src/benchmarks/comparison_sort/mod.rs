@@ -1,4 +1,4 @@
-pub(crate) mod std;
+pub mod std;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,8 +25,10 @@ pub(crate) mod std;
 // SOFTWARE.
 // ============================================================================
 
-pub(crate) mod bucket_sort;
-pub(crate) mod merge_sort;
-pub(crate) mod quick_sort;
-pub(crate) mod rayon;
-pub(crate) mod sample_sort;
+pub mod bench;
+pub mod bucket_sort;
+pub mod check;
+pub mod merge_sort;
+pub mod quick_sort;
+pub mod rayon;
+pub mod sample_sort;
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,73 +24,29 @@
 // SOFTWARE.
 // ============================================================================
 
-#[path = "mod.rs"]
-mod comparison_sort;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
+use pbbs::benchmarks::comparison_sort::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::comparison_sort::check::SortCheck;
+use pbbs::common::io::{read_file_to_vec, write_slice_to_file_seq, ReadOptions, SEQ_INT_HEADER};
+use pbbs::{check, define_args, finalize, report, sweep};
 
-use io::{read_file_to_vec, write_slice_to_file_seq};
-use std::time::Duration;
-
-define_args!(Algs::MERGE, (stable, bool, false));
-
-define_algs!(
-    (STD, "std"),
-    (RAYON, "rayon"),
-    (MERGE, "merge"),
-    (QUICK, "quick"),
-    (BUCKET, "bucket"),
-    (SAMPLE, "sample")
-);
-
-pub fn run<T, F>(alg: Algs, rounds: usize, stable: bool, less: F, inp: &[T]) -> (Vec<T>, Duration)
-where
-    T: Copy + Send + Sync + Default,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
-{
-    let f = match alg {
-        Algs::MERGE => comparison_sort::merge_sort::comp_sort,
-        Algs::QUICK => comparison_sort::quick_sort::comp_sort,
-        Algs::BUCKET => comparison_sort::bucket_sort::comp_sort,
-        Algs::SAMPLE => comparison_sort::sample_sort::comp_sort,
-        Algs::STD => comparison_sort::std::comp_sort,
-        Algs::RAYON => comparison_sort::rayon::comp_sort,
-    };
-
-    let mut r = parlay::maybe_uninit_vec![T::default(); inp.len()];
-    let r_clone = unsafe { (&mut r[..] as *mut [T]).as_mut().unwrap() };
-
-    let mean = time_loop(
-        "sort",
-        rounds,
-        Duration::new(1, 0),
-        || {
-            r_clone.copy_from_slice(inp);
-        },
-        || f(&mut r, less, stable),
-        || {},
-    );
-
-    (r, mean)
-}
+define_args!(DEFAULT_ALG, (stable, bool, false));
 
 fn main() {
-    init!();
-
     let args = Args::parse();
+    let pool = args.threads.build_pool();
 
-    let arr: Vec<i32> = read_file_to_vec(
-        &args.ifname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "sequenceInt"),
-        },
-    );
+    let arr: Vec<i32> = read_file_to_vec(&args.ifname, &ReadOptions::with_header(SEQ_INT_HEADER))
+        .expect("cannot read input file");
 
     let less = |a: i32, b: i32| a < b;
 
-    let (r, d) = run(args.algorithm, args.rounds, args.stable, less, &arr);
+    if sweep!(args, |alg| run(alg, &args.time, args.stable, less, &arr).1) {
+        return;
+    }
+
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, args.stable, less, &arr));
 
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, SortCheck::new(), &arr, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,57 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::convex_hull;
+use crate::benchmarks::convex_hull::check::HullCheck;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::{geometry::Point, geometry_io};
+
+define_algs!((Parallel, "parallel"), (Sequential, "sequential"); serial: Sequential);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::Sequential;
+
+pub fn run(alg: Algs, opts: &TimeOptions, pts: &Vec<Point<2, f64>>) -> (Vec<usize>, Samples) {
+    let f = match alg {
+        Algs::Sequential => convex_hull::sequential::hull,
+        Algs::Parallel => convex_hull::parallel::hull,
+    };
+
+    let mut r = Vec::new();
+    let samples = time_loop("hull", opts, || {}, || r = f(pts), || {});
+
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let pts = geometry_io::read_points_from_file::<2, f64>(&spec.input).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &pts);
+    Ok(outcome(spec, alg, d, || HullCheck.check(&pts, &r)))
+}
@@ -0,0 +1,152 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::geometry::{tri_area, Point};
+use crate::common::geometry_io::read_points_from_file;
+use crate::common::io::{read_file_to_vec, ReadOptions};
+
+type P = Point<2, f64>;
+
+/// Relative tolerance for points on the hull's edges.
+const EPS: f64 = 1e-9;
+
+/// Checks that the hull is a strictly convex polygon, in either orientation,
+/// that contains every point.
+pub struct HullCheck;
+
+impl HullCheck {
+    /// Whether `q` is left of or on the line through `a` and `b`.
+    fn left_of(a: P, b: P, q: P) -> bool {
+        tri_area(a, b, q) >= -EPS * (b - a).length() * (q - a).length()
+    }
+
+    /// Whether `q` is inside the counter-clockwise polygon `hull`, by a binary
+    /// search over the fan of triangles at its first vertex.
+    fn contains(hull: &[P], q: P) -> bool {
+        let h = hull.len();
+        let p0 = hull[0];
+        if !Self::left_of(p0, hull[1], q) || !Self::left_of(hull[h - 1], p0, q) {
+            return false;
+        }
+        // the last vertex k < h - 1 with q left of p0 -> hull[k]
+        let (mut lo, mut hi) = (1, h - 2);
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if Self::left_of(p0, hull[mid], q) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Self::left_of(hull[lo], hull[lo + 1], q)
+    }
+}
+
+impl Checker for HullCheck {
+    type Input = Vec<P>;
+    type Output = Vec<usize>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_points_from_file(fname)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, pts: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let n = pts.len();
+        let h = out.len();
+        if let Some(&i) = out.iter().find(|&&i| i >= n) {
+            return invalid(format!("{i} is not a point (n={n})"));
+        }
+        let mut ids = out.clone();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.len() != h {
+            return invalid("the hull repeats a point".to_string());
+        }
+        if h == 0 {
+            return match n {
+                0 => Ok(()),
+                _ => invalid("the hull is empty".to_string()),
+            };
+        }
+        if h < 3 {
+            // all points must be on the segment (or the point) of the hull
+            let (a, b) = (pts[out[0]], pts[out[h - 1]]);
+            return match (0..n)
+                .into_par_iter()
+                .find_any(|&i| !Self::left_of(a, b, pts[i]) || !Self::left_of(b, a, pts[i]))
+            {
+                Some(i) => invalid(format!("point {i} is not on the hull")),
+                None => Ok(()),
+            };
+        }
+
+        let mut hull: Vec<P> = out.iter().map(|&i| pts[i]).collect();
+        if tri_area(hull[0], hull[1], hull[2]) < 0.0 {
+            hull.reverse();
+        }
+        if let Some(i) =
+            (0..h).find(|&i| tri_area(hull[i], hull[(i + 1) % h], hull[(i + 2) % h]) <= 0.0)
+        {
+            return invalid(format!(
+                "the hull is not strictly convex at vertex {}",
+                (i + 1) % h
+            ));
+        }
+        match (0..n)
+            .into_par_iter()
+            .find_any(|&i| !Self::contains(&hull, pts[i]))
+        {
+            Some(i) => invalid(format!("point {i} is outside of the hull")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    #[test]
+    fn convex_hull() {
+        let pts: Vec<P> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.5, 0.5)]
+            .iter()
+            .map(|&(x, y)| Point::new([x, y]))
+            .collect();
+        assert!(HullCheck.check(&pts, &vec![0, 1, 2, 3]).is_ok());
+        assert!(HullCheck.check(&pts, &vec![3, 2, 1, 0]).is_ok());
+        // a point is outside, the hull is not convex or repeats a point
+        assert!(HullCheck.check(&pts, &vec![0, 1, 2]).is_err());
+        assert!(HullCheck.check(&pts, &vec![0, 1, 4, 2, 3]).is_err());
+        assert!(HullCheck.check(&pts, &vec![0, 1, 2, 3, 0]).is_err());
+        assert!(HullCheck.check(&pts, &vec![]).is_err());
+    }
+}
@@ -1,2 +1,4 @@
-pub(crate) mod parallel;
-pub(crate) mod sequential;
+pub mod bench;
+pub mod check;
+pub mod parallel;
+pub mod sequential;
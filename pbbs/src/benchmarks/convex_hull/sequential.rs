@@ -113,7 +113,9 @@ pub fn hull(pts: &Vec<Point<2, f64>>) -> Vec<usize> {
     result.push(leftmost);
     result.extend_from_slice(&indices[..top_solution]);
     result.push(rightmost);
-    result.extend_from_slice(&indices[indices.len() - below_length..indices.len() - below_length + bottom_solution]);
+    result.extend_from_slice(
+        &indices[indices.len() - below_length..indices.len() - below_length + bottom_solution],
+    );
 
     result
 }
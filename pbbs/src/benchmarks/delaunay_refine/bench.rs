@@ -0,0 +1,69 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::delaunay_refine::check::DrCheck;
+use crate::benchmarks::delaunay_refine::incremental;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::geometry::{Point, Triangles};
+use crate::common::geometry_io::read_triangles_from_file;
+
+type P = Point<2, f64>;
+
+define_algs!((INCREMENTAL, "incremental"));
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::INCREMENTAL;
+
+pub fn run(alg: Algs, opts: &TimeOptions, tris: &Triangles<P>) -> (Triangles<P>, Samples) {
+    let f = match alg {
+        Algs::INCREMENTAL => incremental::refine,
+    };
+
+    let mut r = Triangles {
+        p: vec![],
+        t: vec![],
+    };
+    let samples = time_loop(
+        "dr",
+        opts,
+        || {},
+        || {
+            f(tris, &mut r);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let tris = read_triangles_from_file(&spec.input, 0);
+    let (r, d) = run(alg, &spec.time, &tris);
+    Ok(outcome(spec, alg, d, || DrCheck.check(&tris, &r)))
+}
@@ -0,0 +1,189 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::geometry::{min_angle_check, on_parabola, tri_area, Point, Triangles};
+use crate::common::geometry_io::read_triangles_from_file;
+
+type P = Point<2, f64>;
+
+/// The smallest angle refinement must reach, in degrees.
+const MIN_ANGLE: f64 = 30.0;
+
+/// Relative tolerance for the in-circle test of points read back from text.
+const EPS: f64 = 1e-9;
+
+/// Checks that the result is a Delaunay triangulation of the input's domain
+/// in which only triangles on its boundary have an angle below `MIN_ANGLE`.
+pub struct DrCheck;
+
+impl DrCheck {
+    /// Whether `d` is strictly inside the circle through `a`, `b` and `c`.
+    fn in_circle(a: P, b: P, c: P, d: P) -> bool {
+        let (ad, bd, cd) = (on_parabola(a - d), on_parabola(b - d), on_parabola(c - d));
+        ad.cross(bd).dot(cd) > EPS * ad.length() * bd.length() * cd.length()
+    }
+
+    fn area(tris: &Triangles<P>) -> f64 {
+        tris.t
+            .par_iter()
+            .map(|t| {
+                tri_area(
+                    tris.p[t[0] as usize],
+                    tris.p[t[1] as usize],
+                    tris.p[t[2] as usize],
+                )
+                .abs()
+            })
+            .sum::<f64>()
+            / 2.0
+    }
+}
+
+impl Checker for DrCheck {
+    type Input = Triangles<P>;
+    type Output = Triangles<P>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_triangles_from_file(fname, 0))
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_triangles_from_file(fname, 0))
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let (n, p) = (out.num_points(), &out.p);
+        if let Some(t) = out
+            .t
+            .par_iter()
+            .find_any(|t| t.iter().any(|&v| v < 0 || v as usize >= n))
+        {
+            return invalid(format!("triangle {t:?} has a vertex out of range (n={n})"));
+        }
+        let pt = |t: &[i32; 3], i: usize| p[t[i % 3] as usize];
+        if let Some(t) = out
+            .t
+            .par_iter()
+            .find_any(|t| tri_area(pt(t, 0), pt(t, 1), pt(t, 2)) <= 0.0)
+        {
+            return invalid(format!("triangle {t:?} is not counter-clockwise"));
+        }
+
+        let (inp_area, out_area) = (Self::area(inp), Self::area(out));
+        // refinement only splits triangles, so the area is kept up to rounding
+        if (inp_area - out_area).abs() > 1e-6 * inp_area {
+            return invalid(format!(
+                "the result covers an area of {out_area}, the input {inp_area}"
+            ));
+        }
+
+        // every directed edge (u, v) with its triangle and the opposite corner
+        let mut edges: Vec<(i32, i32, usize, usize)> = out
+            .t
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(j, t)| (0..3).map(move |i| (t[i], t[(i + 1) % 3], j, (i + 2) % 3)))
+            .collect();
+        edges.par_sort_unstable_by_key(|&(u, v, _, _)| (u.min(v), u.max(v)));
+        let key = |e: &(i32, i32, usize, usize)| (e.0.min(e.1), e.0.max(e.1));
+
+        let mut boundary = vec![false; out.num_triangles()];
+        let mut i = 0;
+        while i < edges.len() {
+            let mut j = i + 1;
+            while j < edges.len() && key(&edges[j]) == key(&edges[i]) {
+                j += 1;
+            }
+            match j - i {
+                1 => boundary[edges[i].2] = true,
+                2 => {
+                    let ((u0, ..), (u1, ..)) = (edges[i], edges[i + 1]);
+                    if u0 == u1 {
+                        return invalid(format!("two triangles overlap along an edge of {u0}"));
+                    }
+                    for (a, b) in [(i, i + 1), (i + 1, i)] {
+                        let (ta, tb) = (&out.t[edges[a].2], &out.t[edges[b].2]);
+                        let d = pt(tb, edges[b].3);
+                        if Self::in_circle(pt(ta, 0), pt(ta, 1), pt(ta, 2), d) {
+                            return invalid(format!(
+                                "triangles {ta:?} and {tb:?} are not Delaunay"
+                            ));
+                        }
+                    }
+                }
+                k => {
+                    let (u, v) = key(&edges[i]);
+                    return invalid(format!("edge ({u}, {v}) is shared by {k} triangles"));
+                }
+            }
+            i = j;
+        }
+
+        let skinny = (0..out.num_triangles())
+            .into_par_iter()
+            .filter(|&j| {
+                let t = &out.t[j];
+                !boundary[j] && min_angle_check(pt(t, 0), pt(t, 1), pt(t, 2), MIN_ANGLE)
+            })
+            .count();
+        if skinny != 0 {
+            return invalid(format!(
+                "{skinny} inner triangles have an angle below {MIN_ANGLE} degrees"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    fn triangles(p: &[(f64, f64)], t: Vec<[i32; 3]>) -> Triangles<P> {
+        Triangles::new(p.iter().map(|&(x, y)| Point::new([x, y])).collect(), t)
+    }
+
+    #[test]
+    fn delaunay_triangulation() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let inp = triangles(&square, vec![[0, 1, 2], [0, 2, 3]]);
+        assert!(DrCheck.check(&inp, &inp).is_ok());
+        // clockwise, or missing a part of the domain
+        let out = triangles(&square, vec![[0, 2, 1], [0, 2, 3]]);
+        assert!(DrCheck.check(&inp, &out).is_err());
+        let out = triangles(&square, vec![[0, 1, 2]]);
+        assert!(DrCheck.check(&inp, &out).is_err());
+
+        // the corner 1 is inside the circle through 0, 2 and 3
+        let kite = triangles(
+            &[(0.0, 0.0), (2.0, -1.0), (4.0, 0.0), (2.0, 1.0)],
+            vec![[0, 1, 2], [0, 2, 3]],
+        );
+        assert!(DrCheck.check(&kite, &kite).is_err());
+    }
+}
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,58 +24,26 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
+use pbbs::benchmarks::delaunay_refine::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::delaunay_refine::check::DrCheck;
+use pbbs::common::geometry_io::{read_triangles_from_file, write_triangles_to_file};
+use pbbs::{check, define_args, report, sweep};
 
-#[path = "../../common/mod.rs"]
-mod common;
-#[path = "mod.rs"]
-mod dr;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-
-use common::geometry::{Point, Triangles};
-use common::geometry_io::{read_triangles_from_file, write_triangles_to_file};
-use dr::incremental;
-use misc::*;
-
-type P = Point<2, f64>;
-
-define_args!(Algs::INCREMENTAL);
-
-define_algs!((INCREMENTAL, "incremental"));
-
-pub fn run(alg: Algs, rounds: usize, tris: &Triangles<P>) -> (Triangles<P>, Duration) {
-    let f = match alg {
-        Algs::INCREMENTAL => incremental::refine,
-    };
-
-    let mut r = Triangles {
-        p: vec![],
-        t: vec![],
-    };
-    let mean = time_loop(
-        "dr",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            f(tris, &mut r);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG);
 
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let tris = read_triangles_from_file(&args.ifname, 0);
-    let (r, d) = run(args.algorithm, args.rounds, &tris);
+    if sweep!(args, |alg| run(alg, &args.time, &tris).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &tris));
 
     if !args.ofname.is_empty() {
-        write_triangles_to_file(&r, args.ofname);
+        write_triangles_to_file(&r, &args.ofname);
     }
-    println!("{:?}", d);
+    println!("{:?}", d.mean());
+    let verdict = check!(args, DrCheck, &tris, &r);
+    report!(args, d, verdict);
 }
@@ -1,4 +1,4 @@
-pub(crate) mod incremental;
+pub mod incremental;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -24,3 +24,6 @@ pub(crate) mod incremental;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 // ============================================================================
+
+pub mod bench;
+pub mod check;
@@ -0,0 +1,80 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::{read_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+/// Checks the bucket counts of a histogram. With zero `buckets`, the number
+/// of buckets is taken from the result.
+pub struct HistCheck {
+    pub buckets: usize,
+}
+
+impl Checker for HistCheck {
+    type Input = Vec<u32>;
+    type Output = Vec<u32>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_file_to_vec(
+            fname,
+            &ReadOptions::with_header(SEQ_INT_HEADER),
+        )?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let buckets = if self.buckets == 0 {
+            out.len()
+        } else {
+            self.buckets
+        };
+        if out.len() < buckets {
+            return invalid(format!("{} buckets, expected {buckets}", out.len()));
+        }
+        if let Some(&k) = inp.par_iter().find_any(|&&k| k as usize >= buckets) {
+            return Err(CheckError::Format(format!(
+                "key {k} does not fit {buckets} buckets"
+            )));
+        }
+
+        let mut sorted = inp.clone();
+        sorted.par_sort_unstable();
+        let mut hist = vec![0; out.len()];
+        for &k in sorted.iter() {
+            hist[k as usize] += 1;
+        }
+
+        let diff_count = (&hist, out).into_par_iter().filter(|(h, o)| h != o).count();
+        if diff_count != 0 {
+            return invalid(format!("{diff_count} buckets have wrong counts"));
+        }
+        Ok(())
+    }
+}
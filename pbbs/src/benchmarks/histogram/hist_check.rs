@@ -1,4 +1,4 @@
-#![feature(slice_partition_dedup)]
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,57 +25,15 @@
 // SOFTWARE.
 // ============================================================================
 
-use clap::Parser;
-use rayon::prelude::*;
+use pbbs::benchmarks::histogram::check::HistCheck;
+use pbbs::define_check_args;
 
-#[path ="../../common/io.rs"] mod io;
-use io::read_file_to_vec;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// BW results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-
-    /// the number of buckets
-    #[clap(short, long, value_parser, required=false, default_value_t=0)]
-    buckets: usize,
-}
-
-pub fn check(inp: &mut [usize], out: &mut [usize], buckets: usize) -> bool {
-    assert_eq!(out.len(), buckets);
-
-    inp.par_sort_unstable();
-    let mut hist = vec![0; buckets];
-
-    for &i in inp.iter() { hist[i] += 1; }
-
-    let mut diff_count = 0usize;
-    for i in 0..hist.len() {
-        if hist[i] != out[i] {
-            diff_count+=1;
-        }
-    }
-
-    if diff_count!=0 {
-        eprintln!("output file has {diff_count} differences.");
-        false
-    } else { true }
-}
+define_check_args!((buckets, usize, 0));
 
 fn main() {
-    eprintln!("WARNING: hist_check needs improvement.");
     let args = Args::parse();
-    let mut inp = read_file_to_vec(
-        &args.ifname,
-        Some(|a: &[&str]| assert_eq!(a[0], "sequenceInt"))
-    );
-    let mut out = read_file_to_vec(&args.rfname, Some(|_: &[&str]| {}));
-    if check(&mut inp, &mut out, args.buckets) { println!("OK"); }
-    else { eprintln!("ERR"); std::process::exit(1); }
+    let checker = HistCheck {
+        buckets: args.buckets,
+    };
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
@@ -1,4 +1,3 @@
-use std::time::Duration;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -27,24 +26,18 @@ use std::time::Duration;
 
 use clap::Parser;
 
-#[path = "mod.rs"]
-mod hist;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-
-use hist::{parallel, sequential};
-use io::{read_big_file_to_vec, write_slice_to_file_seq};
-use misc::*;
+use pbbs::benchmarks::histogram::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::histogram::check::HistCheck;
+use pbbs::common::io::{
+    read_big_file_to_vec, write_slice_to_file_seq, ReadOptions, SEQ_INT_HEADER,
+};
+use pbbs::{check, finalize, report, sweep};
 
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
 struct Args {
     /// the algorithm to use
-    #[clap(short, long, value_parser, default_value_t = Algs::PARALLEL)]
+    #[clap(short, long, value_parser, default_value_t = DEFAULT_ALG)]
     algorithm: Algs,
 
     /// the output filename
@@ -55,52 +48,46 @@ struct Args {
     #[clap(value_parser, required = true)]
     ifname: String,
 
-    /// the number of rounds to execute the benchmark
-    #[clap(short, long, value_parser, required = false, default_value_t = 1)]
-    rounds: usize,
+    #[clap(flatten)]
+    time: pbbs::common::time_loop::TimeOptions,
+
+    #[clap(flatten)]
+    threads: pbbs::common::threads::ThreadOptions,
 
     #[clap(short, long, value_parser, required = true)]
     buckets: usize,
-}
 
-define_algs!((PARALLEL, "parallel"), (SEQUENTIAL, "sequential"));
+    /// validate the result after timing
+    #[clap(long, value_parser, required = false, default_value_t = false)]
+    check: bool,
 
-pub fn run(alg: Algs, rounds: usize, buckets: usize, arr: &[u32]) -> (Vec<u32>, Duration) {
-    let f = match alg {
-        Algs::PARALLEL => parallel::hist,
-        Algs::SEQUENTIAL => sequential::hist,
-    };
-
-    let mut r = vec![];
-    let r_ptr = &r as *const Vec<u32> as usize;
-
-    let mean = time_loop(
-        "hist",
-        rounds,
-        Duration::new(1, 0),
-        || unsafe {
-            *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![];
-        },
-        || {
-            f(&arr, buckets, &mut r);
-        },
-        || {},
-    );
-    (r, mean)
+    #[clap(flatten)]
+    report: pbbs::rpb_bench::ReportArgs,
 }
 
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let mut arr = Vec::new();
     read_big_file_to_vec(
         &args.ifname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "sequenceInt"),
-        },
+        &ReadOptions::with_header(SEQ_INT_HEADER),
         &mut arr,
-    );
-    let (r, d) = run(args.algorithm, args.rounds, args.buckets, &arr);
+    )
+    .expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, args.buckets, &arr).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, args.buckets, &arr));
 
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(
+        args,
+        HistCheck {
+            buckets: args.buckets
+        },
+        &arr,
+        &r
+    );
+    report!(args, d, verdict);
 }
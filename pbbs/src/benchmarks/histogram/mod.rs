@@ -1,4 +1,4 @@
-pub(crate) mod parallel;
+pub mod parallel;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,4 +25,6 @@ pub(crate) mod parallel;
 // SOFTWARE.
 // ============================================================================
 
-pub(crate) mod sequential;
+pub mod bench;
+pub mod check;
+pub mod sequential;
@@ -0,0 +1,77 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::integer_sort::check::IsortCheck;
+use crate::benchmarks::integer_sort::parallel_radix_sort;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::io::{read_big_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+define_algs!((PARRADIX, "parradix"));
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::PARRADIX;
+
+pub fn run(alg: Algs, opts: &TimeOptions, g: &[u32], bits: usize) -> (Vec<u32>, Samples) {
+    let f = match alg {
+        Algs::PARRADIX => parallel_radix_sort::int_sort,
+    };
+
+    let mut r = parlay::maybe_uninit_vec![];
+    let r_ptr = &r as *const Vec<u32> as usize;
+
+    let samples = time_loop(
+        "isort",
+        opts,
+        || unsafe {
+            *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![];
+        },
+        || {
+            f(g, bits, &mut r);
+        },
+        || {},
+    );
+
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let bits = spec.param("bits", 0)?;
+    let mut arr = Vec::new();
+    read_big_file_to_vec(
+        &spec.input,
+        &ReadOptions::with_header(SEQ_INT_HEADER),
+        &mut arr,
+    )
+    .map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &arr, bits);
+    Ok(outcome(spec, alg, d, || {
+        IsortCheck { bits }.check(&arr, &r)
+    }))
+}
@@ -0,0 +1,84 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::{read_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+/// Checks that the result is a permutation of the input sorted by the
+/// lowest `bits` bits of each key (all bits if `bits` is zero).
+pub struct IsortCheck {
+    pub bits: usize,
+}
+
+impl IsortCheck {
+    fn key(&self, x: u32) -> u32 {
+        match self.bits {
+            0 | 32.. => x,
+            b => x & ((1 << b) - 1),
+        }
+    }
+}
+
+impl Checker for IsortCheck {
+    type Input = Vec<u32>;
+    type Output = Vec<u32>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_file_to_vec(
+            fname,
+            &ReadOptions::with_header(SEQ_INT_HEADER),
+        )?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        if inp.len() != out.len() {
+            return invalid(format!(
+                "the result has {} keys, the input has {}",
+                out.len(),
+                inp.len()
+            ));
+        }
+        let violations = out
+            .par_windows(2)
+            .filter(|w| self.key(w[0]) > self.key(w[1]))
+            .count();
+        if violations != 0 {
+            return invalid(format!("{violations} keys are out of order"));
+        }
+        let (mut a, mut b) = (inp.clone(), out.clone());
+        a.par_sort_unstable();
+        b.par_sort_unstable();
+        if a != b {
+            return invalid("the result is not a permutation of the input".to_string());
+        }
+        Ok(())
+    }
+}
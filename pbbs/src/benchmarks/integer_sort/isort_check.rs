@@ -1,4 +1,4 @@
-use clap::Parser;
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,32 +25,13 @@ use clap::Parser;
 // SOFTWARE.
 // ============================================================================
 
+use pbbs::benchmarks::integer_sort::check::IsortCheck;
+use pbbs::define_check_args;
 
-use pbbs::common::io::read_file_to_vec;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// sort results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-}
-
-pub fn check(arr: &[usize]) -> Result<(), String> {
-    let mut violation_no = 0usize;
-    for i in 0..arr.len()-1 {
-        if arr[i] > arr[i+1] { violation_no += 1; }
-    }
-    if violation_no != 0 { Err("{violation_no} violations}") }
-    else { Ok(()) }
-}
+define_check_args!((bits, usize, 0));
 
 fn main() {
     let args = Args::parse();
-    let r: Vec<usize> = read_file_to_vec(
-        &args.rfname,
-        Some{ 0:|_: &[&str]| {debug_assert_eq!(true, true)} }
-    );
-
-    check(&r).unwrap();
+    let checker = IsortCheck { bits: args.bits };
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,57 +24,31 @@
 // SOFTWARE.
 // ============================================================================
 
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "mod.rs"]
-mod isort;
-#[path = "../macros.rs"]
-mod macros;
+use pbbs::benchmarks::integer_sort::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::integer_sort::check::IsortCheck;
+use pbbs::common::io::{
+    read_big_file_to_vec, write_slice_to_file_seq, ReadOptions, SEQ_INT_HEADER,
+};
+use pbbs::{check, define_args, finalize, report, sweep};
 
-use io::{read_big_file_to_vec, write_slice_to_file_seq};
-use std::time::Duration;
-
-define_args!(Algs::PARRADIX, (bits, usize, 0));
-
-define_algs!((PARRADIX, "parradix"));
-
-pub fn run(alg: Algs, rounds: usize, g: &[u32], bits: usize) -> (Vec<u32>, Duration) {
-    let f = match alg {
-        Algs::PARRADIX => isort::parallel_radix_sort::int_sort,
-    };
-
-    let mut r = parlay::maybe_uninit_vec![];
-    let r_ptr = &r as *const Vec<u32> as usize;
-
-    let mean = time_loop(
-        "isort",
-        rounds,
-        Duration::new(1, 0),
-        || unsafe {
-            *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![];
-        },
-        || {
-            f(&g, bits, &mut r);
-        },
-        || {},
-    );
-
-    (r, mean)
-}
+define_args!(DEFAULT_ALG, (bits, usize, 0));
 
 fn main() {
-    init!();
-
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let mut arr = Vec::new();
     read_big_file_to_vec(
         &args.ifname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "sequenceInt"),
-        },
+        &ReadOptions::with_header(SEQ_INT_HEADER),
         &mut arr,
-    );
-    let (r, d) = run(args.algorithm, args.rounds, &arr, args.bits);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    )
+    .expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &arr, args.bits).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &arr, args.bits));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, IsortCheck { bits: args.bits }, &arr, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+use std::time::Duration;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+
+use crate::benchmarks::inverted_index::check::IndexCheck;
+use crate::benchmarks::inverted_index::{index_parallel, index_serial};
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::io::chars_from_file;
+use crate::common::time_loop::Warmup;
+use crate::misc::*;
+
+/// the tag that starts a document of the input.
+pub const DOC_START: &str = "<doc";
+
+define_algs!((Serial, "serial"), (Parallel, "parallel"); serial: Serial);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::Serial;
+
+/// the warm-up the benchmark runs by default.
+pub const DEFAULT_WARMUP: Warmup = Warmup::Time(Duration::from_secs(2));
+
+pub fn run(alg: Algs, s: &[DefChar], start: &str, opts: &TimeOptions) -> (Vec<char>, Samples) {
+    let f = match alg {
+        Algs::Serial => index_serial::build_index,
+        Algs::Parallel => index_parallel::build_index,
+    };
+
+    // convert u8 (DefChar) to char type
+    // let vec_s: Vec<char> = s.iter().map(|c| *c as char).collect();
+
+    let r: RefCell<Vec<char>> = RefCell::new(Vec::new());
+
+    let samples = time_loop(
+        "index",
+        opts,
+        || {
+            r.borrow_mut().clear();
+        },
+        || {
+            f(s, start, &mut r.borrow_mut());
+        },
+        || {},
+    );
+
+    (r.into_inner(), samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let arr = chars_from_file(&spec.input, false).map_err(RunError::input)?;
+    let (r, d) = run(alg, &arr, DOC_START, &spec.time);
+    Ok(outcome(spec, alg, d, || {
+        IndexCheck {
+            doc_start: DOC_START,
+        }
+        .check(&arr, &r.iter().collect())
+    }))
+}
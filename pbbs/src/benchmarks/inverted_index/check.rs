@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::chars_from_file;
+use crate::DefChar;
+
+/// Checks an inverted index, one `word doc_id...` line per word in sorted
+/// order, against one built with a sorted map. The doc ids of a word may
+/// come in any order. Documents start at each
+/// `doc_start`; words are maximal runs of ASCII letters, ignoring case.
+pub struct IndexCheck<'a> {
+    pub doc_start: &'a str,
+}
+
+impl IndexCheck<'_> {
+    fn build_index(&self, s: &[DefChar]) -> String {
+        let delim = self.doc_start.as_bytes();
+        let m = delim.len();
+        let starts: Vec<usize> =
+            (0..s.len())
+                .filter(|&i| s[i..].starts_with(delim))
+                .fold(vec![], |mut v, i| {
+                    if v.last().is_none_or(|&l| i >= l + m) {
+                        v.push(i);
+                    }
+                    v
+                });
+
+        let mut words: BTreeMap<Vec<DefChar>, Vec<usize>> = BTreeMap::new();
+        for (doc_id, &b) in starts.iter().enumerate() {
+            let e = starts.get(doc_id + 1).copied().unwrap_or(s.len());
+            for w in s[b + m..e].split(|c| !c.is_ascii_alphabetic()) {
+                if w.is_empty() {
+                    continue;
+                }
+                let docs = words.entry(w.to_ascii_lowercase()).or_default();
+                if docs.last() != Some(&doc_id) {
+                    docs.push(doc_id);
+                }
+            }
+        }
+
+        words
+            .into_iter()
+            .map(|(w, docs)| {
+                let docs: Vec<_> = docs.iter().map(usize::to_string).collect();
+                format!("{} {}\n", String::from_utf8(w).unwrap(), docs.join(" "))
+            })
+            .collect()
+    }
+}
+
+impl Checker for IndexCheck<'_> {
+    type Input = Vec<DefChar>;
+    type Output = String;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(std::fs::read_to_string(fname)?)
+    }
+
+    fn check(&self, s: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let expected = self.build_index(s);
+        let diff = expected
+            .lines()
+            .zip(out.lines())
+            .position(|(e, o)| e != normalize(o));
+        match diff {
+            Some(i) => invalid(format!(
+                "line {}: found {:?}, expected {:?}",
+                i + 1,
+                out.lines().nth(i).unwrap(),
+                expected.lines().nth(i).unwrap()
+            )),
+            None if expected.lines().count() != out.lines().count() => invalid(format!(
+                "{} words in the index, expected {}",
+                out.lines().count(),
+                expected.lines().count()
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sorts the doc ids of an index line, which may come in any order.
+fn normalize(line: &str) -> String {
+    let mut fields = line.split(' ');
+    let word = fields.next().unwrap_or_default();
+    let mut docs: Vec<_> = fields.map(|d| d.parse::<usize>().ok()).collect();
+    docs.sort_unstable();
+    let docs: Vec<_> = docs
+        .iter()
+        .map(|d| d.map_or("?".to_string(), |d| d.to_string()))
+        .collect();
+    format!("{word} {}", docs.join(" "))
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    #[test]
+    fn inverted_index() {
+        let s = b"<doc>a B<doc>b c".to_vec();
+        let check = |out: &str| IndexCheck { doc_start: "<doc>" }.check(&s, &out.to_string());
+        assert!(check("a 0\nb 1 0\nc 1\n").is_ok());
+        assert!(check("a 0\nb 0\nc 1\n").is_err());
+        assert!(check("a 0\nb 0 1\n").is_err());
+        assert!(check("a 0\nb 0 1\nc 1\nd 1\n").is_err());
+    }
+}
@@ -89,7 +89,8 @@ pub fn build_index(s: &[DefChar], doc_start: &str, result: &mut Vec<char>) {
             word_map
                 .par_iter()
                 .map(|(token, _)| {
-                    let token_str: String = unsafe { std::str::from_utf8_unchecked(token) }.to_string();
+                    let token_str: String =
+                        unsafe { std::str::from_utf8_unchecked(token) }.to_string();
                     (token_str, doc_id)
                 })
                 .collect()
@@ -115,7 +116,7 @@ pub fn build_index(s: &[DefChar], doc_start: &str, result: &mut Vec<char>) {
     // let lookup: HashMap<&str, Vec<usize>> = words.into_iter().collect();
     // let sorted_words: Vec<(&str, Vec<usize>)> =
     //     keys.into_iter().map(|k| (k, lookup[&k].clone())).collect();
-    
+
     // no order preserving on equal words -- all unique
     words.par_sort_unstable_by_key(|&(word, _)| word);
     t.next("sort words");
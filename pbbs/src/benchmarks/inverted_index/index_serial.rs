@@ -1,15 +1,16 @@
+use crate::misc::DefChar;
 use parlay::Timer;
 use std::collections::HashMap;
-use crate::misc::DefChar;
 
 pub fn search(input: &[DefChar], delim: &[DefChar]) -> usize {
     if delim.len() == 0 {
         return 0;
     }
 
-    input.windows(delim.len())
-         .position(|window| window == delim)
-         .unwrap_or(input.len()) // out of bound index
+    input
+        .windows(delim.len())
+        .position(|window| window == delim)
+        .unwrap_or(input.len()) // out of bound index
 }
 
 pub fn build_index(s: &[DefChar], doc_start: &str, result: &mut Vec<char>) {
@@ -1,2 +1,4 @@
+pub mod bench;
+pub mod check;
 pub mod index_parallel;
 pub mod index_serial;
@@ -0,0 +1,72 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::longest_repeated_sub_string::check::LrsCheck;
+use crate::benchmarks::longest_repeated_sub_string::doubling;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::io::chars_from_file;
+use crate::common::shared::{AtomicArray, RawArray};
+use crate::misc::*;
+
+define_algs!(
+    (Doubling, "doubling"),
+    (DoublingAtomic, "doubling-atomic"),
+    (DoublingRaw, "doubling-raw")
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::Doubling;
+
+pub fn run(alg: Algs, opts: &TimeOptions, inp: &[DefChar]) -> ((usize, usize, usize), Samples) {
+    let f = match alg {
+        Algs::Doubling => doubling::lrs,
+        Algs::DoublingAtomic => doubling::lrs_with::<AtomicArray<DefInt>>,
+        Algs::DoublingRaw => doubling::lrs_with::<RawArray<DefInt>>,
+    };
+
+    let mut r = (0, 0, 0);
+
+    let samples = time_loop(
+        "lrs",
+        opts,
+        || {},
+        || {
+            r = f(inp);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let arr = chars_from_file(&spec.input, false).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &arr);
+    Ok(outcome(spec, alg, d, || LrsCheck.check(&arr, &r)))
+}
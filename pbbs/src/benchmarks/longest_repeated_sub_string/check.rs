@@ -0,0 +1,129 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::chars_from_file;
+use crate::DefChar;
+
+/// (length, first location, second location)
+type Lrs = (usize, usize, usize);
+
+/// Checks that the two locations start the same substring and that no
+/// substring one char longer repeats. Windows are compared by a rolling hash
+/// and equal hashes by their chars.
+pub struct LrsCheck;
+
+impl LrsCheck {
+    /// A repeated substring of length `k`, if there is one.
+    fn find_repeat(&self, s: &[DefChar], k: usize) -> Option<(usize, usize)> {
+        const BASE: u64 = 0x100000001b3;
+        if k == 0 || k >= s.len() {
+            return None;
+        }
+        let mut prefix = Vec::with_capacity(s.len() + 1);
+        prefix.push(0u64);
+        for &c in s {
+            let h = *prefix.last().unwrap();
+            prefix.push(h.wrapping_mul(BASE).wrapping_add(c as u64 + 1));
+        }
+        let power = (0..k).fold(1u64, |p, _| p.wrapping_mul(BASE));
+
+        let mut windows: Vec<(u64, usize)> = (0..=s.len() - k)
+            .into_par_iter()
+            .map(|i| (prefix[i + k].wrapping_sub(prefix[i].wrapping_mul(power)), i))
+            .collect();
+        windows.par_sort_unstable();
+        windows.par_windows(2).find_map_first(|w| {
+            let ((h0, i), (h1, j)) = (w[0], w[1]);
+            (h0 == h1 && s[i..i + k] == s[j..j + k]).then_some((i, j))
+        })
+    }
+}
+
+impl Checker for LrsCheck {
+    type Input = Vec<DefChar>;
+    type Output = Lrs;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    /// Reads `len:<len>\tloc1:<loc1>\tloc2:<loc2>`.
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        let out = std::fs::read_to_string(fname)?;
+        let fields: Vec<_> = out
+            .split_whitespace()
+            .zip(["len:", "loc1:", "loc2:"])
+            .filter_map(|(f, name)| f.strip_prefix(name)?.parse::<usize>().ok())
+            .collect();
+        match fields[..] {
+            [len, loc1, loc2] => Ok((len, loc1, loc2)),
+            _ => Err(CheckError::Format(format!(
+                "can not parse {:?}",
+                out.trim()
+            ))),
+        }
+    }
+
+    fn check(&self, s: &Self::Input, &(len, loc1, loc2): &Self::Output) -> Result<(), CheckError> {
+        let n = s.len();
+        if len > 0 {
+            if loc1 == loc2 || loc1.max(loc2) + len > n {
+                return invalid(format!(
+                    "locations {loc1} and {loc2} can not hold two substrings of length {len}"
+                ));
+            }
+            if s[loc1..loc1 + len] != s[loc2..loc2 + len] {
+                return invalid(format!("the substrings at {loc1} and {loc2} differ"));
+            }
+        }
+        match self.find_repeat(s, len + 1) {
+            Some((i, j)) => invalid(format!(
+                "the substrings of length {} at {i} and {j} repeat",
+                len + 1
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    #[test]
+    fn longest_repeat() {
+        let s = b"banana".to_vec();
+        assert!(LrsCheck.check(&s, &(3, 1, 3)).is_ok());
+        assert!(LrsCheck.check(&s, &(3, 3, 1)).is_ok());
+        // a longer substring repeats
+        assert!(LrsCheck.check(&s, &(2, 1, 3)).is_err());
+        assert!(LrsCheck.check(&s, &(3, 0, 2)).is_err());
+        assert!(LrsCheck.check(&s, &(3, 1, 4)).is_err());
+        assert!(LrsCheck.check(&b"abc".to_vec(), &(0, 0, 0)).is_ok());
+    }
+}
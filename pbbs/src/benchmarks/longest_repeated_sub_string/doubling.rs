@@ -25,25 +25,28 @@ use rayon::prelude::*;
 // SOFTWARE.
 // ============================================================================
 
-use crate::DefChar;
-
-#[cfg(any(feature = "AW_safe", feature = "sng_ind_atomic"))]
-use crate::{lcp::atomic_lcp, suffix_array::atomic_suffix_array, DefAtomInt, ORDER};
-#[cfg(not(any(feature = "AW_safe", feature = "sng_ind_atomic")))]
-use crate::{lcp::lcp, suffix_array::suffix_array, DefInt};
+use crate::algorithm::lcp::lcp;
+use crate::algorithm::suffix_array::suffix_array_with;
+use crate::common::shared::{DefaultArray, SharedArray};
+use crate::{DefChar, DefInt};
 
 type Result = (usize, usize, usize);
 
-#[cfg(not(any(feature = "AW_safe", feature = "sng_ind_atomic")))]
 pub fn lrs(s: &[DefChar]) -> Result {
+    lrs_with::<DefaultArray<DefInt>>(s)
+}
+
+/// Runs with the suffix array and the lcps in `A`: `AtomicArray` (safe) or
+/// `RawArray` (unsafe).
+pub fn lrs_with<A: SharedArray<DefInt>>(s: &[DefChar]) -> Result {
     let mut t = parlay::Timer::new("lrs"); //t.start();
 
-    let mut sa: Vec<DefInt> = parlay::maybe_uninit_vec![
-        DefInt::default(); s.len()];
-    suffix_array(s, &mut sa);
+    let sa = A::new(s.len(), 0);
+    suffix_array_with(s, &sa);
+    let sa = sa.into_vec();
     t.next("suffix array");
 
-    let lcps = lcp(s, &sa);
+    let lcps = lcp::<A>(s, &sa);
     t.next("lcps");
 
     let idx = (&lcps)
@@ -56,40 +59,25 @@ pub fn lrs(s: &[DefChar]) -> Result {
     (lcps[idx] as usize, sa[idx] as usize, sa[idx + 1] as usize)
 }
 
-#[cfg(any(feature = "AW_safe", feature = "sng_ind_atomic"))]
-pub fn lrs(s: &[DefChar]) -> Result {
-    let mut t = parlay::Timer::new("lrs"); //t.start();
-
-    let mut sa: Vec<_> = (0..s.len())
-        .into_par_iter()
-        .map(|_| DefAtomInt::default())
-        .collect();
-    atomic_suffix_array(s, &mut sa);
-    t.next("suffix array");
-
-    let lcps = atomic_lcp(s, &sa);
-    t.next("lcps");
-
-    let dummy = DefAtomInt::default();
-    let idx = (&lcps)
-        .into_par_iter()
-        .enumerate()
-        .reduce(
-            || (0, &dummy),
-            |a, b| {
-                if a.1.load(ORDER) < b.1.load(ORDER) {
-                    b
-                } else {
-                    a
-                }
-            },
-        )
-        .0;
-    t.next("max element");
+#[cfg(test)]
+mod lrs_tests {
+    use super::*;
+    use crate::benchmarks::longest_repeated_sub_string::check::LrsCheck;
+    use crate::common::check::Checker;
+    use crate::common::shared::{AtomicArray, RawArray};
+    use parlay::random::Random;
 
-    (
-        lcps[idx].load(ORDER) as usize,
-        sa[idx].load(crate::ORDER) as usize,
-        sa[idx + 1].load(crate::ORDER) as usize,
-    )
+    #[test]
+    fn atomic_and_raw_agree() {
+        // a repeat longer than the first round of the lcps.
+        let r = Random::new(1);
+        let half: Vec<_> = (0..3000)
+            .map(|i| b'a' + (r.ith_rand(i) % 4) as u8)
+            .collect();
+        let s = [&half[..], b"x", &half[..]].concat();
+        let atomic = lrs_with::<AtomicArray<DefInt>>(&s);
+        assert_eq!(atomic.0, half.len());
+        assert!(LrsCheck.check(&s, &atomic).is_ok());
+        assert_eq!(lrs_with::<RawArray<DefInt>>(&s), atomic);
+    }
 }
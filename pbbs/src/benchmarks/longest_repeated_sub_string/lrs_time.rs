@@ -24,62 +24,29 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
-
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../../algorithm/lcp.rs"]
-mod lcp;
-#[path = "mod.rs"]
-mod lrs;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "../../algorithm/range_min.rs"]
-mod range_min;
-#[path = "../../algorithm/suffix_array.rs"]
-mod suffix_array;
-
-use io::{chars_from_file, chars_to_file};
-use lrs::doubling;
-use misc::*;
-
-define_args!(Algs::Doubling);
-define_algs!((Doubling, "doubling"));
-
-pub fn run(alg: Algs, rounds: usize, inp: &[DefChar]) -> ((usize, usize, usize), Duration) {
-    let f = match alg {
-        Algs::Doubling => doubling::lrs,
-    };
-
-    let mut r = (0, 0, 0);
-
-    let mean = time_loop(
-        "lrs",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            r = f(&inp);
-        },
-        || {},
-    );
-    (r, mean)
-}
+use pbbs::benchmarks::longest_repeated_sub_string::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::longest_repeated_sub_string::check::LrsCheck;
+use pbbs::common::io::{chars_from_file, chars_to_file};
+use pbbs::{check, define_args, report, sweep};
 
+define_args!(DEFAULT_ALG);
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let arr = chars_from_file(&args.ifname, false).unwrap();
-    let ((len, loc1, loc2), d) = run(args.algorithm, args.rounds, &arr);
+    if sweep!(args, |alg| run(alg, &args.time, &arr).1) {
+        return;
+    }
+    let ((len, loc1, loc2), d) = pool.install(|| run(args.algorithm, &args.time, &arr));
 
     let out = format!("len:{len}\tloc1:{loc1}\tloc2:{loc2}");
     if !args.ofname.is_empty() {
-        chars_to_file(out.as_bytes(), args.ofname).unwrap();
+        chars_to_file(out.as_bytes(), &args.ofname).unwrap();
     } else {
         println!("{}", out);
     }
 
-    println!("{:?}", d);
+    println!("{:?}", d.mean());
+    let verdict = check!(args, LrsCheck, &arr, &(len, loc1, loc2));
+    report!(args, d, verdict);
 }
@@ -26,11 +26,10 @@
 // ============================================================================
 
 macro_rules! define_algs {
-    ($(($alg: ident, $name: expr)),*) => {
+    (@algs $(($alg: ident, $name: expr)),*) => {
         use std::fmt;
         use clap::ValueEnum;
-        #[path ="../../common/time_loop.rs"] mod time_loop;
-        use time_loop::time_loop;
+        use $crate::common::time_loop::{time_loop, Samples, TimeOptions};
 
         #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
         pub enum Algs { $($alg,)* }
@@ -42,11 +41,37 @@ macro_rules! define_algs {
                 }
             }
         }
-    }
+    };
+    ($(($alg: ident, $name: expr)),*; serial: $serial: ident) => {
+        $crate::define_algs!(@algs $(($alg, $name)),*);
+
+        /// the baseline of `--sweep-threads`.
+        pub const SERIAL_ALG: Option<Algs> = Some(Algs::$serial);
+    };
+    ($(($alg: ident, $name: expr)),*) => {
+        $crate::define_algs!(@algs $(($alg, $name)),*);
+
+        /// the baseline of `--sweep-threads`.
+        pub const SERIAL_ALG: Option<Algs> = None;
+    };
 }
 
 #[macro_export]
 macro_rules! define_args {
+    ($default_alg: expr $(, ($name: ident, $type: ty, $default: expr))*; warmup: $warmup: expr) => {
+        $crate::define_args!($default_alg $(, ($name, $type, $default))*);
+
+        impl Args {
+            /// Parses the arguments with the benchmark's own default warm-up.
+            fn parse() -> Self {
+                let warmup: &str = $warmup.to_string().leak();
+                let matches = <Self as clap::CommandFactory>::command()
+                    .mut_arg("warmup", |a| a.default_value(warmup))
+                    .get_matches();
+                <Self as clap::FromArgMatches>::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+            }
+        }
+    };
     ($default_alg: expr $(, ($name: ident, $type: ty, $default: expr))*) => {
         use clap::Parser;
 
@@ -65,9 +90,18 @@ macro_rules! define_args {
             #[clap(value_parser, required=true)]
             ifname: String,
 
-            /// the number of rounds to execute the benchmark
-            #[clap(short, long, value_parser, required=false, default_value_t=1)]
-            rounds: usize,
+            #[clap(flatten)]
+            time: $crate::common::time_loop::TimeOptions,
+
+            #[clap(flatten)]
+            threads: $crate::common::threads::ThreadOptions,
+
+            /// validate the result after timing
+            #[clap(long, value_parser, required=false, default_value_t=false)]
+            check: bool,
+
+            #[clap(flatten)]
+            report: $crate::rpb_bench::ReportArgs,
 
             $(#[clap(long, value_parser, required=false, default_value_t=$default)]
             $name: $type,)*
@@ -76,20 +110,25 @@ macro_rules! define_args {
 }
 
 #[macro_export]
-macro_rules! init {
-    () => {
-        use affinity::set_thread_affinity;
-        use rayon::prelude::*;
-
-        // pin rayon's threads to cores
-        // TODO: find a better way to do this.
-        (0..rayon::current_num_threads())
-            .par_bridge()
-            .for_each(|_| {
-                set_thread_affinity([rayon::current_thread_index().unwrap()]).unwrap();
-                std::thread::sleep(std::time::Duration::from_millis(100))
-            })
-    };
+macro_rules! define_check_args {
+    ($(($name: ident, $type: ty, $default: expr)),*) => {
+        use clap::Parser;
+
+        #[derive(Parser, Debug)]
+        #[clap(version, about, long_about = None)]
+        struct Args {
+            /// the benchmark's result filename
+            #[clap(value_parser, required=true)]
+            rfname: String,
+
+            /// the benchmark's input filename
+            #[clap(value_parser, required=true)]
+            ifname: String,
+
+            $(#[clap(long, value_parser, required=false, default_value_t=$default)]
+            $name: $type,)*
+        }
+    }
 }
 
 #[macro_export]
@@ -105,6 +144,74 @@ macro_rules! finalize {
             }
         }
 
-        println!("mean:  {:?}", $d);
+        println!("mean:  {:?}", $d.mean());
+    };
+}
+
+/// Checks the result if `--check` is given and evaluates to the verdict,
+/// `None` if it was not checked.
+#[macro_export]
+macro_rules! check {
+    ($args: ident, $checker: expr, $inp: expr, $r: expr) => {
+        if $args.check {
+            use $crate::common::check::Checker;
+            Some($crate::common::check::print_verdict(
+                $checker.check($inp, $r),
+            ))
+        } else {
+            None
+        }
+    };
+}
+
+/// Writes the report of the run if `--report` is given, then exits with
+/// status 1 if the check failed.
+#[macro_export]
+macro_rules! report {
+    ($args: ident, $d: ident, $verdict: expr) => {
+        let verdict: Option<bool> = $verdict;
+        $args.report.write(&$crate::rpb_bench::BenchReport {
+            algorithm: $args.algorithm.to_string(),
+            features: $crate::features(),
+            threads: $args.threads.num_threads(),
+            input: $args.ifname.clone(),
+            check: verdict,
+            ..$crate::rpb_bench::BenchReport::new(env!("CARGO_BIN_NAME"), &$d)
+        });
+        if verdict == Some(false) {
+            std::process::exit(1);
+        }
+    };
+}
+
+/// Runs the benchmark on each thread count of `--sweep-threads`, if given,
+/// with `$run` timing an algorithm (`Fn(Algs) -> Samples`). Evaluates to
+/// whether it did.
+#[macro_export]
+macro_rules! sweep {
+    ($args: ident, $run: expr) => {
+        !$args.threads.sweep_threads.is_empty() && {
+            let points = $crate::common::threads::sweep_threads(
+                &$args.threads.sweep_threads,
+                &$args.threads.pin,
+                $args.algorithm,
+                SERIAL_ALG,
+                $run,
+            );
+            let reports: Vec<_> = points
+                .iter()
+                .map(|p| $crate::rpb_bench::BenchReport {
+                    algorithm: $args.algorithm.to_string(),
+                    features: $crate::features(),
+                    threads: p.threads,
+                    input: $args.ifname.clone(),
+                    self_speedup: Some(p.self_speedup),
+                    serial_speedup: p.serial_speedup,
+                    ..$crate::rpb_bench::BenchReport::new(env!("CARGO_BIN_NAME"), &p.samples)
+                })
+                .collect();
+            $args.report.write_all(&reports);
+            true
+        }
     };
 }
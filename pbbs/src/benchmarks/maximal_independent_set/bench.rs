@@ -0,0 +1,83 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_graph::NeighborAccess;
+
+use crate::benchmarks::maximal_independent_set::check::MisCheck;
+use crate::benchmarks::maximal_independent_set::{rusty_incremental_mis, serial_mis};
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::graph::CompressedGraph;
+use crate::common::graph_io::read_graph_from_file;
+use crate::common::shared::{AtomicArray, RawArray};
+
+define_algs!(
+    (SERIAL, "serial"),
+    (RUSTINC, "rustinc"),
+    (RustincAtomic, "rustinc-atomic"),
+    (RustincRaw, "rustinc-raw");
+    serial: SERIAL
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::RUSTINC;
+
+pub fn run<G: NeighborAccess>(alg: Algs, opts: &TimeOptions, g: &G) -> (Vec<u8>, Samples) {
+    let mis = match alg {
+        Algs::SERIAL => serial_mis::maximal_independent_set::<G>,
+        Algs::RUSTINC => rusty_incremental_mis::maximal_independent_set::<G>,
+        Algs::RustincAtomic => {
+            rusty_incremental_mis::maximal_independent_set_with::<G, AtomicArray<u8>>
+        }
+        Algs::RustincRaw => rusty_incremental_mis::maximal_independent_set_with::<G, RawArray<u8>>,
+    };
+
+    let mut r = vec![];
+
+    let samples = time_loop(
+        "mis",
+        opts,
+        || {},
+        || {
+            r = mis(g);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let compressed = spec.param("compressed", false)?;
+    let g = read_graph_from_file(&spec.input).map_err(RunError::input)?;
+    let (r, d) = match compressed {
+        true => run(alg, &spec.time, &CompressedGraph::from(&g)),
+        false => run(alg, &spec.time, &g),
+    };
+    Ok(outcome(spec, alg, d, || MisCheck.check(&g, &r)))
+}
@@ -0,0 +1,79 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::graph::Graph;
+use crate::common::graph_io::read_graph_from_file;
+use crate::common::io::{read_file_to_vec_seq, ReadOptions};
+
+/// Checks that the selected vertices are independent and that every other
+/// vertex has a selected neighbor.
+pub struct MisCheck;
+
+impl Checker for MisCheck {
+    type Input = Graph;
+    type Output = Vec<u8>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_graph_from_file(fname)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec_seq(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, g: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        if out.len() != g.n {
+            return invalid(format!("{} flags for {} vertices", out.len(), g.n));
+        }
+        let selected: Vec<bool> = out.par_iter().map(|&q| q == 1).collect();
+        let mut self_or_ngh_selected = selected.clone();
+        let mut violation_no = 0usize;
+        for a in 0..g.n {
+            for &b in g.index(a).neighbors {
+                let b = b as usize;
+                if a < b && (selected[a] || selected[b]) {
+                    self_or_ngh_selected[a] = true;
+                    self_or_ngh_selected[b] = true;
+                    if selected[a] && selected[b] {
+                        violation_no += 1;
+                    }
+                }
+            }
+        }
+        let missed = self_or_ngh_selected.iter().filter(|q| !**q).count();
+        if violation_no != 0 || missed != 0 {
+            return invalid(format!(
+                "violations_no:{} missed_nodes:{} selected:{}",
+                violation_no,
+                missed,
+                selected.iter().filter(|q| **q).count()
+            ));
+        }
+        Ok(())
+    }
+}
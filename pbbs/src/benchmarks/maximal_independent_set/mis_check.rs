@@ -1,4 +1,4 @@
-use clap::Parser;
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,62 +25,13 @@ use clap::Parser;
 // SOFTWARE.
 // ============================================================================
 
-use rayon::prelude::*;
+use pbbs::benchmarks::maximal_independent_set::check::MisCheck;
+use pbbs::define_check_args;
 
-use pbbs::common::graph::Graph;
-use pbbs::common::io::read_file_to_vec_seq;
-use pbbs::common::graph_io::read_graph_from_file;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// mis results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-}
-
-pub fn check(g: Graph, selected: &[bool]) -> bool {
-    let mut violation_no = 0usize;
-    let mut self_or_ngh_selected: Vec<bool> = selected
-        .par_iter()
-        .map(|q| *q)
-        .collect();
-    for a in 0..g.n {
-        for &b in g.index(a).neighbors {
-            let b = b as usize;
-            if a < b {
-                if selected[a] || selected[b] {
-                    self_or_ngh_selected[a] = true;
-                    self_or_ngh_selected[b] = true;
-                    if selected[a] && selected[b] {
-                        violation_no += 1;
-                    }
-                }
-
-            }
-        }
-    }
-    if violation_no != 0 || self_or_ngh_selected.contains(&false) {
-        println!("violations_no:{} missed_nodes:{} selected:{}",
-            violation_no,
-            self_or_ngh_selected.iter().filter(|q| **q == false).count(),
-            selected.iter().filter(|q| **q == true).count()
-        );
-        false
-    } else { true }
-}
+define_check_args!();
 
 fn main() {
     let args = Args::parse();
-    let g = read_graph_from_file(&args.ifname);
-    let r: Vec<bool> = read_file_to_vec_seq(&args.rfname)
-        .iter()
-        .map(|q: &u8| *q == 1)
-        .collect();
-    if check(g, &r) { println!("OK"); }
-    else { println!("ERR"); std::process::exit(1); }
+    let checker = MisCheck;
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,58 +24,33 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
+use pbbs::benchmarks::maximal_independent_set::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::maximal_independent_set::check::MisCheck;
+use pbbs::common::graph::CompressedGraph;
+use pbbs::common::graph_io::read_graph_from_file;
+use pbbs::common::io::write_slice_to_file_seq;
+use pbbs::{check, define_args, finalize, report, sweep};
 
-#[path = "../../common/graph.rs"]
-mod graph;
-#[path = "../../common/graph_io.rs"]
-mod graph_io;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "mod.rs"]
-mod mis;
-#[path = "../../misc.rs"]
-mod misc;
-
-use graph::Graph;
-use graph_io::read_graph_from_file;
-use io::write_slice_to_file_seq;
-use mis::{rusty_incremental_mis, serial_mis};
-use misc::*;
-
-define_args!(Algs::RUSTINC);
-
-define_algs!((SERIAL, "serial"), (RUSTINC, "rustinc"));
-
-pub fn run(alg: Algs, rounds: usize, g: Graph) -> (Vec<u8>, Duration) {
-    let mis = match alg {
-        Algs::SERIAL => serial_mis::maximal_independent_set,
-        Algs::RUSTINC => rusty_incremental_mis::maximal_independent_set,
-    };
-
-    let mut r = vec![];
-
-    let mean = time_loop(
-        "mis",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            r = mis(&g);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG, (compressed, bool, false));
 
 fn main() {
-    init!();
-
     let args = Args::parse();
-    let g = read_graph_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    let pool = args.threads.build_pool();
+    let g = read_graph_from_file(&args.ifname).expect("cannot read input file");
+    if args.compressed {
+        let cg = CompressedGraph::from(&g);
+        if sweep!(args, |alg| run(alg, &args.time, &cg).1) {
+            return;
+        }
+    } else if sweep!(args, |alg| run(alg, &args.time, &g).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| match args.compressed {
+        true => run(args.algorithm, &args.time, &CompressedGraph::from(&g)),
+        false => run(args.algorithm, &args.time, &g),
+    });
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, MisCheck, &g, &r);
+    report!(args, d, verdict);
 }
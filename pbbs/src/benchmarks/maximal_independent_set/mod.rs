@@ -1,4 +1,4 @@
-pub(super) mod serial_mis;
+pub mod serial_mis;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,4 +25,6 @@ pub(super) mod serial_mis;
 // SOFTWARE.
 // ============================================================================
 
-pub(super) mod rusty_incremental_mis;
+pub mod bench;
+pub mod check;
+pub mod rusty_incremental_mis;
@@ -1,5 +1,4 @@
-use rayon::prelude::*;
-#[cfg(feature = "AW_safe")]
+use rpb_graph::NeighborAccess;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,11 +24,9 @@ use rayon::prelude::*;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 // ============================================================================
-use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
 
-use crate::graph::Graph;
-#[path = "../../common/spec_for.rs"]
-mod spec_for;
+use crate::common::shared::{DefaultArray, SharedArray};
+use crate::common::spec_for;
 use spec_for::StatefulSpecFor;
 
 #[derive(Clone)]
@@ -37,25 +34,20 @@ struct MISState {
     flag: u8,
 }
 
-pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
-    let n = g.n;
-    #[cfg(not(feature = "AW_safe"))]
-    let flags: Vec<u8> = (0..n).into_par_iter().map(|_| 0).collect();
-    #[cfg(feature = "AW_safe")]
-    let flags: Vec<AtomicU8> = (0..n).into_par_iter().map(|_| AtomicU8::new(0)).collect();
-    #[cfg(not(feature = "AW_safe"))]
-    let flags_ptr = flags.as_ptr() as usize;
+pub fn maximal_independent_set<G: NeighborAccess>(g: &G) -> Vec<u8> {
+    maximal_independent_set_with::<G, DefaultArray<u8>>(g)
+}
+
+/// Runs with the flags in `F`: `AtomicArray` (safe) or `RawArray` (unsafe).
+pub fn maximal_independent_set_with<G: NeighborAccess, F: SharedArray<u8>>(g: &G) -> Vec<u8> {
+    let n = g.num_vertices();
+    let flags = F::new(n, 0);
 
     let reserve = |i: usize, s: &mut MISState| -> bool {
         s.flag = 1;
-        let v = g.index(i);
-        for j in 0..v.degree {
-            let ngh = v.neighbors[j] as usize;
+        for ngh in g.neighbors(i) {
             if ngh < i {
-                #[cfg(not(feature = "AW_safe"))]
-                let f = flags[ngh];
-                #[cfg(feature = "AW_safe")]
-                let f = flags[ngh].load(Relaxed);
+                let f = flags.get(ngh);
 
                 if f == 1 {
                     s.flag = 2;
@@ -69,14 +61,7 @@ pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
     };
 
     let commit = |i: usize, s: &mut MISState| -> bool {
-        #[cfg(not(feature = "AW_safe"))]
-        unsafe {
-            (flags_ptr as *mut u8).add(i).write(s.flag);
-        }
-        #[cfg(feature = "AW_safe")]
-        {
-            flags[i].store(s.flag, Relaxed);
-        }
+        flags.set(i, s.flag);
         s.flag > 0
     };
 
@@ -91,8 +76,40 @@ pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
         )
         .expect("failed speculative for");
 
-    #[cfg(not(feature = "AW_safe"))]
-    return flags;
-    #[cfg(feature = "AW_safe")]
-    return flags.into_par_iter().map(|f| f.load(Relaxed)).collect();
+    flags.into_vec()
+}
+
+#[cfg(test)]
+mod mis_tests {
+    use super::*;
+    use crate::benchmarks::maximal_independent_set::serial_mis;
+    use crate::common::graph::Graph;
+    use crate::common::shared::{AtomicArray, RawArray};
+
+    #[test]
+    fn atomic_and_raw_agree() {
+        let n = 2000;
+        let mut x = 1u64;
+        let mut edges = vec![];
+        for _ in 0..8 * n {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let (u, v) = ((x >> 33) as u32 % n, (x >> 13) as u32 % n);
+            if u != v {
+                edges.extend([(u, v), (v, u)]);
+            }
+        }
+        let g = Graph::from_edges(n as usize, edges);
+
+        let expected = serial_mis::maximal_independent_set(&g);
+        assert_eq!(
+            maximal_independent_set_with::<_, AtomicArray<u8>>(&g),
+            expected
+        );
+        assert_eq!(
+            maximal_independent_set_with::<_, RawArray<u8>>(&g),
+            expected
+        );
+    }
 }
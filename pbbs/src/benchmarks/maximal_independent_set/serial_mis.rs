@@ -1,4 +1,4 @@
-use crate::graph::Graph;
+use rpb_graph::NeighborAccess;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -29,15 +29,12 @@ use crate::graph::Graph;
 //   Flags = 0 indicates undecided
 //   Flags = 1 indicates chosen
 //   Flags = 2 indicates a neighbor is chosen
-pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
-    let n = g.n;
+pub fn maximal_independent_set<G: NeighborAccess>(g: &G) -> Vec<u8> {
+    let n = g.num_vertices();
     let mut flags = vec![0u8; n];
     for i in 0..n {
         flags[i] = 1;
-        let v = g.index(i);
-        let nghs = v.neighbors;
-        for j in 0..v.degree {
-            let ngh = nghs[j] as usize;
+        for ngh in g.neighbors(i) {
             if flags[ngh] == 1 {
                 flags[i] = 2;
                 break;
@@ -0,0 +1,76 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::maximal_matching::check::MmCheck;
+use crate::benchmarks::maximal_matching::{rusty_incremental_mm, serial_mm};
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::graph::EdgeArray;
+use crate::common::graph_io::read_edge_array_from_file;
+use crate::common::shared::{AtomicArray, RawArray};
+use crate::misc::*;
+
+define_algs!(
+    (SERIAL, "serial"),
+    (RUSTINC, "rustinc"),
+    (RustincAtomic, "rustinc-atomic"),
+    (RustincRaw, "rustinc-raw");
+    serial: SERIAL
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::RUSTINC;
+
+pub fn run(alg: Algs, opts: &TimeOptions, ea: &EdgeArray) -> (Vec<DefInt>, Samples) {
+    let mm = match alg {
+        Algs::SERIAL => serial_mm::maximal_matching,
+        Algs::RUSTINC => rusty_incremental_mm::maximal_matching,
+        Algs::RustincAtomic => rusty_incremental_mm::maximal_matching_with::<AtomicArray<bool>>,
+        Algs::RustincRaw => rusty_incremental_mm::maximal_matching_with::<RawArray<bool>>,
+    };
+
+    let mut r = vec![];
+
+    let samples = time_loop(
+        "mm",
+        opts,
+        || {},
+        || {
+            r = mm(ea);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let g = read_edge_array_from_file(&spec.input).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &g);
+    Ok(outcome(spec, alg, d, || MmCheck.check(&g, &r)))
+}
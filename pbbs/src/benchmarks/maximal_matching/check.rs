@@ -0,0 +1,81 @@
+use std::cmp::max;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::graph::EdgeArray;
+use crate::common::graph_io::read_edge_array_from_file;
+use crate::common::io::{read_file_to_vec_seq, ReadOptions};
+use crate::DefInt;
+
+/// Checks that no two matched edges share a vertex and that every other
+/// edge has a matched endpoint.
+pub struct MmCheck;
+
+impl Checker for MmCheck {
+    type Input = EdgeArray;
+    type Output = Vec<DefInt>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_edge_array_from_file(fname)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec_seq(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, ea: &Self::Input, matching: &Self::Output) -> Result<(), CheckError> {
+        let m = ea.non_zeros;
+        let n = max(ea.num_rows, ea.num_cols);
+        let mut vs = vec![usize::MAX; n];
+        let mut flags = vec![false; m];
+
+        for &i in matching {
+            let i = i as usize;
+            if i >= m {
+                return invalid(format!("edge {i} does not exist (m={m})"));
+            }
+            (vs[ea[i].u as usize], vs[ea[i].v as usize]) = (i, i);
+            flags[i] = true;
+        }
+
+        for i in 0..m {
+            let u = ea[i].u as usize;
+            let v = ea[i].v as usize;
+            if flags[i] {
+                if vs[u] != i {
+                    return invalid(format!("matched edges share vertex {u}"));
+                }
+                if vs[v] != i {
+                    return invalid(format!("matched edges share vertex {v}"));
+                }
+            } else if u != v && vs[u] == usize::MAX && vs[v] == usize::MAX {
+                return invalid(format!("neither endpoint matched for edge {i}"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use clap::Parser;
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,59 +25,13 @@ use clap::Parser;
 // SOFTWARE.
 // ============================================================================
 
+use pbbs::benchmarks::maximal_matching::check::MmCheck;
+use pbbs::define_check_args;
 
-use pbbs::common::graph::EdgeArray;
-use pbbs::common::io::read_file_to_vec_seq;
-use pbbs::common::graph_io::read_edge_array_from_file;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// mm results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-}
-
-pub fn check(ea: EdgeArray, matching: &[usize]) -> bool {
-    let m = ea.non_zeros;
-    let n = std::cmp::max(ea.num_rows, ea.num_cols);
-    let mut vs = vec![usize::MAX; n];
-    let mut flags = vec![false; m];
-
-    matching.iter().for_each(|&i| {
-        (vs[ea[i].u as usize], vs[ea[i].v as usize]) = (i, i);
-        flags[i] = true;
-    });
-
-    for i in 0..m {
-        let u = ea[i].u as usize;
-        let v = ea[i].v as usize;
-        if flags[i] {
-            if vs[u] != i {
-                println!("mm_check: edges share vertex {u}");
-                return false;
-            }
-            if vs[v] != i {
-                println!("mm_check: edges share vertex {v}");
-                return false;
-            }
-        } else {
-            if u != v && vs[u] == usize::MAX && vs[v] == usize::MAX {
-                println!("mm_check: neither endpoint matched for edge {i}"); return false;
-            }
-        }
-    }
-    true
-}
+define_check_args!();
 
 fn main() {
     let args = Args::parse();
-    let ea = read_edge_array_from_file(&args.ifname);
-    let r: Vec<usize> = read_file_to_vec_seq(&args.rfname);
-    if check(ea, &r) { println!("OK"); }
-    else { println!("ERR"); std::process::exit(1); }
+    let checker = MmCheck;
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
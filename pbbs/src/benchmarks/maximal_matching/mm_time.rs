@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,58 +24,24 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
+use pbbs::benchmarks::maximal_matching::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::maximal_matching::check::MmCheck;
+use pbbs::common::graph_io::read_edge_array_from_file;
+use pbbs::common::io::write_slice_to_file_seq;
+use pbbs::{check, define_args, finalize, report, sweep};
 
-#[path = "../../common/graph.rs"]
-mod graph;
-#[path = "../../common/graph_io.rs"]
-mod graph_io;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod mm;
-
-use graph::EdgeArray;
-use graph_io::read_edge_array_from_file;
-use io::write_slice_to_file_seq;
-use misc::*;
-use mm::{rusty_incremental_mm, serial_mm};
-
-define_args!(Algs::RUSTINC);
-
-define_algs!((SERIAL, "serial"), (RUSTINC, "rustinc"));
-
-pub fn run(alg: Algs, rounds: usize, ea: EdgeArray) -> (Vec<DefInt>, Duration) {
-    let mm = match alg {
-        Algs::SERIAL => serial_mm::maximal_matching,
-        Algs::RUSTINC => rusty_incremental_mm::maximal_matching,
-    };
-
-    let mut r = vec![];
-
-    let mean = time_loop(
-        "mm",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            r = mm(&ea);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG);
 
 fn main() {
-    init!();
-
     let args = Args::parse();
-    let g = read_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    let pool = args.threads.build_pool();
+    let g = read_edge_array_from_file(&args.ifname).expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &g).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &g));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, MmCheck, &g, &r);
+    report!(args, d, verdict);
 }
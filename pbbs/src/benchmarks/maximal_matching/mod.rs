@@ -1,4 +1,4 @@
-pub(crate) mod serial_mm;
+pub mod serial_mm;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,4 +25,6 @@ pub(crate) mod serial_mm;
 // SOFTWARE.
 // ============================================================================
 
-pub(crate) mod rusty_incremental_mm;
+pub mod bench;
+pub mod check;
+pub mod rusty_incremental_mm;
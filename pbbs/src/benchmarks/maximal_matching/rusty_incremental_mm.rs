@@ -25,48 +25,30 @@ use rayon::prelude::*;
 // SOFTWARE.
 // ============================================================================
 
-#[cfg(feature = "AW_safe")]
-use std::sync::atomic::AtomicBool;
-
-use crate::graph::EdgeArray;
+use crate::common::graph::EdgeArray;
+use crate::common::shared::{DefaultArray, SharedArray};
 use crate::DefInt;
-#[cfg(feature = "AW_safe")]
-use crate::ORDER;
 
-#[path = "../../common/spec_for.rs"]
-mod spec_for;
+use crate::common::spec_for;
 
 use spec_for::{Reservation, SpecFor};
 
 pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
+    maximal_matching_with::<DefaultArray<bool>>(ea)
+}
+
+/// Runs with the matched flags in `F`: `AtomicArray` (safe) or `RawArray`
+/// (unsafe).
+pub fn maximal_matching_with<F: SharedArray<bool>>(ea: &EdgeArray) -> Vec<DefInt> {
     let n = std::cmp::max(ea.num_rows, ea.num_cols);
     let m = ea.non_zeros;
     let rs: Vec<Reservation> = (0..n).into_par_iter().map(|_| Reservation::new()).collect();
-
-    #[cfg(not(feature = "AW_safe"))]
-    let matched: Vec<bool> = (0..n).into_par_iter().map(|_| false).collect();
-    #[cfg(feature = "AW_safe")]
-    let matched: Vec<AtomicBool> = (0..n)
-        .into_par_iter()
-        .map(|_| AtomicBool::new(false))
-        .collect();
-
-    #[cfg(not(feature = "AW_safe"))]
-    let matched_ptr = matched.as_ptr() as usize;
+    let matched = F::new(n, false);
 
     let reserve = |i: usize| -> bool {
         let (u, v) = (ea[i].u as usize, ea[i].v as usize);
         let i = i as u32;
-        #[cfg(not(feature = "AW_safe"))]
-        if matched[u] || matched[v] || u == v {
-            false
-        } else {
-            rs[u].reserve(i);
-            rs[v].reserve(i);
-            true
-        }
-        #[cfg(feature = "AW_safe")]
-        if matched[u].load(ORDER) || matched[v].load(ORDER) || u == v {
+        if matched.get(u) || matched.get(v) || u == v {
             false
         } else {
             rs[u].reserve(i);
@@ -81,16 +63,8 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
         if rs[v].check(i) {
             rs[v].reset();
             if rs[u].check(i) {
-                #[cfg(not(feature = "AW_safe"))]
-                unsafe {
-                    (matched_ptr as *mut bool).add(u).write(true);
-                    (matched_ptr as *mut bool).add(v).write(true);
-                }
-                #[cfg(feature = "AW_safe")]
-                {
-                    matched[u].store(true, ORDER);
-                    matched[v].store(true, ORDER);
-                }
+                matched.set(u, true);
+                matched.set(v, true);
                 return true;
             }
         } else if rs[u].check(i) {
@@ -114,3 +88,30 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
 
     matching_idx
 }
+
+#[cfg(test)]
+mod mm_tests {
+    use super::*;
+    use crate::benchmarks::maximal_matching::check::MmCheck;
+    use crate::common::check::Checker;
+    use crate::common::graph::Edge;
+    use crate::common::shared::{AtomicArray, RawArray};
+    use parlay::random::Random;
+
+    #[test]
+    fn atomic_and_raw_agree() {
+        let (n, r) = (2000, Random::new(1));
+        let es = (0..8000)
+            .map(|i| {
+                Edge::new(
+                    (r.ith_rand(2 * i) % n) as _,
+                    (r.ith_rand(2 * i + 1) % n) as _,
+                )
+            })
+            .collect();
+        let ea = EdgeArray::new(es, n as usize, n as usize);
+        let atomic = maximal_matching_with::<AtomicArray<bool>>(&ea);
+        assert!(MmCheck.check(&ea, &atomic).is_ok());
+        assert_eq!(maximal_matching_with::<RawArray<bool>>(&ea), atomic);
+    }
+}
@@ -0,0 +1,111 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_graph::{is_bin_file, GraphIoError, WGH_ADJ_GRAPH_HEADER};
+
+use crate::benchmarks::min_span_forest::check::MsfCheck;
+use crate::benchmarks::min_span_forest::{inc_msf_mod, incremental_msf, serial_msf};
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::graph::WghEdgeArray;
+use crate::common::graph_io::{
+    read_header, read_wgh_edge_array_from_file, read_wgh_graph_from_file,
+};
+use crate::common::shared::{AtomicArray, RawArray};
+use crate::misc::*;
+
+define_algs!(
+    (SERIAL, "serial"),
+    (INCREMENTAL, "incremental"),
+    (INCMOD, "incmod"),
+    (IncrementalAtomic, "incremental-atomic"),
+    (IncrementalRaw, "incremental-raw"),
+    (IncmodAtomic, "incmod-atomic"),
+    (IncmodRaw, "incmod-raw");
+    serial: SERIAL
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::INCREMENTAL;
+
+pub fn run(alg: Algs, opts: &TimeOptions, ea: &WghEdgeArray) -> (Vec<DefInt>, Samples) {
+    let sf = match alg {
+        Algs::SERIAL => serial_msf::minimum_spanning_forest,
+        Algs::INCREMENTAL => incremental_msf::minimum_spanning_forest,
+        Algs::INCMOD => inc_msf_mod::minimum_spanning_forest,
+        Algs::IncrementalAtomic => {
+            incremental_msf::minimum_spanning_forest_with::<AtomicArray<DefIntS>, AtomicArray<bool>>
+        }
+        Algs::IncrementalRaw => {
+            incremental_msf::minimum_spanning_forest_with::<RawArray<DefIntS>, RawArray<bool>>
+        }
+        Algs::IncmodAtomic => {
+            inc_msf_mod::minimum_spanning_forest_with::<AtomicArray<DefIntS>, AtomicArray<bool>>
+        }
+        Algs::IncmodRaw => {
+            inc_msf_mod::minimum_spanning_forest_with::<RawArray<DefIntS>, RawArray<bool>>
+        }
+    };
+    // incmod rewrites the endpoints of the edges.
+    let refresh = matches!(alg, Algs::INCMOD | Algs::IncmodAtomic | Algs::IncmodRaw);
+
+    let mut r = vec![];
+    let mut ea_copy = ea.clone();
+    let ea_copy_shadow = unsafe { (&ea_copy as *const WghEdgeArray).as_ref().unwrap() };
+    let samples = time_loop(
+        "msf",
+        opts,
+        || {
+            if refresh {
+                ea_copy = ea.clone();
+            }
+        },
+        || {
+            sf(ea_copy_shadow, &mut r);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Reads the input as a weighted edge array, from either a weighted edge
+/// array or a weighted adjacency graph file.
+pub fn read_input(fname: &str) -> Result<WghEdgeArray, GraphIoError> {
+    if is_bin_file(fname)? || read_header(fname) == WGH_ADJ_GRAPH_HEADER {
+        read_wgh_graph_from_file(fname).map(|g| WghEdgeArray::from(&g))
+    } else {
+        read_wgh_edge_array_from_file(fname)
+    }
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let ea = read_input(&spec.input).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &ea);
+    Ok(outcome(spec, alg, d, || MsfCheck.check(&ea, &r)))
+}
@@ -0,0 +1,144 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_graph::{is_bin_file, WGH_ADJ_GRAPH_HEADER};
+
+use crate::algorithm::union_find::UnionFind;
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::graph::WghEdgeArray;
+use crate::common::graph_io::{
+    read_header, read_wgh_edge_array_from_file, read_wgh_graph_from_file,
+};
+use crate::common::io::{read_file_to_vec_seq, ReadOptions};
+use crate::{DefInt, DefIntS};
+
+/// Checks that the chosen edges form a spanning forest with the weight of a
+/// minimum one. The reference weight comes from Kruskal's algorithm.
+pub struct MsfCheck;
+
+impl MsfCheck {
+    /// The number of edges in `ids` and their total weight, or the first
+    /// edge that closes a cycle.
+    fn forest(&self, wea: &WghEdgeArray, ids: &[DefInt]) -> Result<(usize, f64), usize> {
+        let mut uf = UnionFind::new(wea.n);
+        let (mut size, mut weight) = (0, 0.0);
+        for &i in ids {
+            let e = wea[i as usize];
+            let (u, v) = (uf.find(e.u as DefIntS), uf.find(e.v as DefIntS));
+            if u == v {
+                return Err(i as usize);
+            }
+            uf.union_roots(u, v);
+            size += 1;
+            weight += e.w as f64;
+        }
+        Ok((size, weight))
+    }
+
+    /// The size and the weight of a minimum spanning forest.
+    fn minimum_forest(&self, wea: &WghEdgeArray) -> (usize, f64) {
+        let mut ids: Vec<DefInt> = (0..wea.m as DefInt).collect();
+        ids.par_sort_by(|&a, &b| wea[a as usize].w.total_cmp(&wea[b as usize].w));
+        let mut uf = UnionFind::new(wea.n);
+        let (mut size, mut weight) = (0, 0.0);
+        for i in ids {
+            let e = wea[i as usize];
+            let (u, v) = (uf.find(e.u as DefIntS), uf.find(e.v as DefIntS));
+            if u != v {
+                uf.union_roots(u, v);
+                size += 1;
+                weight += e.w as f64;
+            }
+        }
+        (size, weight)
+    }
+}
+
+impl Checker for MsfCheck {
+    type Input = WghEdgeArray;
+    type Output = Vec<DefInt>;
+
+    /// Reads a weighted edge array or a weighted adjacency graph.
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        let adjacency = is_bin_file(fname)? || read_header(fname) == WGH_ADJ_GRAPH_HEADER;
+        Ok(if adjacency {
+            read_wgh_graph_from_file(fname).map(|g| WghEdgeArray::from(&g))?
+        } else {
+            read_wgh_edge_array_from_file(fname)?
+        })
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec_seq(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, wea: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        if let Some(i) = out.par_iter().find_any(|&&i| i as usize >= wea.m) {
+            return invalid(format!("edge {i} does not exist (m={})", wea.m));
+        }
+        let (size, weight) = match self.forest(wea, out) {
+            Ok(f) => f,
+            Err(i) => return invalid(format!("edge {i} closes a cycle")),
+        };
+        let (min_size, min_weight) = self.minimum_forest(wea);
+        if size != min_size {
+            return invalid(format!(
+                "the result has {size} edges, a spanning forest has {min_size}"
+            ));
+        }
+        if (weight - min_weight).abs() > 1e-6 * min_weight.abs().max(1.0) {
+            return invalid(format!(
+                "the result weighs {weight}, the minimum is {min_weight}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+    use crate::common::graph::WghEdge;
+
+    #[test]
+    fn minimum_forest() {
+        // a triangle and an isolated vertex
+        let wea = WghEdgeArray::new(
+            vec![
+                WghEdge::new(0, 1, 1.0),
+                WghEdge::new(1, 2, 2.0),
+                WghEdge::new(0, 2, 3.0),
+            ],
+            4,
+        );
+        assert!(MsfCheck.check(&wea, &vec![1, 0]).is_ok());
+        assert!(MsfCheck.check(&wea, &vec![0, 2]).is_err());
+        assert!(MsfCheck.check(&wea, &vec![0, 1, 2]).is_err());
+        assert!(MsfCheck.check(&wea, &vec![0]).is_err());
+        assert!(MsfCheck.check(&wea, &vec![0, 3]).is_err());
+    }
+}
@@ -25,30 +25,33 @@ use rayon::prelude::*;
 // SOFTWARE.
 // ============================================================================
 
-#[cfg(feature = "AW_safe")]
-use std::sync::atomic::AtomicBool;
-
-use crate::graph::WghEdgeArray;
-#[cfg(feature = "AW_safe")]
-use crate::union_find::AtomicUnionFind;
-#[cfg(not(feature = "AW_safe"))]
-use crate::union_find::UnionFind;
-#[cfg(feature = "AW_safe")]
-use crate::ORDER;
+use crate::algorithm::union_find::SharedUnionFind;
+use crate::common::graph::WghEdgeArray;
+use crate::common::shared::{DefaultArray, SharedArray};
 use crate::{DefInt, DefIntS};
 use parlay::internal::sample_sort_inplace;
 use parlay::primitives::pack_index;
 
-#[path = "../../common/spec_for.rs"]
-mod spec_for;
+use crate::common::spec_for;
 use spec_for::{Reservation, SpecFor};
 
 pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
-    #[cfg(feature = "AW_safe")]
-    eprintln!(
-        "WARNING: AW_safe is enabled, \
-        but this algorithm has an array that requires synchronization."
-    );
+    minimum_spanning_forest_with::<DefaultArray<DefIntS>, DefaultArray<bool>>(wea, dest);
+}
+
+/// Runs with the parents of the union-find in `P` and the forest flags in
+/// `F`: `AtomicArray`s (safe) or `RawArray`s (unsafe).
+pub fn minimum_spanning_forest_with<P, F>(wea: &WghEdgeArray, dest: &mut Vec<DefInt>)
+where
+    P: SharedArray<DefIntS>,
+    F: SharedArray<bool>,
+{
+    if P::SYNCHRONIZED && F::SYNCHRONIZED {
+        eprintln!(
+            "WARNING: the shared arrays are atomic, \
+            but this algorithm has an array that requires synchronization."
+        );
+    }
 
     let mut t = parlay::Timer::new("msf");
     t.start();
@@ -69,17 +72,8 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
     sample_sort_inplace(&mut idxs, cmp, false);
     t.next("Sorting");
 
-    #[cfg(not(feature = "AW_safe"))]
-    let uf = UnionFind::new(n);
-    #[cfg(feature = "AW_safe")]
-    let uf = AtomicUnionFind::new(n);
-    let _uf_ptr = &uf as *const _ as usize;
-
-    #[cfg(not(feature = "AW_safe"))]
-    let msf_flags: Vec<_> = (0..m).into_par_iter().map(|_| false).collect();
-    #[cfg(feature = "AW_safe")]
-    let msf_flags: Vec<_> = (0..m).map(|_| AtomicBool::new(false)).collect();
-    let _msf_flags_ptr = msf_flags.as_ptr() as usize;
+    let uf = SharedUnionFind::<P>::new(n);
+    let msf_flags = F::new(m, false);
 
     let rs: Vec<Reservation> = (0..n).map(|_| Reservation::new()).collect();
 
@@ -88,13 +82,8 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
     let reserve = |i: usize| {
         let wea = unsafe { (wea_ptr as *mut WghEdgeArray).as_mut().unwrap() };
         let e = wea.get_mut(idxs[i] as usize);
-        #[cfg(not(feature = "AW_safe"))]
-        let luf = unsafe { (_uf_ptr as *mut UnionFind).as_mut().unwrap() };
-        #[cfg(feature = "AW_safe")]
-        let luf = &uf;
-
-        e.u = luf.find(e.u as DefIntS) as DefInt;
-        e.v = luf.find(e.v as DefIntS) as DefInt;
+        e.u = uf.find(e.u as DefIntS) as DefInt;
+        e.v = uf.find(e.v as DefIntS) as DefInt;
         if e.u != e.v {
             rs[e.v as usize].reserve(idxs[i]);
             rs[e.u as usize].reserve(idxs[i]);
@@ -104,46 +93,17 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         }
     };
 
-    #[cfg(not(feature = "AW_safe"))]
-    let commit = |i: usize| {
-        let luf = unsafe { (_uf_ptr as *mut UnionFind).as_mut().unwrap() };
-        let e = wea[idxs[i] as usize];
-        let (u, v) = (e.u, e.v);
-        if rs[v as usize].check(idxs[i]) {
-            rs[u as usize].check_reset(idxs[i]);
-            luf.link(v as DefIntS, u as DefIntS);
-            unsafe {
-                (_msf_flags_ptr as *mut bool)
-                    .add(idxs[i] as usize)
-                    .write(true);
-            }
-            true
-        } else if rs[u as usize].check(idxs[i]) {
-            luf.link(u as DefIntS, v as DefIntS);
-            unsafe {
-                (_msf_flags_ptr as *mut bool)
-                    .add(idxs[i] as usize)
-                    .write(true);
-            }
-            true
-        } else {
-            false
-        }
-    };
-
-    #[cfg(feature = "AW_safe")]
     let commit = |i: usize| {
-        let luf = &uf;
         let e = wea[idxs[i] as usize];
         let (u, v) = (e.u, e.v);
         if rs[v as usize].check(idxs[i]) {
             rs[u as usize].check_reset(idxs[i]);
-            luf.link(v as DefIntS, u as DefIntS);
-            msf_flags[idxs[i] as usize].store(true, ORDER);
+            uf.link(v as DefIntS, u as DefIntS);
+            msf_flags.set(idxs[i] as usize, true);
             true
         } else if rs[u as usize].check(idxs[i]) {
-            luf.link(u as DefIntS, v as DefIntS);
-            msf_flags[idxs[i] as usize].store(true, ORDER);
+            uf.link(u as DefIntS, v as DefIntS);
+            msf_flags.set(idxs[i] as usize, true);
             true
         } else {
             false
@@ -157,8 +117,6 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         .expect("failed speculative for");
     t.next("Specualtive For");
 
-    #[cfg(feature = "AW_safe")]
-    let msf_flags: Vec<_> = msf_flags.into_par_iter().map(|f| f.into_inner()).collect();
-    pack_index(&msf_flags, dest);
+    pack_index(&msf_flags.into_vec(), dest);
     t.next("Packing");
 }
@@ -25,23 +25,15 @@ use rayon::prelude::*;
 // SOFTWARE.
 // ============================================================================
 
-#[cfg(feature = "AW_safe")]
-use std::sync::atomic::AtomicBool;
-
-use crate::graph::WghEdgeArray;
-use crate::msf::serial_msf::IndexedEdge;
-#[cfg(feature = "AW_safe")]
-use crate::union_find::AtomicUnionFind;
-#[cfg(not(feature = "AW_safe"))]
-use crate::union_find::UnionFind;
-#[cfg(feature = "AW_safe")]
-use crate::ORDER;
+use super::serial_msf::IndexedEdge;
+use crate::algorithm::union_find::SharedUnionFind;
+use crate::common::graph::WghEdgeArray;
+use crate::common::shared::{DefaultArray, SharedArray};
 use crate::{DefInt, DefIntS};
 use parlay::internal::sample_sort_inplace;
 use parlay::primitives::pack_index;
 
-#[path = "../../common/spec_for.rs"]
-mod spec_for;
+use crate::common::spec_for;
 use spec_for::{Reservation, SpecFor};
 
 #[inline(always)]
@@ -54,11 +46,22 @@ fn cmp_idx_edge(a: IndexedEdge, b: IndexedEdge) -> bool {
 }
 
 pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
-    #[cfg(feature = "AW_safe")]
-    eprintln!(
-        "WARNING: AW_safe is enabled, \
-        but this algorithm has an array that requires synchronization."
-    );
+    minimum_spanning_forest_with::<DefaultArray<DefIntS>, DefaultArray<bool>>(wea, dest);
+}
+
+/// Runs with the parents of the union-find in `P` and the forest flags in
+/// `F`: `AtomicArray`s (safe) or `RawArray`s (unsafe).
+pub fn minimum_spanning_forest_with<P, F>(wea: &WghEdgeArray, dest: &mut Vec<DefInt>)
+where
+    P: SharedArray<DefIntS>,
+    F: SharedArray<bool>,
+{
+    if P::SYNCHRONIZED && F::SYNCHRONIZED {
+        eprintln!(
+            "WARNING: the shared arrays are atomic, \
+            but this algorithm has an array that requires synchronization."
+        );
+    }
 
     let mut t = parlay::Timer::new("msf");
     t.start();
@@ -73,45 +76,19 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
     sample_sort_inplace(&mut iwea, cmp_idx_edge, false);
     t.next("Sorting");
 
-    #[cfg(not(feature = "AW_safe"))]
-    let uf = UnionFind::new(n);
-    #[cfg(feature = "AW_safe")]
-    let uf = AtomicUnionFind::new(n);
-    let _uf_ptr = &uf as *const _ as usize;
-
-    #[cfg(not(feature = "AW_safe"))]
-    let msf_flags = vec![false; m];
-    #[cfg(feature = "AW_safe")]
-    let msf_flags: Vec<_> = (0..m).map(|_| AtomicBool::new(false)).collect();
-    let _msf_flags_ptr = msf_flags.as_ptr() as usize;
+    let uf = SharedUnionFind::<P>::new(n);
+    let msf_flags = F::new(m, false);
 
     let rs: Vec<Reservation> = (0..n).map(|_| Reservation::new()).collect();
     let _iwea_ptr = iwea.as_ptr() as usize;
 
-    #[cfg(not(feature = "AW_safe"))]
-    let reserve = |i: usize| {
-        let e = unsafe { (_iwea_ptr as *mut IndexedEdge).add(i).as_mut().unwrap() };
-        let luf = unsafe { (_uf_ptr as *mut UnionFind).as_mut().unwrap() };
-        e.u = luf.find(e.u as DefIntS) as DefInt;
-        e.v = luf.find(e.v as DefIntS) as DefInt;
-        if e.u != e.v {
-            rs[e.v as usize].reserve(i as DefInt);
-            rs[e.u as usize].reserve(i as DefInt);
-            true
-        } else {
-            false
-        }
-    };
-
-    #[cfg(feature = "AW_safe")]
     let reserve = |i: usize| {
         let e = unsafe {
             // FIXME: this requires synchronization
             (_iwea_ptr as *mut IndexedEdge).add(i).as_mut().unwrap()
         };
-        let luf = &uf;
-        e.u = luf.find(e.u as DefIntS) as DefInt;
-        e.v = luf.find(e.v as DefIntS) as DefInt;
+        e.u = uf.find(e.u as DefIntS) as DefInt;
+        e.v = uf.find(e.v as DefIntS) as DefInt;
         if e.u != e.v {
             rs[e.v as usize].reserve(i as DefInt);
             rs[e.u as usize].reserve(i as DefInt);
@@ -121,47 +98,16 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         }
     };
 
-    #[cfg(not(feature = "AW_safe"))]
     let commit = |i: usize| {
-        let luf = unsafe { (_uf_ptr as *mut UnionFind).as_mut().unwrap() };
         let (u, v) = (iwea[i].u, iwea[i].v);
         if rs[v as usize].check(i as DefInt) {
             rs[u as usize].check_reset(i as DefInt);
-            luf.link(v as DefIntS, u as DefIntS);
-            unsafe {
-                (_msf_flags_ptr as *mut bool)
-                    .add(iwea[i].id as usize)
-                    .write(true);
-            }
+            uf.link(v as DefIntS, u as DefIntS);
+            msf_flags.set(iwea[i].id as usize, true);
             return true;
         } else if rs[u as usize].check(i as DefInt) {
-            luf.link(u as DefIntS, v as DefIntS);
-            unsafe {
-                (_msf_flags_ptr as *mut bool)
-                    .add(iwea[i].id as usize)
-                    .write(true);
-            }
-            return true;
-        } else {
-            return false;
-        }
-    };
-
-    #[cfg(feature = "AW_safe")]
-    let commit = |i: usize| {
-        let luf;
-        {
-            luf = &uf;
-        }
-        let (u, v) = (iwea[i].u, iwea[i].v);
-        if rs[v as usize].check(i as DefInt) {
-            rs[u as usize].check_reset(i as DefInt);
-            luf.link(v as DefIntS, u as DefIntS);
-            msf_flags[iwea[i].id as usize].store(true, ORDER);
-            return true;
-        } else if rs[u as usize].check(i as DefInt) {
-            luf.link(u as DefIntS, v as DefIntS);
-            msf_flags[iwea[i].id as usize].store(true, ORDER);
+            uf.link(u as DefIntS, v as DefIntS);
+            msf_flags.set(iwea[i].id as usize, true);
             return true;
         } else {
             return false;
@@ -176,9 +122,6 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         .expect("failed speculative for");
     t.next("Specualtive For");
 
-    #[cfg(feature = "AW_safe")]
-    let msf_flags: Vec<_> = msf_flags.into_par_iter().map(|f| f.into_inner()).collect();
-
-    pack_index(&msf_flags, dest);
+    pack_index(&msf_flags.into_vec(), dest);
     t.next("Packing");
 }
@@ -25,5 +25,7 @@ pub mod serial_msf;
 // SOFTWARE.
 // ============================================================================
 
+pub mod bench;
+pub mod check;
 pub mod inc_msf_mod;
 pub mod incremental_msf;
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,69 +24,23 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
+use pbbs::benchmarks::min_span_forest::bench::{read_input, run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::min_span_forest::check::MsfCheck;
+use pbbs::common::io::write_slice_to_file_seq;
+use pbbs::{check, define_args, finalize, report, sweep};
 
-#[path = "../../common/graph.rs"]
-mod graph;
-#[path = "../../common/graph_io.rs"]
-mod graph_io;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod msf;
-#[path = "../../algorithm/union_find.rs"]
-mod union_find;
-
-use graph::WghEdgeArray;
-use graph_io::read_wgh_edge_array_from_file;
-use io::write_slice_to_file_seq;
-use misc::*;
-use msf::{inc_msf_mod, incremental_msf, serial_msf};
-
-define_args!(Algs::INCREMENTAL);
-
-define_algs!(
-    (SERIAL, "serial"),
-    (INCREMENTAL, "incremental"),
-    (INCMOD, "incmod")
-);
-
-pub fn run(alg: Algs, rounds: usize, ea: WghEdgeArray) -> (Vec<DefInt>, Duration) {
-    let sf = match alg {
-        Algs::SERIAL => serial_msf::minimum_spanning_forest,
-        Algs::INCREMENTAL => incremental_msf::minimum_spanning_forest,
-        Algs::INCMOD => inc_msf_mod::minimum_spanning_forest,
-    };
-
-    let mut r = vec![];
-    let mut ea_copy = ea.clone();
-    let ea_copy_shadow = unsafe { (&ea_copy as *const WghEdgeArray).as_ref().unwrap() };
-    let mean = time_loop(
-        "msf",
-        rounds,
-        Duration::new(1, 0),
-        || {
-            if alg == Algs::INCMOD {
-                ea_copy = ea.clone();
-            }
-        },
-        || {
-            sf(&ea_copy_shadow, &mut r);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG);
 
 fn main() {
-    init!();
     let args = Args::parse();
-    let ea = read_wgh_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, ea);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    let pool = args.threads.build_pool();
+    let ea = read_input(&args.ifname).expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &ea).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &ea));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, MsfCheck, &ea, &r);
+    report!(args, d, verdict);
 }
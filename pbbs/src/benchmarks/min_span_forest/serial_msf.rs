@@ -25,8 +25,8 @@ use std::cmp::{min, Ordering};
 // SOFTWARE.
 // ============================================================================
 
-use crate::graph::{WghEdge, WghEdgeArray};
-use crate::union_find::UnionFind;
+use crate::algorithm::union_find::UnionFind;
+use crate::common::graph::{WghEdge, WghEdgeArray};
 use crate::{DefFloat, DefInt, DefIntS};
 
 #[derive(Clone, Copy)]
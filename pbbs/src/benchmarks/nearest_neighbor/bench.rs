@@ -0,0 +1,74 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::nearest_neighbor;
+use crate::benchmarks::nearest_neighbor::check::KnnCheck;
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::{geometry::Point, geometry_io};
+
+define_algs!((Naive, "naive"), (Chan05, "chan05"));
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::Naive;
+
+pub fn run<const D: usize>(
+    alg: Algs,
+    opts: &TimeOptions,
+    pts: &Vec<Point<D, f64>>,
+) -> (Vec<usize>, Samples) {
+    let f = match alg {
+        Algs::Naive => nearest_neighbor::naive::knn,
+        Algs::Chan05 => nearest_neighbor::chan05::knn,
+    };
+
+    let mut r = Vec::new();
+    let samples = time_loop("nbody", opts, || {}, || r = f(pts), || {});
+
+    (r, samples)
+}
+
+fn suite_run_d<const D: usize>(spec: &RunSpec, alg: Algs) -> Result<RunOutcome, RunError> {
+    let pts = geometry_io::read_points_from_file::<D, f64>(&spec.input).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &pts);
+    Ok(outcome(spec, alg, d, || {
+        KnnCheck::<D>::default().check(&pts, &r)
+    }))
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    match spec.param("dimension", 2)? {
+        2 => suite_run_d::<2>(spec, alg),
+        3 => suite_run_d::<3>(spec, alg),
+        _ => Err(RunError::Param {
+            name: "dimension".to_string(),
+            reason: "it must be 2 or 3".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,106 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::random::Random;
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::geometry::Point;
+use crate::common::geometry_io::read_points_from_file;
+use crate::common::io::{read_file_to_vec, ReadOptions};
+
+/// Checks the nearest neighbors of up to `samples` random points (all of
+/// them if zero) against a brute-force search.
+pub struct KnnCheck<const D: usize> {
+    pub samples: usize,
+}
+
+impl<const D: usize> Default for KnnCheck<D> {
+    fn default() -> Self {
+        Self { samples: 1000 }
+    }
+}
+
+impl<const D: usize> Checker for KnnCheck<D> {
+    type Input = Vec<Point<D, f64>>;
+    type Output = Vec<usize>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_points_from_file(fname)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, pts: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let n = pts.len();
+        if out.len() != n {
+            return invalid(format!("{} neighbors for {n} points", out.len()));
+        }
+        if let Some(i) = (0..n)
+            .into_par_iter()
+            .find_first(|&i| out[i] >= n || out[i] == i)
+        {
+            return invalid(format!("point {i} has no valid neighbor ({})", out[i]));
+        }
+
+        let rand = Random::new(0);
+        let sample: Vec<usize> = match self.samples {
+            0 => (0..n).collect(),
+            s if s >= n => (0..n).collect(),
+            s => (0..s)
+                .map(|i| rand.ith_rand(i as u64) as usize % n)
+                .collect(),
+        };
+        let dist = |i: usize, j: usize| (pts[i] - pts[j]).length_squared();
+        let wrong = sample
+            .into_par_iter()
+            .find_first(|&i| (0..n).any(|j| j != i && dist(i, j) < dist(i, out[i])));
+        match wrong {
+            Some(i) => invalid(format!("{} is not the nearest neighbor of {i}", out[i])),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    #[test]
+    fn nearest_neighbors() {
+        let pts: Vec<Point<2, f64>> = [0.0, 1.0, 3.0, 10.0]
+            .iter()
+            .map(|&x| Point::new([x, 0.0]))
+            .collect();
+        let check = KnnCheck::<2> { samples: 0 };
+        assert!(check.check(&pts, &vec![1, 0, 1, 2]).is_ok());
+        assert!(check.check(&pts, &vec![1, 0, 1, 0]).is_err());
+        assert!(check.check(&pts, &vec![1, 1, 1, 2]).is_err());
+        assert!(check.check(&pts, &vec![1, 0, 1]).is_err());
+    }
+}
@@ -1,55 +1,24 @@
-#![allow(dead_code)]
-
-#[path = "../../common/mod.rs"]
-mod common;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod nearest_neighbor;
-
-use misc::*;
-use std::time::Duration;
-
-use crate::common::{geometry::Point, geometry_io, io::write_slice_to_file_seq};
-
-define_args!(Algs::Naive, (dimension, usize, 2));
-define_algs!((Naive, "naive"), (Chan05, "chan05"));
-
-fn run<const D: usize>(
-    alg: Algs,
-    rounds: usize,
-    pts: &Vec<Point<D, f64>>,
-) -> (Vec<usize>, Duration) {
-    let f = match alg {
-        Algs::Naive => nearest_neighbor::naive::knn,
-        Algs::Chan05 => nearest_neighbor::chan05::knn,
-    };
-
-    let mut r = Vec::new();
-    let mean = time_loop(
-        "nbody",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || r = f(pts),
-        || {},
-    );
-
-    (r, mean)
-}
+use pbbs::benchmarks::nearest_neighbor::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::nearest_neighbor::check::KnnCheck;
+use pbbs::common::{geometry_io, io::write_slice_to_file_seq};
+use pbbs::{check, define_args, finalize, report, sweep};
 
+define_args!(DEFAULT_ALG, (dimension, usize, 2));
 fn handle_args<const D: usize>(args: Args) {
-    let pts = geometry_io::read_points_from_file::<D, f64>(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, &pts);
+    let pool = args.threads.build_pool();
+    let pts =
+        geometry_io::read_points_from_file::<D, f64>(&args.ifname).expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &pts).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &pts));
 
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, KnnCheck::<D>::default(), &pts, &r);
+    report!(args, d, verdict);
 }
 
 fn main() {
-    init!();
-
     let args = Args::parse();
     if args.dimension == 2 {
         handle_args::<2>(args);
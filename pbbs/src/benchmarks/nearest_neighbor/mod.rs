@@ -1,2 +1,4 @@
-pub(crate) mod chan05;
-pub(crate) mod naive;
+pub mod bench;
+pub mod chan05;
+pub mod check;
+pub mod naive;
@@ -0,0 +1,73 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::{read_file_to_vec, ReadOptions, SEQ_INT_HEADER};
+
+/// Checks that the result holds every distinct key of the input exactly
+/// once, in any order.
+pub struct DedupCheck;
+
+impl Checker for DedupCheck {
+    type Input = Vec<u32>;
+    type Output = Vec<u32>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_file_to_vec(
+            fname,
+            &ReadOptions::with_header(SEQ_INT_HEADER),
+        )?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let mut inp_dedup = inp.clone();
+        inp_dedup.par_sort_unstable();
+        inp_dedup.dedup();
+        let mut out = out.clone();
+        out.par_sort_unstable();
+
+        if out.len() != inp_dedup.len() {
+            return invalid(format!(
+                "{} keys in the result, the input has {} distinct keys",
+                out.len(),
+                inp_dedup.len()
+            ));
+        }
+        let diff_count = (&inp_dedup, &out)
+            .into_par_iter()
+            .filter(|(i, o)| i != o)
+            .count();
+        if diff_count != 0 {
+            return invalid(format!("the result has {diff_count} differences"));
+        }
+        Ok(())
+    }
+}
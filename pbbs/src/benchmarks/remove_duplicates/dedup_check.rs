@@ -1,4 +1,4 @@
-#![feature(slice_partition_dedup)]
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,46 +25,13 @@
 // SOFTWARE.
 // ============================================================================
 
-use clap::Parser;
-use rayon::prelude::*;
+use pbbs::benchmarks::remove_duplicates::check::DedupCheck;
+use pbbs::define_check_args;
 
-#[path ="../../common/io.rs"] mod io;
-use io::read_file_to_vec;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// BW results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-}
-
-pub fn check(inp: &mut [i64], out: &mut [i64]) -> bool {
-    inp.par_sort_unstable();
-    let (inp_dedup, _) = inp.partition_dedup();
-    out.par_sort_unstable();
-
-    let mut diff_count = 0usize;
-    for i in 0..inp_dedup.len() {
-        if out[i] != inp_dedup[i] {
-            diff_count+=1;
-        }
-    }
-
-    if diff_count!=0 {
-        eprintln!("output file has {diff_count} differences.");
-        false
-    } else { true }
-}
+define_check_args!();
 
 fn main() {
     let args = Args::parse();
-    let mut inp = read_file_to_vec(&args.ifname, Some(|a: &[&str]| assert_eq!(a[0], "sequenceInt")));
-    let mut out = read_file_to_vec(&args.rfname, Some(|_: &[&str]| {}));
-    if check(&mut inp, &mut out) { println!("OK"); }
-    else { eprintln!("ERR"); std::process::exit(1); }
+    let checker = DedupCheck;
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
@@ -1,4 +1,3 @@
-use std::time::Duration;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,56 +24,30 @@ use std::time::Duration;
 // SOFTWARE.
 // ============================================================================
 
-#[path = "mod.rs"]
-mod dedup;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-
-use dedup::parlay_hash;
-use io::{read_big_file_to_vec, write_slice_to_file_seq};
-
-define_args!(Algs::PARHASH);
-define_algs!((PARHASH, "parhash"));
-
-pub fn run(alg: Algs, rounds: usize, arr: &[u32]) -> (Vec<u32>, Duration) {
-    let f = match alg {
-        Algs::PARHASH => parlay_hash::dedup,
-    };
-
-    let mut r = vec![];
-    let r_ptr = &r as *const Vec<u32> as usize;
-
-    let mean = time_loop(
-        "dedup",
-        rounds,
-        Duration::new(1, 0),
-        || unsafe {
-            *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![];
-        },
-        || {
-            f(&arr, &mut r);
-        },
-        || {},
-    );
-    (r, mean)
-}
+use pbbs::benchmarks::remove_duplicates::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::remove_duplicates::check::DedupCheck;
+use pbbs::common::io::{
+    read_big_file_to_vec, write_slice_to_file_seq, ReadOptions, SEQ_INT_HEADER,
+};
+use pbbs::{check, define_args, finalize, report, sweep};
 
+define_args!(DEFAULT_ALG);
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let mut arr = Vec::new();
     read_big_file_to_vec(
         &args.ifname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "sequenceInt"),
-        },
+        &ReadOptions::with_header(SEQ_INT_HEADER),
         &mut arr,
-    );
-    let (r, d) = run(args.algorithm, args.rounds, &arr);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    )
+    .expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &arr).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &arr));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, DedupCheck, &arr, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,76 @@
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::spanning_forest::check::SfCheck;
+use crate::benchmarks::spanning_forest::{incremental_sf, serial_sf};
+use crate::benchmarks::suite::outcome;
+use crate::common::check::Checker;
+use crate::common::graph::EdgeArray;
+use crate::common::graph_io::read_edge_array_from_file;
+use crate::common::shared::{AtomicArray, RawArray};
+use crate::misc::*;
+
+define_algs!(
+    (SERIAL, "serial"),
+    (INCREMENTAL, "incremental"),
+    (IncrementalAtomic, "incremental-atomic"),
+    (IncrementalRaw, "incremental-raw");
+    serial: SERIAL
+);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::INCREMENTAL;
+
+pub fn run(alg: Algs, opts: &TimeOptions, ea: &EdgeArray) -> (Vec<DefInt>, Samples) {
+    let sf = match alg {
+        Algs::SERIAL => serial_sf::spanning_forest,
+        Algs::INCREMENTAL => incremental_sf::spanning_forest,
+        Algs::IncrementalAtomic => incremental_sf::spanning_forest_with::<AtomicArray<DefIntS>>,
+        Algs::IncrementalRaw => incremental_sf::spanning_forest_with::<RawArray<DefIntS>>,
+    };
+
+    let mut r = vec![];
+
+    let samples = time_loop(
+        "sf",
+        opts,
+        || {},
+        || {
+            r = sf(ea);
+        },
+        || {},
+    );
+    (r, samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let g = read_edge_array_from_file(&spec.input).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &g);
+    Ok(outcome(spec, alg, d, || SfCheck.check(&g, &r)))
+}
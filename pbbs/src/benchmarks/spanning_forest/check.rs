@@ -0,0 +1,82 @@
+use parlay::primitives::pack;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::benchmarks::spanning_forest::serial_sf::spanning_forest;
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::graph::EdgeArray;
+use crate::common::graph_io::read_edge_array_from_file;
+use crate::common::io::{read_file_to_vec_seq, ReadOptions};
+use crate::DefInt;
+
+/// Checks that the chosen edges form a spanning forest, comparing with the
+/// serial algorithm.
+pub struct SfCheck;
+
+impl Checker for SfCheck {
+    type Input = EdgeArray;
+    type Output = Vec<DefInt>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(read_edge_array_from_file(fname)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec_seq(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, ea: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let n = out.len();
+
+        //run serial ST
+        let serial_st = spanning_forest(ea);
+        if n != serial_st.len() {
+            return invalid(format!(
+                "wrong edge count: the forest has {} edges but the result has {n}",
+                serial_st.len()
+            ));
+        }
+
+        //check if ST has cycles by running serial ST on it
+        //and seeing if result changes
+        let mut flags = vec![false; ea.non_zeros];
+        for &o in out {
+            match flags.get_mut(o as usize) {
+                Some(f) => *f = true,
+                None => return invalid(format!("edge {o} does not exist")),
+            }
+        }
+        let mut new_es = vec![];
+        pack(&ea.es, &flags, &mut new_es);
+        let m = new_es.len();
+
+        let new_ea = EdgeArray::new(new_es, ea.num_rows, ea.num_cols);
+        if m != n || m != spanning_forest(&new_ea).len() {
+            return invalid("the result is not a spanning forest".to_string());
+        }
+        Ok(())
+    }
+}
@@ -27,18 +27,14 @@ use std::mem::swap;
 
 use rayon::prelude::*;
 
-use crate::graph::EdgeArray;
+use crate::algorithm::union_find::SharedUnionFind;
+use crate::common::graph::EdgeArray;
+use crate::common::shared::{DefaultArray, SharedArray};
 use crate::{DefInt, DefIntS};
 
-#[path = "../../common/spec_for.rs"]
-mod spec_for;
+use crate::common::spec_for;
 use spec_for::{Reservation, StatefulSpecFor};
 
-#[cfg(feature = "AW_safe")]
-use crate::union_find::AtomicUnionFind;
-#[cfg(not(feature = "AW_safe"))]
-use crate::union_find::UnionFind;
-
 #[derive(Clone)]
 struct SFState {
     u: DefIntS,
@@ -46,38 +42,23 @@ struct SFState {
 }
 
 pub fn spanning_forest(ea: &EdgeArray) -> Vec<u32> {
+    spanning_forest_with::<DefaultArray<DefIntS>>(ea)
+}
+
+/// Runs with the parents of the union-find in `A`: `AtomicArray` (safe) or
+/// `RawArray` (unsafe).
+pub fn spanning_forest_with<A: SharedArray<DefIntS>>(ea: &EdgeArray) -> Vec<u32> {
     let m = ea.non_zeros;
     let n = ea.num_rows;
 
-    #[cfg(not(feature = "AW_safe"))]
-    let uf = UnionFind::new(n);
-    #[cfg(not(feature = "AW_safe"))]
-    let uf_ptr = &uf as *const UnionFind as usize;
-
-    #[cfg(feature = "AW_safe")]
-    let uf = AtomicUnionFind::new(n);
+    let uf = SharedUnionFind::<A>::new(n);
 
     let rs: Vec<Reservation> = (0..n).into_par_iter().map(|_| Reservation::new()).collect();
 
     let reserve = |i: usize, s: &mut SFState| -> bool {
         let e = &ea[i];
-
-        #[cfg(feature = "AW_safe")]
-        {
-            s.u = uf.find(e.u as i32);
-            s.v = uf.find(e.v as i32);
-        }
-        #[cfg(not(feature = "AW_safe"))]
-        unsafe {
-            s.u = (uf_ptr as *mut UnionFind)
-                .as_mut()
-                .unwrap()
-                .find(e.u as i32);
-            s.v = (uf_ptr as *mut UnionFind)
-                .as_mut()
-                .unwrap()
-                .find(e.v as i32);
-        }
+        s.u = uf.find(e.u as i32);
+        s.v = uf.find(e.v as i32);
 
         if s.u > s.v {
             swap(&mut s.u, &mut s.v);
@@ -93,14 +74,7 @@ pub fn spanning_forest(ea: &EdgeArray) -> Vec<u32> {
 
     let commit = |i: usize, s: &mut SFState| -> bool {
         if rs[s.v as usize].check(i as DefInt) {
-            #[cfg(feature = "AW_safe")]
-            {
-                uf.link(s.v, s.u);
-            }
-            #[cfg(not(feature = "AW_safe"))]
-            unsafe {
-                (uf_ptr as *mut UnionFind).as_mut().unwrap().link(s.v, s.u);
-            }
+            uf.link(s.v, s.u);
             true
         } else {
             false
@@ -116,3 +90,30 @@ pub fn spanning_forest(ea: &EdgeArray) -> Vec<u32> {
         .filter_map(|r| if r.reserved() { Some(r.get()) } else { None })
         .collect()
 }
+
+#[cfg(test)]
+mod sf_tests {
+    use super::*;
+    use crate::benchmarks::spanning_forest::check::SfCheck;
+    use crate::common::check::Checker;
+    use crate::common::graph::Edge;
+    use crate::common::shared::{AtomicArray, RawArray};
+    use parlay::random::Random;
+
+    #[test]
+    fn atomic_and_raw_agree() {
+        let (n, r) = (2000, Random::new(1));
+        let es = (0..3000)
+            .map(|i| {
+                Edge::new(
+                    (r.ith_rand(2 * i) % n) as _,
+                    (r.ith_rand(2 * i + 1) % n) as _,
+                )
+            })
+            .collect();
+        let ea = EdgeArray::new(es, n as usize, n as usize);
+        let atomic = spanning_forest_with::<AtomicArray<DefIntS>>(&ea);
+        assert!(SfCheck.check(&ea, &atomic).is_ok());
+        assert_eq!(spanning_forest_with::<RawArray<DefIntS>>(&ea), atomic);
+    }
+}
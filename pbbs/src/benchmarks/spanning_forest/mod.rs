@@ -25,4 +25,6 @@ pub mod serial_sf;
 // SOFTWARE.
 // ============================================================================
 
+pub mod bench;
+pub mod check;
 pub mod incremental_sf;
@@ -25,8 +25,8 @@ use parlay::maybe_uninit_vec;
 // SOFTWARE.
 // ============================================================================
 
-use crate::graph::EdgeArray;
-use crate::union_find::UnionFind;
+use crate::algorithm::union_find::UnionFind;
+use crate::common::graph::EdgeArray;
 use crate::{DefInt, DefIntS};
 
 pub fn spanning_forest(ea: &EdgeArray) -> Vec<DefInt> {
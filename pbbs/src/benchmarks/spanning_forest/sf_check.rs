@@ -1,4 +1,4 @@
-use clap::Parser;
+use pbbs::common::check::{exit_on_error, Checker};
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,66 +25,13 @@ use clap::Parser;
 // SOFTWARE.
 // ============================================================================
 
+use pbbs::benchmarks::spanning_forest::check::SfCheck;
+use pbbs::define_check_args;
 
-use parlay::primitives::pack;
-use pbbs::DefInt;
-use pbbs::common::graph::EdgeArray;
-use pbbs::common::io::read_file_to_vec_seq;
-use pbbs::common::graph_io::read_edge_array_from_file;
-use pbbs::benchmarks::spanning_forest::serial_sf::spanning_forest;
-
-#[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
-struct Args {
-    /// mis results filename
-    #[clap(value_parser, required=true)]
-    rfname: String,
-
-    /// the input graph's filename
-    #[clap(value_parser, required=true)]
-    ifname: String,
-}
-
-pub fn check(ea: &EdgeArray, out: &[DefInt]) -> bool {
-    let n = out.len();
-
-    //run serial ST
-    let serial_st = spanning_forest(ea);
-    if n != serial_st.len() {
-        println!(
-            "Wrong edge count: ST has {}
-                edges but algorithm returned {} edges.",
-            serial_st.len(),
-            n
-        );
-        return false;
-    }
-
-    //check if ST has cycles by running serial ST on it
-    //and seeing if result changes
-    let mut flags = vec![false; ea.non_zeros];
-    out
-        .iter()
-        .for_each(|&o| flags[o as usize] = true);
-    let mut new_es = vec![];
-    pack(&ea.es, &flags, &mut new_es);
-    let m = new_es.len();
-
-    let new_ea = EdgeArray::new(new_es, ea.num_rows, ea.num_cols);
-    let check = spanning_forest(&new_ea);
-
-    if m != check.len() {
-        println!("Result is not a spanning tree");
-        return false
-    }
-
-    true
-}
+define_check_args!();
 
 fn main() {
     let args = Args::parse();
-    let ea = read_edge_array_from_file(&args.ifname);
-    let r: Vec<DefInt> = read_file_to_vec_seq(&args.rfname);
-    if check(&ea, &r) { println!("OK"); }
-    else { println!("ERR"); std::process::exit(1); }
+    let checker = SfCheck;
+    exit_on_error(checker.check_files(&args.ifname, &args.rfname));
 }
@@ -1,4 +1,3 @@
-#![allow(dead_code)]
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,59 +24,24 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::time::Duration;
+use pbbs::benchmarks::spanning_forest::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::spanning_forest::check::SfCheck;
+use pbbs::common::graph_io::read_edge_array_from_file;
+use pbbs::common::io::write_slice_to_file_seq;
+use pbbs::{check, define_args, finalize, report, sweep};
 
-#[path = "../../common/graph.rs"]
-mod graph;
-#[path = "../../common/graph_io.rs"]
-mod graph_io;
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod sf;
-#[path = "../../algorithm/union_find.rs"]
-mod union_find;
-
-use graph::EdgeArray;
-use graph_io::read_edge_array_from_file;
-use io::write_slice_to_file_seq;
-use misc::*;
-use sf::{incremental_sf, serial_sf};
-
-define_args!(Algs::INCREMENTAL);
-
-define_algs!((SERIAL, "serial"), (INCREMENTAL, "incremental"));
-
-pub fn run(alg: Algs, rounds: usize, ea: EdgeArray) -> (Vec<DefInt>, Duration) {
-    let sf = match alg {
-        Algs::SERIAL => serial_sf::spanning_forest,
-        Algs::INCREMENTAL => incremental_sf::spanning_forest,
-    };
-
-    let mut r = vec![];
-
-    let mean = time_loop(
-        "sf",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            r = sf(&ea);
-        },
-        || {},
-    );
-    (r, mean)
-}
+define_args!(DEFAULT_ALG);
 
 fn main() {
-    init!();
     let args = Args::parse();
-    let g = read_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    let pool = args.threads.build_pool();
+    let g = read_edge_array_from_file(&args.ifname).expect("cannot read input file");
+    if sweep!(args, |alg| run(alg, &args.time, &g).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &g));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, SfCheck, &g, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,93 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::{chars_from_file, read_file_to_vec, ReadOptions};
+use crate::{DefChar, DefInt};
+
+/// Checks a suffix array in linear time: once it is a permutation, it is
+/// sorted iff every two neighbors are ordered by their first char and then
+/// by the ranks of the suffixes that follow it.
+pub struct SaCheck;
+
+impl Checker for SaCheck {
+    type Input = Vec<DefChar>;
+    type Output = Vec<DefInt>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        Ok(read_file_to_vec(fname, &ReadOptions::default())?)
+    }
+
+    fn check(&self, s: &Self::Input, sa: &Self::Output) -> Result<(), CheckError> {
+        let n = s.len();
+        if sa.len() != n {
+            return invalid(format!("{} suffixes for a text of {n} chars", sa.len()));
+        }
+
+        // the empty suffix at n ranks before all others
+        let mut rank = vec![usize::MAX; n + 1];
+        rank[n] = 0;
+        for (i, &a) in sa.iter().enumerate() {
+            match rank.get_mut(a as usize) {
+                Some(r) if *r == usize::MAX && (a as usize) < n => *r = i + 1,
+                _ => return invalid(format!("{a} at {i} is not a new suffix")),
+            }
+        }
+
+        let key = |a: usize| (s[a], rank[a + 1]);
+        match sa
+            .par_windows(2)
+            .position_first(|w| key(w[0] as usize) >= key(w[1] as usize))
+        {
+            Some(i) => invalid(format!(
+                "suffixes {} and {} at {i} are out of order",
+                sa[i],
+                sa[i + 1]
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    #[test]
+    fn suffix_order() {
+        let s = b"banana".to_vec();
+        assert!(SaCheck.check(&s, &vec![5, 3, 1, 0, 4, 2]).is_ok());
+        assert!(SaCheck.check(&s, &vec![5, 3, 1, 0, 2, 4]).is_err());
+        assert!(SaCheck.check(&s, &vec![5, 3, 1, 0, 4, 4]).is_err());
+        assert!(SaCheck.check(&s, &vec![5, 3, 1, 0, 4]).is_err());
+        assert!(SaCheck.check(&s, &vec![5, 3, 1, 0, 4, 6]).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-pub(crate) mod parallel_range;
+pub mod parallel_range;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -24,3 +24,6 @@ pub(crate) mod parallel_range;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 // ============================================================================
+
+pub mod bench;
+pub mod check;
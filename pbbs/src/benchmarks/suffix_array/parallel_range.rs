@@ -1,4 +1,4 @@
-use crate::suffix_array;
+use crate::algorithm::suffix_array;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,14 +25,11 @@ use crate::suffix_array;
 // SOFTWARE.
 // ============================================================================
 
-use crate::DefChar;
+use crate::common::shared::SharedArray;
+use crate::{DefChar, DefInt};
 
-#[cfg(not(feature = "AW_safe"))]
-pub fn suffix_array(s: &[DefChar], r: &mut [crate::DefInt]) {
-    suffix_array::suffix_array(s, r);
-}
-
-#[cfg(feature = "AW_safe")]
-pub fn suffix_array(s: &[DefChar], r: &mut [crate::DefAtomInt]) {
-    suffix_array::atomic_suffix_array(s, r);
+/// Writes the suffix array of `s` into `r`, with the ranks and the
+/// character flags in `A`: `AtomicArray` (safe) or `RawArray` (unsafe).
+pub fn suffix_array<A: SharedArray<DefInt>>(s: &[DefChar], r: &A) {
+    suffix_array::suffix_array_with(s, r);
 }
@@ -24,63 +24,22 @@
 // SOFTWARE.
 // ============================================================================
 
-use rayon::prelude::*;
-use std::time::Duration;
-
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod sa;
-#[path = "../../algorithm/suffix_array.rs"]
-mod suffix_array;
-
-use io::{chars_from_file, write_slice_to_file_seq};
-use misc::*;
-use sa::parallel_range;
-
-define_args!(Algs::ParRange);
-define_algs!((ParRange, "par-range"));
-
-pub fn run(alg: Algs, rounds: usize, inp: &[DefChar]) -> (Vec<DefInt>, Duration) {
-    let f = match alg {
-        Algs::ParRange => parallel_range::suffix_array,
-    };
-
-    #[cfg(not(feature = "AW_safe"))]
-    let mut r: Vec<_> = (0..inp.len())
-        .into_par_iter()
-        .map(|_| DefInt::default())
-        .collect();
-    #[cfg(feature = "AW_safe")]
-    let mut r: Vec<_> = (0..inp.len())
-        .into_par_iter()
-        .map(|_| DefAtomInt::default())
-        .collect();
-
-    let mean = time_loop(
-        "sa",
-        rounds,
-        Duration::new(1, 0),
-        || {},
-        || {
-            f(&inp, &mut r);
-        },
-        || {},
-    );
-    #[cfg(feature = "AW_safe")]
-    let r: Vec<_> = r.into_par_iter().map(|ri| ri.load(ORDER)).collect();
-    (r, mean)
-}
+use pbbs::benchmarks::suffix_array::bench::{run, Algs, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::suffix_array::check::SaCheck;
+use pbbs::common::io::{chars_from_file, write_slice_to_file_seq};
+use pbbs::{check, define_args, finalize, report, sweep};
 
+define_args!(DEFAULT_ALG);
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let arr = chars_from_file(&args.ifname, false).unwrap();
-    let (r, d) = run(args.algorithm, args.rounds, &arr);
-
-    finalize!(args, r, d, write_slice_to_file_seq(&r, args.ofname));
+    if sweep!(args, |alg| run(alg, &args.time, &arr).1) {
+        return;
+    }
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &arr));
+
+    finalize!(args, r, d, write_slice_to_file_seq(&r, &args.ofname));
+    let verdict = check!(args, SaCheck, &arr, &r);
+    report!(args, d, verdict);
 }
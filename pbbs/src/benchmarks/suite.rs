@@ -0,0 +1,168 @@
+use std::fmt::Display;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use clap::ValueEnum;
+use rpb_bench::{algorithm_names, Benchmark, RunError, RunOutcome, RunSpec, Warmup};
+
+use crate::benchmarks::*;
+use crate::common::check::{print_verdict, CheckError};
+use crate::common::time_loop::Samples;
+
+/// Returns the benchmarks of pbbs, named after their timing binaries.
+pub fn benchmarks() -> Vec<Benchmark> {
+    vec![
+        benchmark(
+            "bw",
+            bw_decode::bench::DEFAULT_ALG,
+            &[],
+            bw_decode::bench::suite_run,
+        ),
+        benchmark(
+            "sort",
+            comparison_sort::bench::DEFAULT_ALG,
+            &["stable"],
+            comparison_sort::bench::suite_run,
+        ),
+        benchmark(
+            "hull",
+            convex_hull::bench::DEFAULT_ALG,
+            &[],
+            convex_hull::bench::suite_run,
+        ),
+        benchmark(
+            "dr",
+            delaunay_refine::bench::DEFAULT_ALG,
+            &[],
+            delaunay_refine::bench::suite_run,
+        ),
+        benchmark(
+            "hist",
+            histogram::bench::DEFAULT_ALG,
+            &["buckets"],
+            histogram::bench::suite_run,
+        ),
+        benchmark(
+            "isort",
+            integer_sort::bench::DEFAULT_ALG,
+            &["bits"],
+            integer_sort::bench::suite_run,
+        ),
+        Benchmark {
+            warmup: inverted_index::bench::DEFAULT_WARMUP,
+            ..benchmark(
+                "index",
+                inverted_index::bench::DEFAULT_ALG,
+                &[],
+                inverted_index::bench::suite_run,
+            )
+        },
+        benchmark(
+            "lrs",
+            longest_repeated_sub_string::bench::DEFAULT_ALG,
+            &[],
+            longest_repeated_sub_string::bench::suite_run,
+        ),
+        benchmark(
+            "mis",
+            maximal_independent_set::bench::DEFAULT_ALG,
+            &["compressed"],
+            maximal_independent_set::bench::suite_run,
+        ),
+        benchmark(
+            "mm",
+            maximal_matching::bench::DEFAULT_ALG,
+            &[],
+            maximal_matching::bench::suite_run,
+        ),
+        benchmark(
+            "msf",
+            min_span_forest::bench::DEFAULT_ALG,
+            &[],
+            min_span_forest::bench::suite_run,
+        ),
+        benchmark(
+            "knn",
+            nearest_neighbor::bench::DEFAULT_ALG,
+            &["dimension"],
+            nearest_neighbor::bench::suite_run,
+        ),
+        benchmark(
+            "dedup",
+            remove_duplicates::bench::DEFAULT_ALG,
+            &[],
+            remove_duplicates::bench::suite_run,
+        ),
+        benchmark(
+            "sf",
+            spanning_forest::bench::DEFAULT_ALG,
+            &[],
+            spanning_forest::bench::suite_run,
+        ),
+        benchmark(
+            "sa",
+            suffix_array::bench::DEFAULT_ALG,
+            &[],
+            suffix_array::bench::suite_run,
+        ),
+        benchmark(
+            "wc",
+            word_counts::bench::DEFAULT_ALG,
+            &[],
+            word_counts::bench::suite_run,
+        ),
+    ]
+}
+
+fn benchmark<A: ValueEnum>(
+    name: &'static str,
+    default: A,
+    params: &'static [&'static str],
+    run: fn(&RunSpec) -> Result<RunOutcome, RunError>,
+) -> Benchmark {
+    Benchmark {
+        name,
+        algorithms: algorithm_names(default),
+        params,
+        features: crate::features,
+        warmup: Warmup::DEFAULT,
+        run,
+    }
+}
+
+/// Returns the outcome of a run, checking its result with `check` if the
+/// spec asks for it.
+pub(crate) fn outcome<A, C>(spec: &RunSpec, alg: A, samples: Samples, check: C) -> RunOutcome
+where
+    A: Display,
+    C: FnOnce() -> Result<(), CheckError>,
+{
+    RunOutcome {
+        algorithm: alg.to_string(),
+        samples,
+        check: spec.check.then(|| print_verdict(check())),
+    }
+}
@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_bench::{RunError, RunOutcome, RunSpec};
+
+use crate::benchmarks::suite::outcome;
+use crate::benchmarks::word_counts::check::WcCheck;
+use crate::benchmarks::word_counts::{wc_histogram, wc_serial};
+use crate::common::check::Checker;
+use crate::common::io::chars_from_file;
+use crate::misc::*;
+
+pub type ResultType = (String, usize);
+
+define_algs!((Serial, "serial"), (Histogram, "histogram"); serial: Serial);
+
+/// the algorithm the benchmark runs by default.
+pub const DEFAULT_ALG: Algs = Algs::Serial;
+
+pub fn run(alg: Algs, opts: &TimeOptions, inp: &[DefChar]) -> (Vec<ResultType>, Samples) {
+    let f = match alg {
+        Algs::Serial => wc_serial::word_counts,
+        Algs::Histogram => wc_histogram::word_counts,
+    };
+
+    let r: RefCell<Vec<ResultType>> = RefCell::new(Vec::new());
+
+    // convert u8 (DefChar) to char type
+    // let vec_inp: Vec<char> = inp.iter().map(|c| *c as char).collect();
+
+    let samples = time_loop(
+        "wc",
+        opts,
+        || {
+            r.borrow_mut().clear();
+        },
+        || {
+            f(inp, &mut r.borrow_mut());
+        },
+        || {},
+    );
+
+    (r.into_inner(), samples)
+}
+
+/// Runs the benchmark as a suite manifest describes it.
+pub fn suite_run(spec: &RunSpec) -> Result<RunOutcome, RunError> {
+    let alg = spec.algorithm(DEFAULT_ALG)?;
+    let arr = chars_from_file(&spec.input, false).map_err(RunError::input)?;
+    let (r, d) = run(alg, &spec.time, &arr);
+    Ok(outcome(spec, alg, d, || WcCheck.check(&arr, &r)))
+}
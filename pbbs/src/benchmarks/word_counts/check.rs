@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use crate::common::check::{invalid, CheckError, Checker};
+use crate::common::io::chars_from_file;
+use crate::DefChar;
+
+type ResultType = (String, usize);
+
+/// Checks the counts of the words (maximal runs of ASCII letters, ignoring
+/// case) against a plain hash map count.
+pub struct WcCheck;
+
+impl Checker for WcCheck {
+    type Input = Vec<DefChar>;
+    type Output = Vec<ResultType>;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError> {
+        Ok(chars_from_file(fname, false)?)
+    }
+
+    /// Reads one `(word, count)` per line.
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError> {
+        std::fs::read_to_string(fname)?
+            .lines()
+            .enumerate()
+            .map(|(i, l)| {
+                l.strip_prefix('(')
+                    .and_then(|l| l.strip_suffix(')'))
+                    .and_then(|l| l.split_once(", "))
+                    .and_then(|(w, c)| Some((w.to_string(), c.parse().ok()?)))
+                    .ok_or_else(|| CheckError::Format(format!("line {}: {l:?}", i + 1)))
+            })
+            .collect()
+    }
+
+    fn check(&self, s: &Self::Input, out: &Self::Output) -> Result<(), CheckError> {
+        let mut counts: HashMap<Vec<DefChar>, usize> = HashMap::new();
+        for w in s
+            .split(|c| !c.is_ascii_alphabetic())
+            .filter(|w| !w.is_empty())
+        {
+            *counts.entry(w.to_ascii_lowercase()).or_default() += 1;
+        }
+        let mut expected: Vec<ResultType> = counts
+            .into_iter()
+            .map(|(w, c)| (String::from_utf8(w).unwrap(), c))
+            .collect();
+        expected.par_sort_unstable();
+        let mut out = out.clone();
+        out.par_sort_unstable();
+
+        if out.len() != expected.len() {
+            return invalid(format!(
+                "{} words in the result, the text has {}",
+                out.len(),
+                expected.len()
+            ));
+        }
+        match (&expected, &out)
+            .into_par_iter()
+            .find_first(|(e, o)| e != o)
+        {
+            Some((e, o)) => invalid(format!("found {o:?}, expected {e:?}")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_tests {
+    use super::*;
+
+    fn counts(cs: &[(&str, usize)]) -> Vec<ResultType> {
+        cs.iter().map(|&(w, c)| (w.to_string(), c)).collect()
+    }
+
+    #[test]
+    fn word_counts() {
+        let s = b"The cat, the hat.".to_vec();
+        let check = |out: &[(&str, usize)]| WcCheck.check(&s, &counts(out));
+        assert!(check(&[("hat", 1), ("the", 2), ("cat", 1)]).is_ok());
+        assert!(check(&[("hat", 1), ("the", 1), ("cat", 1)]).is_err());
+        assert!(check(&[("the", 2), ("cat", 1)]).is_err());
+        assert!(check(&[("hat", 1), ("The", 2), ("cat", 1)]).is_err());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod bench;
+pub mod check;
 pub mod wc_histogram;
 pub mod wc_serial;
@@ -1,25 +1,12 @@
 use std::fs;
-use std::time::Duration;
-use std::cell::RefCell;
 
-#[path = "../../common/io.rs"]
-mod io;
-#[path = "../macros.rs"]
-mod macros;
-#[path = "../../misc.rs"]
-mod misc;
-#[path = "mod.rs"]
-mod wc;
+use pbbs::benchmarks::word_counts::bench::{run, Algs, ResultType, DEFAULT_ALG, SERIAL_ALG};
+use pbbs::benchmarks::word_counts::check::WcCheck;
+use pbbs::common::io::chars_from_file;
+use pbbs::misc::*;
+use pbbs::{check, define_args, finalize, report, sweep};
 
-use io::chars_from_file;
-use misc::*;
-use wc::{wc_histogram, wc_serial};
-
-type ResultType = (String, usize);
-
-define_args!(Algs::Serial);
-
-define_algs!((Serial, "serial"), (Histogram, "histogram"));
+define_args!(DEFAULT_ALG);
 
 fn write_histograms_to_file(result: &Vec<ResultType>, out_file: &String) {
     let mut output: String = String::new();
@@ -32,37 +19,18 @@ fn write_histograms_to_file(result: &Vec<ResultType>, out_file: &String) {
     fs::write(out_file, output).unwrap();
 }
 
-pub fn run(alg: Algs, rounds: usize, inp: &[DefChar]) -> (Vec<ResultType>, Duration) {
-    let f = match alg {
-        Algs::Serial => wc_serial::word_counts,
-        Algs::Histogram => wc_histogram::word_counts,
-    };
-
-    let r: RefCell<Vec<ResultType>> = RefCell::new(Vec::new());
-
-    // convert u8 (DefChar) to char type
-    // let vec_inp: Vec<char> = inp.iter().map(|c| *c as char).collect();
-
-    let mean = time_loop(
-        "wc",
-        rounds,
-        Duration::new(1, 0),
-        || { r.borrow_mut().clear(); },
-        || {
-            f(&inp, &mut r.borrow_mut());
-        },
-        || {},
-    );
-
-    (r.into_inner(), mean)
-}
-
 fn main() {
-    init!();
     let args = Args::parse();
+    let pool = args.threads.build_pool();
     let arr: Vec<DefChar> = chars_from_file(&args.ifname, false).unwrap();
 
-    let (r, d) = run(args.algorithm, args.rounds, &arr);
+    if sweep!(args, |alg| run(alg, &args.time, &arr).1) {
+        return;
+    }
+
+    let (r, d) = pool.install(|| run(args.algorithm, &args.time, &arr));
 
     finalize!(args, r, d, write_histograms_to_file(&r, &args.ofname));
+    let verdict = check!(args, WcCheck, &arr, &r);
+    report!(args, d, verdict);
 }
@@ -0,0 +1,128 @@
+use std::{fmt, io};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rpb_graph::GraphIoError;
+
+use crate::common::io::ParseError;
+
+/// Why a benchmark's result was rejected.
+pub enum CheckError {
+    Io(io::Error),
+    /// the input or the result file is malformed.
+    Format(String),
+    /// the result is wrong.
+    Invalid(String),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Format(reason) => write!(f, "malformed file: {reason}"),
+            Self::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl fmt::Debug for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+impl From<io::Error> for CheckError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<GraphIoError> for CheckError {
+    fn from(e: GraphIoError) -> Self {
+        match e {
+            GraphIoError::Io(e) => Self::Io(e),
+            e => Self::Format(e.to_string()),
+        }
+    }
+}
+
+impl From<ParseError> for CheckError {
+    fn from(e: ParseError) -> Self {
+        match e {
+            ParseError::Io(e) => Self::Io(e),
+            e => Self::Format(e.to_string()),
+        }
+    }
+}
+
+/// Shorthand for rejecting a result.
+pub fn invalid<T>(reason: String) -> Result<T, CheckError> {
+    Err(CheckError::Invalid(reason))
+}
+
+/// Validates the result of a benchmark against its input.
+///
+/// The `*_check` binaries read both from files with `check_files`; the
+/// timing binaries call `check` on the result they hold in memory.
+pub trait Checker {
+    type Input;
+    type Output;
+
+    fn read_input(&self, fname: &str) -> Result<Self::Input, CheckError>;
+
+    fn read_output(&self, fname: &str) -> Result<Self::Output, CheckError>;
+
+    fn check(&self, inp: &Self::Input, out: &Self::Output) -> Result<(), CheckError>;
+
+    fn check_files(&self, ifname: &str, ofname: &str) -> Result<(), CheckError> {
+        let inp = self.read_input(ifname)?;
+        let out = self.read_output(ofname)?;
+        self.check(&inp, &out)
+    }
+}
+
+/// Prints the verdict of a check and returns whether it passed.
+pub fn print_verdict(res: Result<(), CheckError>) -> bool {
+    match res {
+        Ok(()) => {
+            println!("OK");
+            true
+        }
+        Err(e) => {
+            eprintln!("ERR: {e}");
+            false
+        }
+    }
+}
+
+/// Prints the verdict of a check and exits with status 1 if it failed.
+pub fn exit_on_error(res: Result<(), CheckError>) {
+    if !print_verdict(res) {
+        std::process::exit(1);
+    }
+}
@@ -32,21 +32,17 @@ use std::fmt::{Debug, Display, LowerExp};
 use std::str::FromStr;
 
 use crate::common::geometry::*;
-use crate::common::io::read_file_to_vec;
+use crate::common::io::{read_file_to_vec, ParseError, ReadOptions};
 
 const HEADER_TRI: &str = "pbbs_triangles";
 
-pub fn read_points_from_file<const D: usize, T>(fname: &str) -> Vec<Point<D, T>>
+pub fn read_points_from_file<const D: usize, T>(fname: &str) -> Result<Vec<Point<D, T>>, ParseError>
 where
     T: Float + FromStr + Send,
     <T as std::str::FromStr>::Err: Debug,
 {
-    read_file_to_vec(
-        fname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], format!("pbbs_sequencePoint{}d", D)),
-        },
-    )
+    let header = format!("pbbs_sequencePoint{}d", D);
+    read_file_to_vec(fname, &ReadOptions::with_header(&header))
 }
 
 pub fn read_triangles_from_file<P>(fname: &str, offset: usize) -> Triangles<P>
@@ -186,91 +186,50 @@ impl Index<usize> for WghEdgeArray {
 //    ADJACENCY ARRAY REPRESENTATION
 // **************************************************************
 
-pub struct Vertex<'a> {
-    pub neighbors: &'a [DefInt],
-    pub degree: usize,
-}
+pub type Vertex<'a> = rpb_graph::Vertex<'a, DefInt>;
 
-impl<'a> Vertex<'a> {
-    pub fn new(n: &'a [DefInt], d: usize) -> Self {
-        Self {
-            neighbors: n,
-            degree: d,
-        }
-    }
-}
+pub type Graph = rpb_graph::Graph<DefInt>;
 
-impl<'a> Default for Vertex<'a> {
-    fn default() -> Self {
-        Self {
-            neighbors: &[],
-            degree: 0,
-        }
+pub type CompressedGraph = rpb_graph::CompressedGraph<DefInt>;
+
+impl From<&EdgeArray> for Graph {
+    fn from(ea: &EdgeArray) -> Self {
+        let es = ea.es.par_iter().map(|e| (e.u, e.v)).collect();
+        Graph::from_edges(ea.num_rows.max(ea.num_cols), es)
     }
 }
 
-pub struct Graph {
-    pub offsets: Vec<DefInt>,
-    pub edges: Vec<DefInt>,
-    pub degrees: Vec<DefInt>,
-    pub n: usize,
-    pub m: usize,
+impl From<&Graph> for EdgeArray {
+    fn from(g: &Graph) -> Self {
+        let es = g
+            .to_edges()
+            .into_par_iter()
+            .map(|(u, v)| Edge::new(u, v))
+            .collect();
+        EdgeArray::new(es, g.n, g.n)
+    }
 }
 
-impl Graph {
-    pub const fn num_vertices(&self) -> usize {
-        self.n
-    }
+// **************************************************************
+//    WEIGHTED ADJACENCY ARRAY REPRESENTATION
+// **************************************************************
 
-    pub fn num_edges(&self) -> usize {
-        if self.degrees.len() == 0 {
-            self.m
-        } else {
-            todo!("not yet implemented!");
-        }
-    }
+pub type WghGraph<W = DefFloat> = rpb_graph::WghGraph<DefInt, W>;
 
-    pub const fn get_offsets(&self) -> &Vec<DefInt> {
-        &self.offsets
+impl From<&WghEdgeArray> for WghGraph {
+    fn from(ea: &WghEdgeArray) -> Self {
+        let es = ea.es.par_iter().map(|e| (e.u, e.v, e.w)).collect();
+        WghGraph::from_edges(ea.n, es)
     }
+}
 
-    pub fn add_degrees(&mut self) {
-        debug_assert!(self.degrees.len() == 0);
-        self.degrees = (0..self.n)
+impl From<&WghGraph> for WghEdgeArray {
+    fn from(g: &WghGraph) -> Self {
+        let es = g
+            .to_edges()
             .into_par_iter()
-            .map(|i| self.offsets[i + 1] - self.offsets[i])
+            .map(|(u, v, w)| WghEdge::new(u, v, w))
             .collect();
-    }
-
-    pub fn new(offsets: &[DefInt], edges: &[DefInt], n: usize) -> Self {
-        debug_assert_eq!(n + 1, offsets.len());
-        debug_assert_eq!(edges.len(), offsets[n] as usize);
-
-        Self {
-            offsets: offsets.to_vec(),
-            edges: edges.to_vec(),
-            n: n,
-            m: edges.len(),
-            degrees: vec![],
-        }
-    }
-
-    #[inline(always)]
-    #[allow(mismatched_lifetime_syntaxes)]
-    pub fn index(&self, i: usize) -> Vertex {
-        debug_assert!(i < self.n);
-
-        let (of, of_next) = (self.offsets[i] as usize, self.offsets[i + 1] as usize);
-
-        let d = match self.degrees.len() {
-            0 => of_next - of,
-            _ => self.degrees[i] as usize,
-        };
-        let n = &self.edges[of..of_next];
-
-        Vertex {
-            neighbors: n,
-            degree: d,
-        }
+        WghEdgeArray::new(es, g.n)
     }
 }
@@ -26,102 +26,84 @@ use std::cmp::max;
 // ============================================================================
 
 use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::io::{BufRead, BufReader};
 
 use rayon::prelude::*;
 
 use super::graph::*;
-use super::io::{read_big_file_to_vec, read_file_to_vec};
-use crate::DefInt;
+use crate::{DefFloat, DefInt};
 use parlay::verbose_println;
-
-const ADJ_GRAPH_HEADER: &str = "AdjacencyGraph";
+use rpb_graph::{
+    import_edge_list, read_edge_array, read_wgh_edge_array, EdgeList, GraphIoError, ImportOptions,
+};
 
 #[allow(unexpected_cfgs)]
-pub fn read_graph_from_file(fname: &str) -> Graph {
-    let file = File::open(&fname).unwrap();
-    let reader = BufReader::new(file);
-    let mut lines = reader.lines();
-
-    verbose_println!("reading file header...");
-    assert_eq!(lines.next().unwrap().unwrap(), ADJ_GRAPH_HEADER);
-    let n = lines.next().unwrap().unwrap().parse().unwrap();
-    let m = lines.next().unwrap().unwrap().parse().unwrap();
-
-    verbose_println!("making the graph (n={n}, m={m})...");
-    let mut g = Graph {
-        offsets: Vec::with_capacity(n + 1),
-        edges: Vec::with_capacity(m),
-        degrees: vec![],
-        n,
-        m,
-    };
-    unsafe {
-        g.offsets.set_len(n + 1);
-        g.edges.set_len(m);
-    }
-
-    verbose_println!("reading offsets...");
-    for i in 0..n {
-        let tt = lines.next().unwrap().unwrap();
-        let ttt = tt.parse();
-        if ttt.is_err() {
-            println!("{i}, {tt}")
-        }
-        g.offsets[i] = ttt.unwrap();
-    }
-    g.offsets[n] = m as DefInt;
-
-    verbose_println!("reading edges...");
-    for i in 0..m {
-        g.edges[i] = lines.next().unwrap().unwrap().parse().unwrap();
-    }
-
-    verbose_println!("graph generated.");
-    g
+pub fn read_graph_from_file(fname: &str) -> Result<Graph, GraphIoError> {
+    verbose_println!("reading the graph...");
+    let g = Graph::read(fname)?;
+    verbose_println!("graph generated (n={}, m={}).", g.n, g.m);
+    Ok(g)
 }
 
 #[allow(unexpected_cfgs)]
-pub fn read_edge_array_from_file(fname: &str) -> EdgeArray {
-    let mut ea = EdgeArray {
-        es: vec![],
-        num_rows: 0,
-        num_cols: 0,
-        non_zeros: 0,
-    };
+pub fn read_wgh_graph_from_file(fname: &str) -> Result<WghGraph, GraphIoError> {
+    verbose_println!("reading the graph...");
+    let g = WghGraph::read(fname)?;
+    verbose_println!("graph generated (n={}, m={}).", g.n, g.m);
+    Ok(g)
+}
+
+pub fn write_graph_to_file(g: &Graph, fname: &str) {
+    g.to_file(fname).expect("cannot write to output");
+}
 
+pub fn write_wgh_graph_to_file(g: &WghGraph, fname: &str) {
+    g.to_file(fname).expect("cannot write to output");
+}
+
+pub fn write_graph_to_bin_file(g: &Graph, fname: &str) {
+    g.to_bin_file(fname).expect("cannot write to output");
+}
+
+pub fn write_wgh_graph_to_bin_file(g: &WghGraph, fname: &str) {
+    g.to_bin_file(fname).expect("cannot write to output");
+}
+
+/// Returns the first line of a file, which names the format of PBBS text
+/// inputs.
+pub fn read_header(fname: &str) -> String {
+    let file = File::open(fname).expect("cannot read input file");
+    let mut header = String::new();
+    BufReader::new(file)
+        .read_line(&mut header)
+        .expect("cannot read input file");
+    header.trim().to_string()
+}
+
+#[allow(unexpected_cfgs)]
+pub fn read_edge_array_from_file(fname: &str) -> Result<EdgeArray, GraphIoError> {
     verbose_println!("reading file...");
-    read_big_file_to_vec(
-        fname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "EdgeArray"),
-        },
-        &mut ea.es,
-    );
-    let n = ea.es.len();
+    let es: Vec<Edge> = read_edge_array(fname)?
+        .into_par_iter()
+        .map(|(u, v)| Edge::new(u, v))
+        .collect();
 
     verbose_println!("finding_max...");
-    let max = (&ea.es).into_par_iter().cloned().reduce(
+    let max = es.par_iter().cloned().reduce(
         || Edge::new(0, 0),
         |a, b| Edge::new(a.u.max(b.u), a.v.max(b.v)),
     );
     let rm = max.u.max(max.v) as usize + 1;
 
-    ea.non_zeros = n;
-    ea.num_rows = rm;
-    ea.num_cols = rm;
-
     verbose_println!("done.");
-    ea
+    Ok(EdgeArray::new(es, rm, rm))
 }
 
-pub fn read_wgh_edge_array_from_file(fname: &str) -> WghEdgeArray {
-    let es: Vec<WghEdge> = read_file_to_vec(
-        fname,
-        Some {
-            0: |w: &[&str]| debug_assert_eq!(w[0], "WeightedEdgeArray"),
-        },
-    );
+pub fn read_wgh_edge_array_from_file(fname: &str) -> Result<WghEdgeArray, GraphIoError> {
+    let es: Vec<WghEdge> = read_wgh_edge_array(fname)?
+        .into_par_iter()
+        .map(|(u, v, w)| WghEdge::new(u, v, w))
+        .collect();
 
     let m = es.par_iter().cloned().reduce(
         || WghEdge::new(0, 0, 0.0),
@@ -134,5 +116,56 @@ pub fn read_wgh_edge_array_from_file(fname: &str) -> WghEdgeArray {
         es.len()
     );
 
-    WghEdgeArray::new(es, max(m.u, m.v) as usize + 1)
+    Ok(WghEdgeArray::new(es, max(m.u, m.v) as usize + 1))
+}
+
+// **************************************************************
+//    SNAP AND MATRIXMARKET IMPORTERS
+// **************************************************************
+
+#[allow(unexpected_cfgs)]
+fn import(
+    fname: &str,
+    options: &ImportOptions,
+) -> Result<EdgeList<DefInt, DefFloat>, GraphIoError> {
+    verbose_println!("importing {fname}...");
+    let es = import_edge_list(fname, options)?;
+    verbose_println!("imported n={} m={}.", es.n, es.edges.len());
+    Ok(es)
+}
+
+/// Imports a SNAP edge list or a MatrixMarket file as an edge array.
+pub fn import_edge_array_from_file(
+    fname: &str,
+    options: &ImportOptions,
+) -> Result<EdgeArray, GraphIoError> {
+    let es = import(fname, options)?;
+    let n = es.n;
+    let es = es
+        .edges
+        .into_par_iter()
+        .map(|(u, v, _)| Edge::new(u, v))
+        .collect();
+    Ok(EdgeArray::new(es, n, n))
+}
+
+/// Imports a SNAP edge list or a MatrixMarket file as a weighted edge array.
+/// The edges of an unweighted input weigh one.
+pub fn import_wgh_edge_array_from_file(
+    fname: &str,
+    options: &ImportOptions,
+) -> Result<WghEdgeArray, GraphIoError> {
+    let es = import(fname, options)?;
+    let n = es.n;
+    let es = es
+        .edges
+        .into_par_iter()
+        .map(|(u, v, w)| WghEdge::new(u, v, w))
+        .collect();
+    Ok(WghEdgeArray::new(es, n))
+}
+
+/// Imports a SNAP edge list or a MatrixMarket file as a graph.
+pub fn import_graph_from_file(fname: &str, options: &ImportOptions) -> Result<Graph, GraphIoError> {
+    Ok(import(fname, options)?.to_graph())
 }
@@ -26,10 +26,116 @@ use std::{fs, io, io::prelude::*};
 // ============================================================================
 
 use rayon::prelude::*;
+use std::fmt;
+
+/// The header of a file of integers.
+pub const SEQ_INT_HEADER: &str = "sequenceInt";
+
+/// How to read a file with one value per line. Blank lines may only end
+/// the file.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReadOptions<'a> {
+    /// the first line of the file, if it has one.
+    pub header: Option<&'a str>,
+    /// skip the lines that do not parse, the header included, instead of
+    /// failing.
+    pub lenient: bool,
+}
+
+impl<'a> ReadOptions<'a> {
+    pub fn with_header(header: &'a str) -> Self {
+        Self {
+            header: Some(header),
+            lenient: false,
+        }
+    }
+
+    /// Returns the index of the first line after the header.
+    fn check_header(&self, lines: &[(usize, &str)]) -> Result<usize, ParseError> {
+        match self.header {
+            None => Ok(0),
+            Some(_) if self.lenient => Ok(0),
+            Some(h) => match lines.first() {
+                Some((_, l)) if l.trim() == h => Ok(1),
+                l => Err(ParseError::Header {
+                    expected: h.to_string(),
+                    found: l.map_or("", |l| l.1).trim().to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// An error while reading a file with one value per line. Line numbers start
+/// at one.
+pub enum ParseError {
+    Io(io::Error),
+    /// the file does not start with the expected header.
+    Header {
+        expected: String,
+        found: String,
+    },
+    /// a line is not a valid value.
+    Line {
+        line: usize,
+        token: String,
+    },
+}
+
+impl ParseError {
+    fn line(index: usize, token: &str) -> Self {
+        Self::Line {
+            line: index + 1,
+            token: token.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Header { expected, found } => {
+                write!(f, "line 1: expected header {expected:?}, found {found:?}")
+            }
+            Self::Line { line, token } => write!(f, "line {line}: can not parse {token:?}"),
+        }
+    }
+}
+
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// The lines of `body` before its trailing blank lines.
+fn without_trailing_blanks<'a, 'b>(body: &'b [(usize, &'a str)]) -> &'b [(usize, &'a str)] {
+    let end = body
+        .iter()
+        .rposition(|(_, l)| !l.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    &body[..end]
+}
+
+/// Parses the `i`-th line, which must not be blank.
+fn parse_line<T: std::str::FromStr>(&(i, l): &(usize, &str)) -> Result<T, ParseError> {
+    match l.trim() {
+        "" => Err(ParseError::line(i, l)),
+        t => t.parse().map_err(|_| ParseError::line(i, l)),
+    }
+}
 
-#[allow(dead_code)]
 #[inline(always)]
-pub(crate) fn fmt_f64(num: f64, precision: usize, exp_pad: usize) -> String {
+pub fn fmt_f64(num: f64, precision: usize, exp_pad: usize) -> String {
     let mut num = format!("{:.precision$e}", num, precision = precision);
     let exp = num.split_off(num.find('e').unwrap());
 
@@ -43,8 +149,7 @@ pub(crate) fn fmt_f64(num: f64, precision: usize, exp_pad: usize) -> String {
     num
 }
 
-#[allow(dead_code)]
-pub(crate) fn write_slice_to_file_seq<T, F>(s: &[T], of: F)
+pub fn write_slice_to_file_seq<T, F>(s: &[T], of: F)
 where
     T: std::string::ToString,
     F: AsRef<std::path::Path>,
@@ -53,64 +158,75 @@ where
     fs::write(of, s.join("\n")).expect("cannot write to output");
 }
 
-#[allow(dead_code)]
-pub(crate) fn read_file_to_vec_seq<T, P>(fname: P) -> Vec<T>
+pub fn read_file_to_vec_seq<T, P>(fname: P, options: &ReadOptions) -> Result<Vec<T>, ParseError>
 where
     T: std::str::FromStr,
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
     P: AsRef<std::path::Path>,
 {
-    let s = fs::read_to_string(fname).expect("cannot read input file");
-    let w: Vec<_> = s.split('\n').collect();
-    w.into_iter()
-        .map(str::parse)
-        .filter(Result::is_ok)
-        .map(Result::unwrap)
-        .collect::<Vec<T>>()
+    let s = fs::read_to_string(fname)?;
+    let lines: Vec<_> = s.split('\n').enumerate().collect();
+    let body = &lines[options.check_header(&lines)?..];
+    if options.lenient {
+        return Ok(body
+            .iter()
+            .filter_map(|(_, l)| l.trim().parse().ok())
+            .collect());
+    }
+    without_trailing_blanks(body)
+        .iter()
+        .map(parse_line)
+        .collect()
 }
 
-#[allow(dead_code)]
-pub(crate) fn read_file_to_vec<T, P, F>(fname: P, debug_assert: Option<F>) -> Vec<T>
+pub fn read_file_to_vec<T, P>(fname: P, options: &ReadOptions) -> Result<Vec<T>, ParseError>
 where
     T: std::str::FromStr + Send,
-    <T as std::str::FromStr>::Err: std::fmt::Debug + Send,
     P: AsRef<std::path::Path>,
-    F: Fn(&[&str]),
 {
-    let s = fs::read_to_string(fname).expect("cannot read input file");
-    let w: Vec<_> = s.par_split('\n').collect();
-    if debug_assert.is_some() {
-        debug_assert.unwrap()(&w);
-    }
-    w.into_par_iter()
-        .map(str::parse)
-        .filter(Result::is_ok)
-        .map(Result::unwrap)
-        .collect::<Vec<T>>()
+    let s = fs::read_to_string(fname)?;
+    parse_lines(&s, options)
 }
 
-#[allow(dead_code)]
-pub(crate) fn read_big_file_to_vec<T, P, F>(fname: P, debug_assert: Option<F>, dest: &mut Vec<T>)
+pub fn read_big_file_to_vec<T, P>(
+    fname: P,
+    options: &ReadOptions,
+    dest: &mut Vec<T>,
+) -> Result<(), ParseError>
 where
     T: std::str::FromStr + Send,
-    <T as std::str::FromStr>::Err: std::fmt::Debug + Send,
     P: AsRef<std::path::Path>,
-    F: Fn(&[&str]),
 {
-    if debug_assert.is_some() {
-        eprintln!("debug_assert is not supported for read_big_file_to_vec");
+    *dest = parse_lines(&fs::read_to_string(fname)?, options)?;
+    Ok(())
+}
+
+/// Parses one value per line in parallel. On failure, reports the first bad
+/// line.
+fn parse_lines<T>(s: &str, options: &ReadOptions) -> Result<Vec<T>, ParseError>
+where
+    T: std::str::FromStr + Send,
+{
+    let lines: Vec<&str> = s.par_split('\n').collect();
+    let lines: Vec<_> = lines.into_par_iter().enumerate().collect();
+    let body = &lines[options.check_header(&lines)?..];
+    if options.lenient {
+        return Ok(body
+            .par_iter()
+            .filter_map(|(_, l)| l.trim().parse().ok())
+            .collect());
     }
-    *dest = fs::read_to_string(fname)
-        .expect("cannot read input file")
-        .par_split('\n')
-        .map(str::parse)
-        .filter(Result::is_ok)
-        .map(Result::unwrap)
-        .collect::<Vec<T>>();
+    let body = without_trailing_blanks(body);
+    body.par_iter()
+        .map(|l| parse_line(l).ok())
+        .collect::<Option<Vec<T>>>()
+        .ok_or_else(|| {
+            body.par_iter()
+                .find_map_first(|l| parse_line::<T>(l).err())
+                .unwrap()
+        })
 }
 
-#[allow(dead_code)]
-pub(crate) fn chars_from_file<P: AsRef<std::path::Path>>(
+pub fn chars_from_file<P: AsRef<std::path::Path>>(
     fname: P,
     null_terminate: bool,
 ) -> io::Result<Vec<u8>> {
@@ -123,9 +239,72 @@ pub(crate) fn chars_from_file<P: AsRef<std::path::Path>>(
     Ok(buffer)
 }
 
-#[allow(dead_code)]
-pub(crate) fn chars_to_file<P: AsRef<std::path::Path>>(buffer: &[u8], fname: P) -> io::Result<()> {
+pub fn chars_to_file<P: AsRef<std::path::Path>>(buffer: &[u8], fname: P) -> io::Result<()> {
     let mut f = fs::File::create(fname)?;
     f.write_all(buffer)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod io_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reads `contents` with all three readers, which must agree.
+    fn read(contents: &str, options: &ReadOptions) -> Result<Vec<u32>, String> {
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+        let id = FILES.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("pbbs_io_{}_{id}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        let seq = read_file_to_vec_seq(&path, options).map_err(|e| e.to_string());
+        let par = read_file_to_vec(&path, options).map_err(|e| e.to_string());
+        let mut big = vec![];
+        let big = read_big_file_to_vec(&path, options, &mut big)
+            .map(|()| big)
+            .map_err(|e| e.to_string());
+        fs::remove_file(path).unwrap();
+        assert_eq!(seq, par);
+        assert_eq!(seq, big);
+        seq
+    }
+
+    #[test]
+    fn strict() {
+        let header = ReadOptions::with_header(SEQ_INT_HEADER);
+        assert_eq!(read("sequenceInt\n1\n 2 \n3", &header), Ok(vec![1, 2, 3]));
+        assert_eq!(read("sequenceInt\n1\n2\n\n\n", &header), Ok(vec![1, 2]));
+        assert_eq!(read("1\n2\n", &ReadOptions::default()), Ok(vec![1, 2]));
+        assert_eq!(read("", &ReadOptions::default()), Ok(vec![]));
+    }
+
+    #[test]
+    fn errors() {
+        let header = ReadOptions::with_header(SEQ_INT_HEADER);
+        assert_eq!(
+            read("1\n2\n", &header),
+            Err("line 1: expected header \"sequenceInt\", found \"1\"".to_string())
+        );
+        assert_eq!(
+            read("sequenceInt\n1\nx\n3\ny\n", &header),
+            Err("line 3: can not parse \"x\"".to_string())
+        );
+        assert_eq!(
+            read("sequenceInt\n1\n\n3\n", &header),
+            Err("line 3: can not parse \"\"".to_string())
+        );
+        assert_eq!(
+            read("1\n-2\n", &ReadOptions::default()),
+            Err("line 2: can not parse \"-2\"".to_string())
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let options = ReadOptions {
+            header: Some(SEQ_INT_HEADER),
+            lenient: true,
+        };
+        assert_eq!(read("sequenceInt\n1\nx\n\n3\n", &options), Ok(vec![1, 3]));
+        assert_eq!(read("1\n2\n", &options), Ok(vec![1, 2]));
+    }
+}
@@ -1,4 +1,4 @@
-pub(crate) mod io;
+pub mod io;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,12 +25,15 @@ pub(crate) mod io;
 // SOFTWARE.
 // ============================================================================
 
-pub(crate) mod atomics;
-pub(crate) mod geometry;
-pub(crate) mod geometry_io;
-pub(crate) mod graph;
-pub(crate) mod graph_io;
-pub(crate) mod spec_for;
-pub(crate) mod time_loop;
-pub(crate) mod topology;
-pub(crate) mod topology_from_triangles;
+pub mod atomics;
+pub mod check;
+pub mod geometry;
+pub mod geometry_io;
+pub mod graph;
+pub mod graph_io;
+pub mod shared;
+pub mod spec_for;
+pub mod threads;
+pub mod time_loop;
+pub mod topology;
+pub mod topology_from_triangles;
@@ -0,0 +1,158 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
+
+use crate::ORDER;
+
+/// A value `AtomicArray` can store as an atomic.
+pub trait Atom: Copy + Send + Sync {
+    type Atomic: Send + Sync;
+
+    fn atomic(v: Self) -> Self::Atomic;
+    fn load(a: &Self::Atomic) -> Self;
+    fn store(a: &Self::Atomic, v: Self);
+}
+
+macro_rules! atom {
+    ($t: ty, $a: ty) => {
+        impl Atom for $t {
+            type Atomic = $a;
+
+            fn atomic(v: Self) -> $a {
+                <$a>::new(v)
+            }
+            fn load(a: &$a) -> Self {
+                a.load(ORDER)
+            }
+            fn store(a: &$a, v: Self) {
+                a.store(v, ORDER)
+            }
+        }
+    };
+}
+
+atom!(bool, AtomicBool);
+atom!(u8, AtomicU8);
+atom!(i32, AtomicI32);
+atom!(u32, AtomicU32);
+atom!(u64, AtomicU64);
+atom!(usize, AtomicUsize);
+
+/// An array that parallel tasks read and write at the same time. An algorithm
+/// generic over it builds both its safe and its unsafe variant from one source.
+///
+/// # Safety
+///
+/// `get` and `set` take `&self`, so an algorithm calls them from many tasks
+/// at once, on the same elements. An implementation answers for what
+/// overlapping calls do: `AtomicArray` makes them atomic, while `RawArray`
+/// lets them race, which is the point of the unsafe variants.
+pub unsafe trait SharedArray<T>: Sync {
+    /// Whether overlapping accesses are synchronized.
+    const SYNCHRONIZED: bool;
+
+    fn new(n: usize, v: T) -> Self;
+    fn from_vec(v: Vec<T>) -> Self;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn get(&self, i: usize) -> T;
+    fn set(&self, i: usize, v: T);
+    fn into_vec(self) -> Vec<T>;
+}
+
+/// The safe variant: every element is an atomic.
+pub struct AtomicArray<T: Atom>(Vec<T::Atomic>);
+
+// SAFETY: every access is a single atomic load or store.
+unsafe impl<T: Atom> SharedArray<T> for AtomicArray<T> {
+    const SYNCHRONIZED: bool = true;
+
+    fn new(n: usize, v: T) -> Self {
+        Self((0..n).into_par_iter().map(|_| T::atomic(v)).collect())
+    }
+    fn from_vec(v: Vec<T>) -> Self {
+        Self(v.into_par_iter().map(T::atomic).collect())
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn get(&self, i: usize) -> T {
+        T::load(&self.0[i])
+    }
+    fn set(&self, i: usize, v: T) {
+        T::store(&self.0[i], v)
+    }
+    fn into_vec(self) -> Vec<T> {
+        self.0.par_iter().map(T::load).collect()
+    }
+}
+
+/// The unsafe variant: plain reads and writes, racing with those of other
+/// tasks. It stays private to the crate, so only its own algorithms race.
+pub(crate) struct RawArray<T>(Vec<UnsafeCell<T>>);
+
+// SAFETY: this is only sound while no task writes an element that another
+// task reads or writes. The unsafe variants break that on purpose, like the
+// C++ PBBS does, to measure what the atomics of `AtomicArray` cost.
+unsafe impl<T: Send> Sync for RawArray<T> {}
+
+// SAFETY: see the `Sync` impl above; the accesses are plain and may race.
+unsafe impl<T: Copy + Send + Sync> SharedArray<T> for RawArray<T> {
+    const SYNCHRONIZED: bool = false;
+
+    fn new(n: usize, v: T) -> Self {
+        Self((0..n).into_par_iter().map(|_| UnsafeCell::new(v)).collect())
+    }
+    fn from_vec(v: Vec<T>) -> Self {
+        Self(v.into_par_iter().map(UnsafeCell::new).collect())
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn get(&self, i: usize) -> T {
+        // SAFETY: the index is checked; the read may race with a `set`.
+        unsafe { *self.0[i].get() }
+    }
+    fn set(&self, i: usize, v: T) {
+        // SAFETY: the index is checked; the write may race with a `get` or a
+        // `set` of another task.
+        unsafe { *self.0[i].get() = v }
+    }
+    fn into_vec(self) -> Vec<T> {
+        self.0.into_par_iter().map(UnsafeCell::into_inner).collect()
+    }
+}
+
+/// The variant the `AW_safe` feature selects.
+#[cfg(feature = "AW_safe")]
+pub(crate) type DefaultArray<T> = AtomicArray<T>;
+#[cfg(not(feature = "AW_safe"))]
+pub(crate) type DefaultArray<T> = RawArray<T>;
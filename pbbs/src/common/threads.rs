@@ -0,0 +1,289 @@
+use std::fmt;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::str::FromStr;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::common::time_loop::Samples;
+
+/// Where to pin the threads of the benchmark's pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Pin {
+    /// let the OS schedule the threads.
+    None,
+    /// thread `i` on core `i`.
+    Compact,
+    /// spread the threads evenly over all the cores.
+    Scatter,
+    /// thread `i` on the `i`-th core of the list (cycling).
+    List(Vec<usize>),
+}
+
+impl Pin {
+    /// Returns the core of thread `i` of `n`, if it is pinned.
+    pub fn core(&self, i: usize, n: usize) -> Option<usize> {
+        self.core_of(i, n, affinity::get_core_num())
+    }
+
+    /// Returns the core of thread `i` of `n` on a machine with `cores` cores.
+    fn core_of(&self, i: usize, n: usize, cores: usize) -> Option<usize> {
+        match self {
+            Pin::None => None,
+            Pin::Compact => Some(i % cores),
+            Pin::Scatter => Some(i * cores / n.clamp(1, cores) % cores),
+            Pin::List(l) => Some(l[i % l.len()]),
+        }
+    }
+}
+
+/// Parses `none`, `compact`, `scatter` or a list of cores such as
+/// `0,2,4-7`.
+impl FromStr for Pin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => return Ok(Pin::None),
+            "compact" => return Ok(Pin::Compact),
+            "scatter" => return Ok(Pin::Scatter),
+            _ => {}
+        }
+        let err = || format!("expected none, compact, scatter or a list of cores, found `{s}`");
+        let mut cores = vec![];
+        for part in s.split(',') {
+            match part.split_once('-') {
+                Some((a, b)) => {
+                    let a: usize = a.trim().parse().map_err(|_| err())?;
+                    let b: usize = b.trim().parse().map_err(|_| err())?;
+                    if a > b {
+                        return Err(err());
+                    }
+                    cores.extend(a..=b);
+                }
+                None => cores.push(part.trim().parse().map_err(|_| err())?),
+            }
+        }
+        Ok(Pin::List(cores))
+    }
+}
+
+impl fmt::Display for Pin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pin::None => write!(f, "none"),
+            Pin::Compact => write!(f, "compact"),
+            Pin::Scatter => write!(f, "scatter"),
+            Pin::List(l) => {
+                let l: Vec<_> = l.iter().map(usize::to_string).collect();
+                write!(f, "{}", l.join(","))
+            }
+        }
+    }
+}
+
+/// The thread pool a benchmark runs in.
+#[derive(clap::Args, Clone, Debug)]
+// otherwise clap shows the doc comment as the about of every binary.
+#[command(about = None, long_about = None)]
+pub struct ThreadOptions {
+    /// the number of threads (0 uses RAYON_NUM_THREADS or all the cores)
+    #[clap(short = 't', long, value_parser, required = false, default_value_t = 0)]
+    pub threads: usize,
+
+    /// pin the threads: none, compact, scatter or a list of cores (e.g. 0,2,4-7)
+    #[clap(long, value_parser, required = false, default_value_t = Pin::Compact)]
+    pub pin: Pin,
+
+    /// time the benchmark on each of these thread counts (e.g. 1,2,4) and
+    /// print the speedups
+    #[clap(long, value_delimiter = ',', value_parser = sweep_count, required = false)]
+    pub sweep_threads: Vec<usize>,
+}
+
+/// Parses a thread count of `--sweep-threads`. Unlike `--threads`, 0 is not
+/// all the cores, as a sweep reports the counts it ran on.
+fn sweep_count(s: &str) -> Result<usize, String> {
+    match s.trim().parse() {
+        Ok(0) => Err("a sweep can not run on 0 threads".to_string()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(format!("{e}")),
+    }
+}
+
+impl Default for ThreadOptions {
+    fn default() -> Self {
+        Self {
+            threads: 0,
+            pin: Pin::Compact,
+            sweep_threads: vec![],
+        }
+    }
+}
+
+impl ThreadOptions {
+    /// Returns the number of threads of the pool.
+    pub fn num_threads(&self) -> usize {
+        match self.threads {
+            0 => rayon::current_num_threads(),
+            n => n,
+        }
+    }
+
+    /// Builds a pool of `num_threads` threads, pinned as asked.
+    pub fn build_pool(&self) -> ThreadPool {
+        let n = self.num_threads();
+        let pin = self.pin.clone();
+        ThreadPoolBuilder::new()
+            .num_threads(n)
+            .start_handler(move |i| {
+                if let Some(core) = pin.core(i, n) {
+                    if let Err(e) = affinity::set_thread_affinity([core]) {
+                        eprintln!("Warning: can not pin thread {i} to core {core}: {e}");
+                    }
+                }
+            })
+            .build()
+            .expect("cannot build the thread pool")
+    }
+}
+
+/// The time of an algorithm on one thread count of a sweep.
+#[derive(Clone, Debug)]
+pub struct SweepPoint {
+    pub threads: usize,
+    pub samples: Samples,
+    /// the speedup over `alg` on one thread.
+    pub self_speedup: f64,
+    /// the speedup over the serial algorithm on one thread, if there is one.
+    pub serial_speedup: Option<f64>,
+}
+
+/// Times `alg` with `run` in a fresh pool of each of `counts` threads, and
+/// `serial` (if any) on one thread as the baseline. Prints the speedups.
+pub fn sweep_threads<A, F>(
+    counts: &[usize],
+    pin: &Pin,
+    alg: A,
+    serial: Option<A>,
+    run: F,
+) -> Vec<SweepPoint>
+where
+    A: Copy + Send,
+    F: Fn(A) -> Samples + Sync,
+{
+    let run = &run;
+    let time = |alg: A, threads: usize| {
+        let opts = ThreadOptions {
+            threads,
+            pin: pin.clone(),
+            ..ThreadOptions::default()
+        };
+        opts.build_pool().install(move || run(alg))
+    };
+
+    let serial = serial.map(|s| time(s, 1).mean().as_secs_f64());
+    let points: Vec<_> = counts
+        .iter()
+        .map(|&threads| (threads, time(alg, threads)))
+        .collect();
+    let one = match points.iter().find(|(t, _)| *t == 1) {
+        Some((_, s)) => s.mean(),
+        None => time(alg, 1).mean(),
+    }
+    .as_secs_f64();
+
+    println!("threads\tmean\tself-speedup\tserial-speedup");
+    points
+        .into_iter()
+        .map(|(threads, samples)| {
+            let mean = samples.mean().as_secs_f64();
+            let p = SweepPoint {
+                threads,
+                self_speedup: one / mean,
+                serial_speedup: serial.map(|s| s / mean),
+                samples,
+            };
+            let serial = p
+                .serial_speedup
+                .map_or("-".to_string(), |s| format!("{s:.2}"));
+            println!("{threads}\t{mean:.6}\t{:.2}\t{serial}", p.self_speedup);
+            p
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod threads_tests {
+    use super::*;
+
+    #[test]
+    fn parse_pin() {
+        assert_eq!("none".parse(), Ok(Pin::None));
+        assert_eq!("compact".parse(), Ok(Pin::Compact));
+        assert_eq!("scatter".parse(), Ok(Pin::Scatter));
+        assert_eq!("0,2,4-7".parse(), Ok(Pin::List(vec![0, 2, 4, 5, 6, 7])));
+        assert_eq!(" 1 , 3 ".parse(), Ok(Pin::List(vec![1, 3])));
+        assert_eq!("5-5".parse(), Ok(Pin::List(vec![5])));
+        for bad in ["7-4", "", "1,,2", "1,", "4-", "-4", "a", "1-2-3"] {
+            assert!(bad.parse::<Pin>().is_err(), "{bad}");
+        }
+        let pin: Pin = "0,2,4-7".parse().unwrap();
+        assert_eq!(pin.to_string().parse(), Ok(pin));
+    }
+
+    #[test]
+    fn parse_sweep() {
+        #[derive(clap::Parser)]
+        struct Args {
+            #[command(flatten)]
+            threads: ThreadOptions,
+        }
+        let parse = |args: &[&str]| {
+            <Args as clap::Parser>::try_parse_from([&["bench"], args].concat())
+                .map(|a| a.threads.sweep_threads)
+        };
+        assert_eq!(parse(&["--sweep-threads", "1,2,4"]).unwrap(), [1, 2, 4]);
+        assert!(parse(&["--sweep-threads", "1,0"]).is_err());
+        assert!(parse(&["--sweep-threads", "x"]).is_err());
+        assert!(parse(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn core() {
+        let cores = |pin: Pin, n: usize| -> Vec<_> {
+            (0..n).map(|i| pin.core_of(i, n, 8).unwrap()).collect()
+        };
+        assert_eq!(Pin::None.core_of(0, 1, 8), None);
+        assert_eq!(cores(Pin::Compact, 10), [0, 1, 2, 3, 4, 5, 6, 7, 0, 1]);
+        assert_eq!(cores(Pin::Scatter, 4), [0, 2, 4, 6]);
+        assert_eq!(cores(Pin::Scatter, 3), [0, 2, 5]);
+        // with more threads than cores, the cores are shared evenly.
+        assert_eq!(cores(Pin::Scatter, 10), [0, 1, 2, 3, 4, 5, 6, 7, 0, 1]);
+        assert_eq!(cores(Pin::List(vec![3, 5]), 5), [3, 5, 3, 5, 3]);
+    }
+}
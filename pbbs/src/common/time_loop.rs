@@ -1,4 +1,4 @@
-use std::time::Duration;
+use parlay::Timer;
 // ============================================================================
 // This code is part of RPB.
 // ----------------------------------------------------------------------------
@@ -25,19 +25,21 @@ use std::time::Duration;
 // SOFTWARE.
 // ============================================================================
 
-use parlay::Timer;
+pub use rpb_bench::{AllocStats, PerfCounters, Samples, TimeOptions, Warmup};
 
-/// Runs a function `runf` for `r` times and returns the mean time.
+/// Runs a function `runf` and returns the time of each run.
 /// Before each run, `initf` is called and after each run `endf` is called.
-/// `delay` is the minimum time of warm-up.
+/// After the warm-up, it runs at least `opts.rounds` times, and more if
+/// `opts.min_time` or `opts.target_error` ask for it. With the `count_alloc`
+/// feature, it also records what the timed runs of `runf` allocate, and with
+/// the `perf` feature their hardware events on the threads of the pool.
 pub fn time_loop<S, W, T>(
     name: &str,
-    r: usize,
-    delay: Duration,
+    opts: &TimeOptions,
     mut initf: S,
     mut runf: W,
     mut endf: T,
-) -> Duration
+) -> Samples
 where
     S: FnMut(),
     W: FnMut(),
@@ -46,23 +48,48 @@ where
     let mut t = Timer::new(name);
     let mut ot = Timer::new("OutLoopTime");
 
+    let mut warmup_rounds = 0;
     t.start();
-    while t.total_time() < delay {
+    while match opts.warmup {
+        Warmup::Time(d) => t.total_time() < d,
+        Warmup::Rounds(r) => warmup_rounds < r,
+    } {
         initf();
         runf();
         endf();
+        warmup_rounds += 1;
     }
+    let warmup = t.stop();
 
+    let mut samples = Samples {
+        warmup,
+        rounds: Vec::with_capacity(opts.rounds),
+        ..Samples::default()
+    };
+    let mut counters = PerfCounters::rayon_pool();
     ot.start();
-    for _ in 0..r {
+    while !opts.done(&samples) {
         initf();
+        counters.start();
         t.start();
-        runf();
-        t.next("");
+        let alloc = AllocStats::measure(&mut runf);
+        let d = t.stop();
+        let perf = counters.stop();
+        t.report(d, "");
+        samples.record(d, alloc, perf);
         endf();
     }
     ot.stop();
     ot.total();
+    if let Some(a) = samples.alloc_per_round() {
+        println!(
+            "alloc:\t{} allocations, {} bytes, peak {} bytes per round",
+            a.allocations, a.bytes, a.peak_bytes
+        );
+    }
+    if let Some(p) = samples.perf_per_round() {
+        println!("perf:\t{p} per round");
+    }
 
-    t.total_time() / r as u32
+    samples
 }
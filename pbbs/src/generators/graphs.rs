@@ -0,0 +1,134 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use clap::ValueEnum;
+use parlay::random::Random;
+use rpb_graph::{write_edge_array, write_wgh_edge_array, EdgeList, ImportOptions};
+
+use crate::uniform;
+use pbbs::{DefFloat, DefInt};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GraphFormat {
+    /// a symmetric AdjacencyGraph (mis)
+    Adj,
+    /// an EdgeArray with one direction of every edge (mm, sf)
+    Edges,
+    /// a WeightedEdgeArray with weights in [0, 1) (msf)
+    WghEdges,
+}
+
+/// `m` edges of an rMat graph on 2^`log_n` vertices. Every edge picks one of
+/// the four quadrants of the adjacency matrix with probabilities a, b, c and
+/// 1-a-b-c, `log_n` times.
+pub fn rmat(
+    log_n: u32,
+    m: usize,
+    (a, b, c): (f64, f64, f64),
+    rand: &Random,
+) -> Vec<(DefInt, DefInt)> {
+    (0..m as u64)
+        .into_par_iter()
+        .map(|i| {
+            let r = rand.fork(i);
+            let (mut u, mut v) = (0, 0);
+            for l in 0..log_n {
+                let x = uniform(&r, l as u64);
+                let (du, dv) = if x < a {
+                    (0, 0)
+                } else if x < a + b {
+                    (0, 1)
+                } else if x < a + b + c {
+                    (1, 0)
+                } else {
+                    (1, 1)
+                };
+                u = 2 * u + du;
+                v = 2 * v + dv;
+            }
+            (u, v)
+        })
+        .collect()
+}
+
+/// The edges of a `dims`-dimensional torus with `side` vertices per
+/// dimension: every vertex is linked to its next neighbor in each dimension.
+/// Returns the number of vertices too.
+pub fn grid(side: usize, dims: u32) -> (usize, Vec<(DefInt, DefInt)>) {
+    let n = side.pow(dims);
+    let es = (0..n)
+        .into_par_iter()
+        .flat_map_iter(|v| {
+            (0..dims).map(move |d| {
+                let stride = side.pow(d);
+                let coord = v / stride % side;
+                let next = v - coord * stride + (coord + 1) % side * stride;
+                (v as DefInt, next as DefInt)
+            })
+        })
+        .collect();
+    (n, es)
+}
+
+/// Writes the graph without self-loops and parallel edges. The weights of a
+/// weighted output are drawn from `rand`.
+pub fn write_graph(
+    n: usize,
+    es: Vec<(DefInt, DefInt)>,
+    format: GraphFormat,
+    rand: &Random,
+    of: &str,
+) {
+    let options = ImportOptions {
+        symmetrize: matches!(format, GraphFormat::Adj),
+        remove_self_loops: true,
+        remove_duplicates: true,
+        relabel: false,
+    };
+    // parallel edges in opposite directions are duplicates too
+    let es = es
+        .into_par_iter()
+        .map(|(u, v)| (u.min(v), u.max(v), 1.0))
+        .collect();
+    let es = EdgeList::<DefInt, DefFloat>::new(n, es, false, &options).expect("too many vertices");
+
+    match format {
+        GraphFormat::Adj => es.to_graph().to_file(of),
+        GraphFormat::Edges => write_edge_array(of, &es.to_pairs()),
+        GraphFormat::WghEdges => {
+            let rand = rand.fork(u64::MAX);
+            let es: Vec<_> = es
+                .edges
+                .par_iter()
+                .enumerate()
+                .map(|(i, &(u, v, _))| (u, v, uniform(&rand, i as u64) as DefFloat))
+                .collect();
+            write_wgh_edge_array(of, &es)
+        }
+    }
+    .expect("cannot write to output")
+}
@@ -0,0 +1,212 @@
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+mod graphs;
+mod points;
+mod sequences;
+mod text;
+mod triangles;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use parlay::random::Random;
+
+use graphs::GraphFormat;
+use pbbs::common::geometry_io::{write_points_to_file, write_triangles_to_file};
+use pbbs::common::io::SEQ_INT_HEADER;
+use points::PointDist;
+
+/// Returns the `i`th random number of `r` as a float in [0, 1).
+pub(crate) fn uniform(r: &Random, i: u64) -> f64 {
+    (r.ith_rand(i) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SeqDist {
+    /// uniform in [0, range)
+    Random,
+    /// range^(1-u) for a uniform u, so small values are far more common
+    Exponential,
+    /// 0..n with `swaps` random pairs swapped
+    AlmostSorted,
+}
+
+#[derive(Subcommand)]
+enum Input {
+    /// a sequence of integers (sequenceInt) for isort, sort, hist and dedup
+    Seq {
+        /// the length of the sequence
+        #[arg(short)]
+        n: usize,
+        /// the distribution of the values
+        #[arg(short, long, value_enum, default_value = "random")]
+        dist: SeqDist,
+        /// the values are below range (n by default)
+        #[arg(long)]
+        range: Option<u64>,
+        /// the number of swaps of an almost sorted sequence (sqrt(n) by default)
+        #[arg(long)]
+        swaps: Option<usize>,
+    },
+    /// an rMat graph with power-law degrees
+    Rmat {
+        /// the number of vertices, rounded up to a power of two
+        #[arg(short)]
+        n: usize,
+        /// the number of generated edges (10n by default)
+        #[arg(short)]
+        m: Option<usize>,
+        /// the probability of the top-left quadrant
+        #[arg(short, default_value_t = 0.5)]
+        a: f64,
+        /// the probability of the top-right quadrant
+        #[arg(short, default_value_t = 0.1)]
+        b: f64,
+        /// the probability of the bottom-left quadrant
+        #[arg(short, default_value_t = 0.1)]
+        c: f64,
+        /// the output format
+        #[arg(short, long, value_enum, default_value = "adj")]
+        format: GraphFormat,
+    },
+    /// a 2D or 3D torus grid graph
+    Grid {
+        /// the number of vertices, rounded to the closest square or cube
+        #[arg(short)]
+        n: usize,
+        /// the number of dimensions (2 or 3)
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(2..=3))]
+        dims: u32,
+        /// the output format
+        #[arg(short, long, value_enum, default_value = "adj")]
+        format: GraphFormat,
+    },
+    /// 2D or 3D points (pbbs_sequencePoint) for hull and knn
+    Points {
+        /// the number of points
+        #[arg(short)]
+        n: usize,
+        /// the number of dimensions (2 or 3)
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(2..=3))]
+        dims: u32,
+        /// the distribution of the points
+        #[arg(long, value_enum, default_value = "cube")]
+        dist: PointDist,
+    },
+    /// a Delaunay triangulation of jittered grid points (pbbs_triangles) for dr
+    Triangles {
+        /// the number of points, rounded to the closest square
+        #[arg(short)]
+        n: usize,
+    },
+    /// text of words drawn from an English trigram model for wc, index, sa,
+    /// lrs and bw
+    Text {
+        /// the number of words
+        #[arg(short)]
+        n: usize,
+    },
+}
+
+/// Generates the inputs of the benchmarks in the PBBS formats. The same seed
+/// always gives the same file, whatever the number of threads.
+#[derive(Parser)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    input: Input,
+    /// the output filename
+    #[arg(global = true, short, long, default_value = "")]
+    ofname: String,
+    /// the seed of the random numbers
+    #[arg(global = true, short, long, default_value_t = 1)]
+    seed: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.ofname.is_empty() {
+        eprintln!("an output filename is required (-o)");
+        std::process::exit(1);
+    }
+    let rand = Random::new(args.seed);
+    let of = &args.ofname;
+
+    match args.input {
+        Input::Seq {
+            n,
+            dist,
+            range,
+            swaps,
+        } => {
+            let range = range.unwrap_or(n as u64).max(1);
+            if range > u32::MAX as u64 + 1 {
+                eprintln!("the values of a sequenceInt must fit in 32 bits");
+                std::process::exit(1);
+            }
+            let s = match dist {
+                SeqDist::Random => sequences::random(n, range, &rand),
+                SeqDist::Exponential => sequences::exponential(n, range, &rand),
+                SeqDist::AlmostSorted => {
+                    let swaps = swaps.unwrap_or((n as f64).sqrt() as usize);
+                    sequences::almost_sorted(n, swaps, &rand)
+                }
+            };
+            sequences::write_sequence_to_file(SEQ_INT_HEADER, &s, of)
+        }
+        Input::Rmat {
+            n,
+            m,
+            a,
+            b,
+            c,
+            format,
+        } => {
+            if a < 0.0 || b < 0.0 || c < 0.0 || a + b + c > 1.0 {
+                eprintln!("a, b and c must be probabilities that add up to at most 1");
+                std::process::exit(1);
+            }
+            let log_n = n.max(2).next_power_of_two().trailing_zeros();
+            let es = graphs::rmat(log_n, m.unwrap_or(10 * n), (a, b, c), &rand);
+            graphs::write_graph(1 << log_n, es, format, &rand, of)
+        }
+        Input::Grid { n, dims, format } => {
+            let side = (n as f64).powf(1.0 / dims as f64).round().max(1.0) as usize;
+            let (n, es) = graphs::grid(side, dims);
+            graphs::write_graph(n, es, format, &rand, of)
+        }
+        Input::Points { n, dims, dist } => match dims {
+            2 => write_points_to_file(&points::points::<2>(n, dist, &rand), of),
+            _ => write_points_to_file(&points::points::<3>(n, dist, &rand), of),
+        },
+        Input::Triangles { n } => {
+            let side = (n as f64).sqrt().round().max(2.0) as usize;
+            write_triangles_to_file(&triangles::jittered_grid(side, &rand), of)
+        }
+        Input::Text { n } => {
+            std::fs::write(of, text::trigram_words(n, &rand)).expect("cannot write to output")
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use clap::ValueEnum;
+use parlay::random::Random;
+
+use crate::uniform;
+use pbbs::common::geometry::Point;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PointDist {
+    /// uniform in the cube [-1, 1)^d
+    Cube,
+    /// uniform on the surface of the unit sphere
+    Sphere,
+    /// a Kuzmin distribution: a random direction at a distance r from the
+    /// origin with P(r < x) = 1 - 1/sqrt(1 + x^2), so a few points lie very
+    /// far away
+    Kuzmin,
+}
+
+fn in_cube<const D: usize>(rand: &Random, k: u64) -> [f64; D] {
+    std::array::from_fn(|j| 2.0 * uniform(rand, k * D as u64 + j as u64) - 1.0)
+}
+
+/// A uniform direction: the normalized first point in the unit ball.
+fn on_sphere<const D: usize>(rand: &Random) -> [f64; D] {
+    (0..)
+        .map(|k| in_cube::<D>(rand, k))
+        .find_map(|p| {
+            let len = p.iter().map(|x| x * x).sum::<f64>().sqrt();
+            (len > 1e-9 && len <= 1.0).then(|| p.map(|x| x / len))
+        })
+        .unwrap()
+}
+
+pub fn points<const D: usize>(n: usize, dist: PointDist, rand: &Random) -> Vec<Point<D, f64>> {
+    (0..n as u64)
+        .into_par_iter()
+        .map(|i| {
+            let r = rand.fork(i);
+            Point::new(match dist {
+                PointDist::Cube => in_cube(&r, 0),
+                PointDist::Sphere => on_sphere(&r),
+                PointDist::Kuzmin => {
+                    let s = 1.0 - uniform(&r.next(), 0);
+                    let radius = (1.0 / (s * s) - 1.0).sqrt();
+                    on_sphere::<D>(&r).map(|x| x * radius)
+                }
+            })
+        })
+        .collect()
+}
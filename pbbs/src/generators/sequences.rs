@@ -0,0 +1,68 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt::Display;
+
+use parlay::random::Random;
+
+use crate::uniform;
+
+/// `n` integers drawn uniformly from [0, range).
+pub fn random(n: usize, range: u64, rand: &Random) -> Vec<u32> {
+    (0..n)
+        .into_par_iter()
+        .map(|i| (rand.ith_rand(i as u64) % range) as u32)
+        .collect()
+}
+
+/// `n` integers in [0, range) whose logarithm is uniform, like PBBS's
+/// `exptSeq`: about half of them are below sqrt(range).
+pub fn exponential(n: usize, range: u64, rand: &Random) -> Vec<u32> {
+    let (range, lg) = (range as f64, (range as f64).ln());
+    (0..n)
+        .into_par_iter()
+        .map(|i| ((range / (lg * uniform(rand, i as u64)).exp()) as u64 - 1) as u32)
+        .collect()
+}
+
+/// 0..n with `swaps` random pairs of elements swapped.
+pub fn almost_sorted(n: usize, swaps: usize, rand: &Random) -> Vec<u32> {
+    let mut s: Vec<u32> = (0..n as u32).into_par_iter().collect();
+    if n > 0 {
+        for i in 0..swaps as u64 {
+            let a = rand.ith_rand(2 * i) as usize % n;
+            let b = rand.ith_rand(2 * i + 1) as usize % n;
+            s.swap(a, b);
+        }
+    }
+    s
+}
+
+pub fn write_sequence_to_file<T: Display + Sync>(header: &str, s: &[T], of: &str) {
+    let lines = s.par_iter().map(|x| format!("{x}\n")).collect::<String>();
+    std::fs::write(of, format!("{header}\n{lines}")).expect("cannot write to output");
+}
@@ -0,0 +1,113 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::random::Random;
+
+/// The English text the trigram model learns from.
+const CORPUS: &str = "a parallel program splits the work of a problem among many \
+    threads that run at the same time on the cores of a machine and then \
+    combines their results into one answer the speed of such a program \
+    depends on how evenly the work is shared on how often the threads have \
+    to wait for each other and on how well each of them uses the memory \
+    that is close to its core some problems are easy to divide like adding \
+    up the numbers of a long list while others such as finding the shortest \
+    paths of a graph or sorting the words of a large text need careful \
+    thought about the order in which things happen when two threads write \
+    to the same place without any coordination the outcome can change from \
+    one run to the next which makes such bugs hard to find and even harder \
+    to explain a language that checks these rules before the program ever \
+    runs gives the programmer more confidence but it also asks for a little \
+    more effort up front benchmarks help us measure what this effort costs \
+    and whether the safety is worth its price for real workloads";
+
+/// The letters of the model: a space (the end of a word) and `a` to `z`.
+const ALPHABET: usize = 27;
+const MAX_WORD_LEN: usize = 24;
+
+type Counts = Vec<[[u32; ALPHABET]; ALPHABET]>;
+
+fn symbol(c: u8) -> usize {
+    match c {
+        b'a'..=b'z' => (c - b'a') as usize + 1,
+        _ => 0,
+    }
+}
+
+/// How often every letter follows every pair of letters. Every word starts
+/// after two spaces, so the model can generate the words independently.
+fn trigram_counts() -> Counts {
+    let mut counts = vec![[[0; ALPHABET]; ALPHABET]; ALPHABET];
+    for w in CORPUS.split_whitespace() {
+        let mut prev = (0, 0);
+        for s in w.bytes().map(symbol).chain([0]) {
+            counts[prev.0][prev.1][s] += 1;
+            prev = (prev.1, s);
+        }
+    }
+    counts
+}
+
+fn word(counts: &Counts, rand: &Random) -> String {
+    let mut w = String::new();
+    let mut prev = (0, 0);
+    for k in 0..MAX_WORD_LEN as u64 {
+        let next = &counts[prev.0][prev.1];
+        let mut x = rand.ith_rand(k) % next.iter().sum::<u32>() as u64;
+        let s = next
+            .iter()
+            .position(|&c| {
+                let found = x < c as u64;
+                x = x.saturating_sub(c as u64);
+                found
+            })
+            .unwrap();
+        if s == 0 {
+            break;
+        }
+        w.push((b'a' + s as u8 - 1) as char);
+        prev = (prev.1, s);
+    }
+    w
+}
+
+/// `n` words drawn from a trigram model of English, sixteen per line.
+pub fn trigram_words(n: usize, rand: &Random) -> String {
+    let counts = trigram_counts();
+    (0..n as u64)
+        .into_par_iter()
+        .map(|i| {
+            let sep = if i % 16 == 15 || i + 1 == n as u64 {
+                '\n'
+            } else {
+                ' '
+            };
+            let mut w = word(&counts, &rand.fork(i));
+            w.push(sep);
+            w
+        })
+        .collect()
+}
@@ -0,0 +1,70 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::random::Random;
+
+use crate::uniform;
+use pbbs::common::geometry::{in_circle, Point, Tri, Triangles};
+
+/// How far, in cells, a point may move from its grid position. Small enough
+/// for the edges of the grid to stay Delaunay.
+const JITTER: f64 = 0.2;
+
+/// A Delaunay triangulation of `side` x `side` points of the unit square,
+/// each moved randomly from its place on a grid. Every cell of the grid is
+/// split into two counter-clockwise triangles along the diagonal that keeps
+/// the triangulation Delaunay.
+pub fn jittered_grid(side: usize, rand: &Random) -> Triangles<Point<2, f64>> {
+    let cell = 1.0 / side as f64;
+    let p: Vec<_> = (0..side * side)
+        .into_par_iter()
+        .map(|i| {
+            let jitter = |j| (2.0 * uniform(rand, 2 * i as u64 + j) - 1.0) * JITTER;
+            let (x, y) = ((i % side) as f64, (i / side) as f64);
+            Point::new([(x + jitter(0)) * cell, (y + jitter(1)) * cell])
+        })
+        .collect();
+
+    let cells = side - 1;
+    let t: Vec<Tri> = (0..cells * cells)
+        .into_par_iter()
+        .flat_map_iter(|c| {
+            let v = (c / cells * side + c % cells) as i32;
+            let s = side as i32;
+            // the corners counter-clockwise from the bottom left
+            let [a, b, c, d] = [v, v + 1, v + s + 1, v + s];
+            let at = |k: i32| p[k as usize];
+            if in_circle(at(a), at(b), at(c), at(d)) {
+                [[a, b, d], [b, c, d]]
+            } else {
+                [[a, b, c], [a, c, d]]
+            }
+        })
+        .collect();
+
+    Triangles::new(p, t)
+}
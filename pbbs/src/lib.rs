@@ -0,0 +1,51 @@
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+pub mod algorithm;
+pub mod benchmarks;
+pub mod common;
+pub mod misc;
+
+pub use misc::*;
+pub use rpb_bench;
+
+/// The features of this crate that are enabled, for benchmark reports.
+pub fn features() -> Vec<String> {
+    [
+        ("mem_safe", cfg!(feature = "mem_safe")),
+        ("rng_ind_safe", cfg!(feature = "rng_ind_safe")),
+        ("sng_ind_safe", cfg!(feature = "sng_ind_safe")),
+        ("sng_ind_unsafe", cfg!(feature = "sng_ind_unsafe")),
+        ("sng_ind_atomic", cfg!(feature = "sng_ind_atomic")),
+        ("AW_safe", cfg!(feature = "AW_safe")),
+        ("count_alloc", cfg!(feature = "count_alloc")),
+        ("perf", cfg!(feature = "perf")),
+    ]
+    .into_iter()
+    .filter(|(_, on)| *on)
+    .map(|(f, _)| f.to_string())
+    .collect()
+}
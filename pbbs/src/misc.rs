@@ -24,23 +24,16 @@
 // SOFTWARE.
 // ============================================================================
 
-#[allow(dead_code)]
-pub(crate) type DefInt = u32;
+pub type DefInt = u32;
 
-#[allow(dead_code)]
-pub(crate) type DefIntS = i32;
+pub type DefIntS = i32;
 
-#[allow(dead_code)]
-pub(crate) type DefFloat = f32;
+pub type DefFloat = f32;
 
-#[allow(dead_code)]
-pub(crate) type DefChar = u8;
+pub type DefChar = u8;
 
-#[allow(dead_code)]
-pub(crate) type DefAtomInt = std::sync::atomic::AtomicU32;
+pub type DefAtomInt = std::sync::atomic::AtomicU32;
 
-#[allow(dead_code)]
-pub(crate) type DefAtomIntS = std::sync::atomic::AtomicI32;
+pub type DefAtomIntS = std::sync::atomic::AtomicI32;
 
-#[allow(dead_code)]
-pub(crate) static ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::Relaxed;
+pub static ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::Relaxed;
@@ -0,0 +1,235 @@
+use clap::Parser;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use pbbs::common::threads::{Pin, ThreadOptions};
+use rpb_bench::{BenchReport, Benchmark, ReportArgs, RunSpec, TimeOptions};
+
+mod manifest;
+
+use manifest::{Entry, Manifest, Settings};
+
+/// Runs the benchmark suite a manifest lists, in-process, checks the results
+/// and writes one report of all the runs.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// the suite manifest (TOML, or JSON if it ends in .json)
+    #[arg(required_unless_present = "list")]
+    manifest: Option<PathBuf>,
+    /// pin the threads: none, compact, scatter or a list of cores (e.g. 0,2,4-7)
+    #[arg(long, default_value_t = Pin::Compact)]
+    pin: Pin,
+    /// list the benchmarks with their algorithms and options, and exit
+    #[arg(long)]
+    list: bool,
+    #[command(flatten)]
+    report: ReportArgs,
+}
+
+/// Returns the feature set of pbbs this driver was built with.
+fn feature_set() -> String {
+    let sets = [
+        ("default", cfg!(feature = "default")),
+        ("synchronized", cfg!(feature = "synchronized")),
+        ("interior_unsafe", cfg!(feature = "interior_unsafe")),
+        ("unsafe", cfg!(feature = "unsafe")),
+    ];
+    let on: Vec<_> = sets.iter().filter(|(_, on)| *on).map(|(s, _)| *s).collect();
+    on.join("+")
+}
+
+fn benchmarks() -> Vec<Benchmark> {
+    let mut benchmarks = pbbs::benchmarks::suite::benchmarks();
+    benchmarks.extend(multiqueue::suite::benchmarks());
+    benchmarks
+}
+
+/// Returns the benchmark of `entry` and its timing options, or why the entry
+/// can not run.
+fn validate<'a>(
+    entry: &Entry,
+    defaults: &Settings,
+    benchmarks: &'a [Benchmark],
+) -> Result<(&'a Benchmark, TimeOptions), String> {
+    let bench = benchmarks
+        .iter()
+        .find(|b| b.name == entry.benchmark)
+        .ok_or_else(|| format!("unknown benchmark '{}'", entry.benchmark))?;
+    if let Some(alg) = &entry.algorithm {
+        if !bench.algorithms.iter().any(|a| a.eq_ignore_ascii_case(alg)) {
+            return Err(format!(
+                "unknown algorithm '{alg}' [possible values: {}]",
+                bench.algorithms.join(", ")
+            ));
+        }
+    }
+    if let Some(name) = entry
+        .params
+        .keys()
+        .find(|p| !bench.params.contains(&p.as_str()))
+    {
+        return Err(format!(
+            "unknown option '{name}' [possible values: {}]",
+            bench.params.join(", ")
+        ));
+    }
+    let mut time = TimeOptions {
+        rounds: entry.rounds(defaults),
+        warmup: bench.warmup,
+        ..TimeOptions::default()
+    };
+    if let Some(warmup) = entry.warmup(defaults) {
+        time.warmup = warmup.to_string().parse()?;
+    }
+    Ok((bench, time))
+}
+
+fn main() {
+    let args = Args::parse();
+    let benchmarks = benchmarks();
+    if args.list {
+        for b in &benchmarks {
+            println!(
+                "{}\talgorithms: {}\toptions: {}",
+                b.name,
+                b.algorithms.join(", "),
+                b.params.join(", ")
+            );
+        }
+        return;
+    }
+
+    let path = args.manifest.unwrap();
+    let manifest = Manifest::read(&path).unwrap_or_else(|e| {
+        eprintln!("cannot read manifest {}: {e}", path.display());
+        std::process::exit(1);
+    });
+    let defaults = &manifest.defaults;
+
+    // reject a broken manifest before timing anything.
+    let mut entries = vec![];
+    for (i, entry) in manifest.bench.iter().enumerate() {
+        match validate(entry, defaults, &benchmarks) {
+            Ok((bench, time)) => entries.push((entry, bench, time)),
+            Err(e) => {
+                eprintln!("{}: bench {}: {e}", path.display(), i + 1);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let built = feature_set();
+    let mut reports = vec![];
+    let mut failed = 0;
+    for (entry, bench, time) in entries {
+        if let Some(features) = entry.features(defaults) {
+            if features != built {
+                println!(
+                    "skipping {}: it needs the {features} feature set, rpb was built with {built}",
+                    bench.name
+                );
+                continue;
+            }
+        }
+        let params: BTreeMap<_, _> = entry
+            .params
+            .iter()
+            .map(|(k, v)| (k.clone(), v.to_string()))
+            .collect();
+        for input in &entry.inputs {
+            for threads in entry.threads(defaults) {
+                let pool = ThreadOptions {
+                    threads,
+                    pin: args.pin.clone(),
+                    ..ThreadOptions::default()
+                }
+                .build_pool();
+                let spec = RunSpec {
+                    algorithm: entry.algorithm.clone(),
+                    input: input.display().to_string(),
+                    time,
+                    threads: pool.current_num_threads(),
+                    check: entry.check(defaults),
+                    params: params.clone(),
+                };
+                println!(
+                    "== {} {} on {} threads: {}",
+                    bench.name,
+                    spec.algorithm.as_ref().unwrap_or(&bench.algorithms[0]),
+                    spec.threads,
+                    spec.input
+                );
+
+                // a panicking benchmark fails its run, not the whole suite. The
+                // release profile aborts on panics, so this only holds in debug.
+                match panic::catch_unwind(AssertUnwindSafe(|| pool.install(|| (bench.run)(&spec))))
+                {
+                    Ok(Ok(outcome)) => {
+                        if outcome.check == Some(false) {
+                            failed += 1;
+                        }
+                        reports.push(BenchReport {
+                            algorithm: outcome.algorithm,
+                            features: (bench.features)(),
+                            threads: spec.threads,
+                            input: spec.input.clone(),
+                            check: outcome.check,
+                            ..BenchReport::new(bench.name, &outcome.samples)
+                        });
+                    }
+                    Ok(Err(e)) => {
+                        eprintln!("ERR: {e}");
+                        failed += 1;
+                    }
+                    Err(_) => failed += 1,
+                }
+            }
+        }
+    }
+
+    println!("benchmark\talgorithm\tthreads\tmean\tcheck\tinput");
+    for r in &reports {
+        let check = match r.check {
+            Some(true) => "ok",
+            Some(false) => "failed",
+            None => "-",
+        };
+        println!(
+            "{}\t{}\t{}\t{:.6}\t{check}\t{}",
+            r.benchmark, r.algorithm, r.threads, r.mean, r.input
+        );
+    }
+    args.report.write_all(&reports);
+    if failed > 0 {
+        eprintln!("{failed} runs failed");
+        std::process::exit(1);
+    }
+}
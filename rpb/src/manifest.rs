@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use serde::Deserialize;
+
+/// A suite of benchmark runs, read from a TOML or JSON file.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// the settings of the entries that do not set them.
+    #[serde(default)]
+    pub defaults: Settings,
+    #[serde(default)]
+    pub bench: Vec<Entry>,
+}
+
+/// The settings an entry can inherit from the defaults.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub rounds: Option<usize>,
+    /// seconds (`1.5s`) or rounds (`10r`).
+    pub warmup: Option<Value>,
+    /// run the entry once per thread count (0 uses all the cores).
+    pub threads: Option<Vec<usize>>,
+    pub check: Option<bool>,
+    /// the cargo feature set `rpb` must be built with, e.g. `unsafe`.
+    pub features: Option<String>,
+}
+
+/// One benchmark and algorithm, to run on each input and thread count.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    pub benchmark: String,
+    /// the benchmark's default algorithm if not given.
+    pub algorithm: Option<String>,
+    /// relative to the directory of the manifest.
+    pub inputs: Vec<PathBuf>,
+    /// the benchmark's own options, e.g. `bits` of `isort`.
+    #[serde(default)]
+    pub params: BTreeMap<String, Value>,
+    pub rounds: Option<usize>,
+    pub warmup: Option<Value>,
+    pub threads: Option<Vec<usize>>,
+    pub check: Option<bool>,
+    pub features: Option<String>,
+}
+
+/// A scalar of the manifest, handed to the benchmark as a string.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::Str(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Why a manifest could not be read.
+pub enum ManifestError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Toml(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl fmt::Debug for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<io::Error> for ManifestError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl Manifest {
+    /// Reads a manifest, as JSON if the file name ends in `.json` and as
+    /// TOML otherwise. The inputs are resolved against its directory.
+    pub fn read(path: &Path) -> Result<Self, ManifestError> {
+        let text = fs::read_to_string(path)?;
+        let mut manifest: Manifest = match path.extension() {
+            Some(ext) if ext == "json" => {
+                serde_json::from_str(&text).map_err(ManifestError::Json)?
+            }
+            _ => toml::from_str(&text).map_err(ManifestError::Toml)?,
+        };
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for entry in &mut manifest.bench {
+            for input in &mut entry.inputs {
+                *input = dir.join(&*input);
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+// the settings of an entry take precedence over the defaults.
+impl Entry {
+    pub fn rounds(&self, defaults: &Settings) -> usize {
+        self.rounds.or(defaults.rounds).unwrap_or(1)
+    }
+
+    pub fn warmup<'a>(&'a self, defaults: &'a Settings) -> Option<&'a Value> {
+        self.warmup.as_ref().or(defaults.warmup.as_ref())
+    }
+
+    pub fn threads(&self, defaults: &Settings) -> Vec<usize> {
+        let threads = self.threads.as_ref().or(defaults.threads.as_ref());
+        threads.cloned().unwrap_or_else(|| vec![0])
+    }
+
+    pub fn check(&self, defaults: &Settings) -> bool {
+        self.check.or(defaults.check).unwrap_or(false)
+    }
+
+    pub fn features<'a>(&'a self, defaults: &'a Settings) -> Option<&'a str> {
+        self.features.as_deref().or(defaults.features.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod manifest_tests {
+    use super::*;
+
+    #[test]
+    fn toml_and_json() {
+        let toml = r#"
+            [defaults]
+            rounds = 3
+            threads = [1, 4]
+            check = true
+
+            [[bench]]
+            benchmark = "isort"
+            inputs = ["seq.txt"]
+            params = { bits = 16 }
+
+            [[bench]]
+            benchmark = "sort"
+            algorithm = "quick"
+            inputs = ["a.txt", "b.txt"]
+            rounds = 5
+            warmup = "10r"
+            features = "unsafe"
+        "#;
+        let m: Manifest = toml::from_str(toml).unwrap();
+        let (isort, sort) = (&m.bench[0], &m.bench[1]);
+        assert_eq!(isort.rounds(&m.defaults), 3);
+        assert_eq!(isort.threads(&m.defaults), [1, 4]);
+        assert_eq!(isort.params["bits"].to_string(), "16");
+        assert!(isort.check(&m.defaults));
+        assert_eq!(isort.features(&m.defaults), None);
+        assert_eq!(sort.rounds(&m.defaults), 5);
+        assert_eq!(
+            sort.warmup(&m.defaults),
+            Some(&Value::Str("10r".to_string()))
+        );
+        assert_eq!(sort.features(&m.defaults), Some("unsafe"));
+
+        let json = r#"{
+            "defaults": { "rounds": 3, "threads": [1, 4], "check": true },
+            "bench": [
+                { "benchmark": "isort", "inputs": ["seq.txt"], "params": { "bits": 16 } },
+                { "benchmark": "sort", "algorithm": "quick", "inputs": ["a.txt", "b.txt"],
+                  "rounds": 5, "warmup": "10r", "features": "unsafe" }
+            ]
+        }"#;
+        assert_eq!(serde_json::from_str::<Manifest>(json).unwrap(), m);
+
+        let typo = "[[bench]]\nbenchmark = \"isort\"\ninputs = []\nround = 3\n";
+        assert!(toml::from_str::<Manifest>(typo).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use serde::{Deserialize, Serialize};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations and the bytes it hands
+/// out. It is the global allocator of the binaries built with the
+/// `count_alloc` feature; every thread updates the same counters, so it slows
+/// down code that allocates a lot.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn count(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED.fetch_add(size, Relaxed);
+        let live = LIVE.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(live, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            Self::count(layout.size());
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            Self::count(layout.size());
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            Self::count(new_size);
+        }
+        p
+    }
+}
+
+#[cfg(feature = "count_alloc")]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// What a piece of code allocated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// the number of allocations, counting reallocations.
+    pub allocations: usize,
+    /// the bytes they asked for.
+    pub bytes: usize,
+    /// the most bytes live at once, above those live when it started.
+    pub peak_bytes: usize,
+}
+
+impl AllocStats {
+    /// Runs `f` and returns what it allocated, or `None` if the binary was
+    /// built without the `count_alloc` feature.
+    pub fn measure<F: FnOnce()>(f: F) -> Option<Self> {
+        if cfg!(feature = "count_alloc") {
+            Some(counted(f))
+        } else {
+            f();
+            None
+        }
+    }
+
+    /// Adds the allocations of `other`, keeping the higher peak.
+    pub fn merge(&mut self, other: Self) {
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+        self.peak_bytes = self.peak_bytes.max(other.peak_bytes);
+    }
+}
+
+/// Runs `f` and returns what it allocated through `CountingAlloc`. The
+/// counters are global, so the stats are only right if nothing else
+/// allocates meanwhile.
+fn counted<F: FnOnce()>(f: F) -> AllocStats {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = ALLOCATED.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+    f();
+    AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED.load(Relaxed) - bytes,
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(live),
+    }
+}
+
+#[cfg(test)]
+mod alloc_tests {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let a = CountingAlloc;
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(300, 8).unwrap();
+        let s = counted(|| unsafe {
+            let p = a.alloc(small);
+            let q = a.alloc_zeroed(small);
+            let p = a.realloc(p, small, 300);
+            a.dealloc(q, small);
+            a.dealloc(p, large);
+        });
+        // with `count_alloc` the test harness allocates too.
+        if !cfg!(feature = "count_alloc") {
+            assert_eq!(
+                s,
+                AllocStats {
+                    allocations: 3,
+                    bytes: 500,
+                    peak_bytes: 400,
+                }
+            );
+        }
+
+        let mut total = s;
+        total.merge(AllocStats {
+            allocations: 1,
+            bytes: 10,
+            peak_bytes: 1000,
+        });
+        assert_eq!(total.allocations, s.allocations + 1);
+        assert_eq!(total.peak_bytes, 1000);
+    }
+}
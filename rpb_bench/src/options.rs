@@ -0,0 +1,218 @@
+use std::time::Duration;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{PerfStats, Samples};
+
+/// How long to run the benchmark before timing it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Warmup {
+    /// run for at least this long.
+    Time(Duration),
+    /// run this many times.
+    Rounds(usize),
+}
+
+impl Warmup {
+    /// the warm-up of a benchmark that does not set its own.
+    pub const DEFAULT: Warmup = Warmup::Time(Duration::from_secs(1));
+}
+
+/// Parses `1.5` or `1.5s` as seconds, and `10r` as rounds.
+impl FromStr for Warmup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("expected seconds (e.g. `1.5s`) or rounds (e.g. `10r`), found `{s}`");
+        if let Some(r) = s.strip_suffix('r') {
+            return r.parse().map(Warmup::Rounds).map_err(|_| err());
+        }
+        let secs: f64 = s
+            .strip_suffix('s')
+            .unwrap_or(s)
+            .parse()
+            .map_err(|_| err())?;
+        Duration::try_from_secs_f64(secs)
+            .map(Warmup::Time)
+            .map_err(|_| err())
+    }
+}
+
+impl fmt::Display for Warmup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warmup::Time(d) => write!(f, "{}s", d.as_secs_f64()),
+            Warmup::Rounds(r) => write!(f, "{r}r"),
+        }
+    }
+}
+
+/// How many times to run a benchmark.
+#[derive(clap::Args, Clone, Copy, Debug)]
+// keep the doc comment out of the `--help` of the binaries.
+#[command(about = None, long_about = None)]
+pub struct TimeOptions {
+    /// the number of rounds to execute the benchmark
+    #[clap(short, long, value_parser, required = false, default_value_t = 1)]
+    pub rounds: usize,
+
+    /// the warm-up before timing, in seconds (`1.5s`) or rounds (`10r`)
+    #[clap(long, value_parser, required = false, default_value_t = Warmup::DEFAULT)]
+    pub warmup: Warmup,
+
+    /// keep running rounds until they take this many seconds in total
+    #[clap(long, value_parser, required = false, default_value_t = 0.0)]
+    pub min_time: f64,
+
+    /// keep running rounds until the 95% confidence interval of the mean is
+    /// within this fraction of it (e.g. 0.02)
+    #[clap(long, value_parser, required = false)]
+    pub target_error: Option<f64>,
+
+    /// the maximum number of rounds of `--min-time` and `--target-error`
+    #[clap(long, value_parser, required = false, default_value_t = 100)]
+    pub max_rounds: usize,
+}
+
+impl Default for TimeOptions {
+    fn default() -> Self {
+        Self {
+            rounds: 1,
+            warmup: Warmup::DEFAULT,
+            min_time: 0.0,
+            target_error: None,
+            max_rounds: 100,
+        }
+    }
+}
+
+impl TimeOptions {
+    /// Whether the timed rounds in `samples` are enough.
+    pub fn done(&self, samples: &Samples) -> bool {
+        let n = samples.rounds.len();
+        if n < self.rounds {
+            return false;
+        }
+        if n >= self.max_rounds {
+            return true;
+        }
+        samples.total().as_secs_f64() >= self.min_time
+            && self
+                .target_error
+                .is_none_or(|e| samples.relative_error() <= e)
+    }
+
+    /// Runs `round`, which times itself and returns the hardware events it
+    /// counted, for the warm-up and then until the timed rounds are `done`.
+    pub fn run_rounds<F>(&self, mut round: F) -> Samples
+    where
+        F: FnMut() -> (Duration, Option<PerfStats>),
+    {
+        let mut samples = Samples::default();
+        let mut warmup_rounds = 0;
+        while match self.warmup {
+            Warmup::Time(d) => samples.warmup < d,
+            Warmup::Rounds(r) => warmup_rounds < r,
+        } {
+            samples.warmup += round().0;
+            warmup_rounds += 1;
+        }
+        while !self.done(&samples) {
+            let (time, perf) = round();
+            samples.record(time, None, perf);
+        }
+        samples
+    }
+}
+
+#[cfg(test)]
+mod options_tests {
+    use super::*;
+
+    fn samples(secs: &[f64]) -> Samples {
+        Samples {
+            rounds: secs.iter().map(|&s| Duration::from_secs_f64(s)).collect(),
+            ..Samples::default()
+        }
+    }
+
+    #[test]
+    fn warmup() {
+        let parse = |s: &str| s.parse::<Warmup>();
+        assert_eq!(parse("1.5s"), Ok(Warmup::Time(Duration::from_millis(1500))));
+        assert_eq!(parse("1.5"), Ok(Warmup::Time(Duration::from_millis(1500))));
+        assert_eq!(parse("0"), Ok(Warmup::Time(Duration::ZERO)));
+        assert_eq!(parse("10r"), Ok(Warmup::Rounds(10)));
+        for bad in ["-1", "r", "s", "1.5r", "10x", ""] {
+            assert!(parse(bad).is_err(), "{bad}");
+        }
+
+        assert_eq!(
+            Warmup::Time(Duration::from_millis(1500)).to_string(),
+            "1.5s"
+        );
+        assert_eq!(Warmup::Rounds(10).to_string(), "10r");
+        assert_eq!(parse(&Warmup::DEFAULT.to_string()), Ok(Warmup::DEFAULT));
+    }
+
+    #[test]
+    fn done() {
+        let opts = TimeOptions {
+            rounds: 3,
+            ..TimeOptions::default()
+        };
+        assert!(!opts.done(&samples(&[1.0, 1.0])));
+        assert!(opts.done(&samples(&[1.0, 1.0, 1.0])));
+
+        // `rounds` is a minimum, even above `max_rounds`.
+        let opts = TimeOptions {
+            rounds: 3,
+            max_rounds: 2,
+            ..TimeOptions::default()
+        };
+        assert!(!opts.done(&samples(&[1.0, 1.0])));
+
+        let opts = TimeOptions {
+            min_time: 1.0,
+            max_rounds: 5,
+            ..TimeOptions::default()
+        };
+        assert!(!opts.done(&samples(&[0.1, 0.1, 0.1])));
+        assert!(opts.done(&samples(&[0.5, 0.5])));
+        assert!(opts.done(&samples(&[0.1; 5])));
+
+        let opts = TimeOptions {
+            target_error: Some(0.02),
+            ..TimeOptions::default()
+        };
+        assert!(!opts.done(&samples(&[1.0])));
+        assert!(!opts.done(&samples(&[1.0, 2.0, 1.0])));
+        assert!(opts.done(&samples(&[1.0, 1.0, 1.0])));
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+use std::fs::File;
+use std::io::{self, Read};
+use std::sync::Once;
+
+use serde::{Deserialize, Serialize};
+
+/// Hardware event counts of a piece of code. An event is `None` if the CPU
+/// or the kernel can not count it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerfStats {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycles: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_misses: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_misses: Option<u64>,
+}
+
+impl PerfStats {
+    fn from_counts(counts: [Option<u64>; 4]) -> Self {
+        let [cycles, instructions, cache_misses, branch_misses] = counts;
+        Self {
+            cycles,
+            instructions,
+            cache_misses,
+            branch_misses,
+        }
+    }
+
+    fn counts(&self) -> [Option<u64>; 4] {
+        [
+            self.cycles,
+            self.instructions,
+            self.cache_misses,
+            self.branch_misses,
+        ]
+    }
+
+    /// Adds the counts of `other`.
+    pub fn merge(&mut self, other: Self) {
+        let mut counts = self.counts();
+        for (c, o) in counts.iter_mut().zip(other.counts()) {
+            *c = match (*c, o) {
+                (Some(c), Some(o)) => Some(c + o),
+                (c, o) => c.or(o),
+            };
+        }
+        *self = Self::from_counts(counts);
+    }
+
+    /// Returns the counts divided by `n`.
+    pub fn divide(&self, n: u64) -> Self {
+        Self::from_counts(self.counts().map(|c| c.map(|c| c / n.max(1))))
+    }
+}
+
+impl fmt::Display for PerfStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = ["cycles", "instructions", "cache misses", "branch misses"];
+        let counted: Vec<_> = self
+            .counts()
+            .iter()
+            .zip(names)
+            .filter_map(|(c, name)| c.map(|c| format!("{c} {name}")))
+            .collect();
+        write!(f, "{}", counted.join(", "))
+    }
+}
+
+/// The counters of `PerfStats` on some threads, opened with
+/// `perf_event_open` for user space only, which needs no root with the
+/// default `perf_event_paranoid`. The counters of a thread are one group, so
+/// they count the same instructions and are read together. Without the
+/// `perf` feature, off Linux, or if the kernel refuses, there are no counters
+/// and `stop` returns `None`.
+#[derive(Default)]
+pub struct PerfCounters {
+    /// the counter of every event on every thread that could be opened. The
+    /// first one of a thread leads its group.
+    threads: Vec<[Option<File>; 4]>,
+    /// the events that could be opened on all the threads.
+    counted: [bool; 4],
+    /// the readings of `start`.
+    started: Vec<Option<Count>>,
+}
+
+impl PerfCounters {
+    /// Counts the calling thread.
+    pub fn this_thread() -> Self {
+        if !cfg!(feature = "perf") {
+            return Self::default();
+        }
+        Self::new(vec![sys::open_events()])
+    }
+
+    /// Counts every thread of the current rayon pool.
+    pub fn rayon_pool() -> Self {
+        if !cfg!(feature = "perf") {
+            return Self::default();
+        }
+        Self::new(rayon::broadcast(|_| sys::open_events()))
+    }
+
+    fn new(opened: Vec<[io::Result<File>; 4]>) -> Self {
+        let mut counted = [true; 4];
+        for events in &opened {
+            for (c, e) in counted.iter_mut().zip(events) {
+                if let Err(e) = e {
+                    *c = false;
+                    static WARN: Once = Once::new();
+                    WARN.call_once(|| {
+                        eprintln!("Warning: can not count some hardware events: {e}");
+                    });
+                }
+            }
+        }
+        if !counted.contains(&true) {
+            return Self::default();
+        }
+        // the events counted on only some threads stay open, as they are in
+        // the groups, but are left out of the counts.
+        let threads = opened
+            .into_iter()
+            .map(|events| events.map(Result::ok))
+            .collect();
+        Self {
+            threads,
+            counted,
+            started: vec![],
+        }
+    }
+
+    fn leaders(&self) -> impl Iterator<Item = &File> {
+        self.threads
+            .iter()
+            .filter_map(|e| e.iter().flatten().next())
+    }
+
+    /// Starts counting.
+    pub fn start(&mut self) {
+        self.started = self.read();
+        for f in self.leaders() {
+            sys::enable(f, true);
+        }
+    }
+
+    /// Stops counting and returns the counts since `start`, summed over the
+    /// threads.
+    pub fn stop(&mut self) -> Option<PerfStats> {
+        if self.threads.is_empty() {
+            return None;
+        }
+        for f in self.leaders() {
+            sys::enable(f, false);
+        }
+        let mut counts = [None; 4];
+        for (now, then) in self.read().iter().zip(&self.started) {
+            if let (Some(now), Some(then)) = (now, then) {
+                for (i, c) in now.since(then).into_iter().enumerate() {
+                    if let Some(c) = c.filter(|_| self.counted[i]) {
+                        counts[i] = Some(counts[i].unwrap_or(0) + c);
+                    }
+                }
+            }
+        }
+        Some(PerfStats::from_counts(counts))
+    }
+
+    fn read(&self) -> Vec<Option<Count>> {
+        self.threads.iter().map(Count::of).collect()
+    }
+}
+
+/// A reading of the group of counters of a thread: their values and how
+/// long the group was enabled and actually counting, for when the kernel
+/// multiplexes the counters.
+#[derive(Clone, Copy)]
+struct Count {
+    values: [Option<u64>; 4],
+    enabled: u64,
+    running: u64,
+}
+
+impl Count {
+    /// Reads the group of `events` through its leader, the first of them.
+    fn of(events: &[Option<File>; 4]) -> Option<Self> {
+        let mut leader = events.iter().flatten().next()?;
+        // the number of counters, the times and a value per counter.
+        let mut buf = [0u8; 8 * (3 + 4)];
+        let len = leader.read(&mut buf).ok()?;
+        let word = |i: usize| u64::from_ne_bytes(buf[8 * i..8 * i + 8].try_into().unwrap());
+        let nr = word(0) as usize;
+        if len < 8 * (3 + nr) {
+            return None;
+        }
+        // the values follow the order the counters joined the group in.
+        let mut values = [None; 4];
+        let members = events.iter().enumerate().filter(|(_, f)| f.is_some());
+        for (k, (i, _)) in members.take(nr).enumerate() {
+            values[i] = Some(word(3 + k));
+        }
+        Some(Count {
+            values,
+            enabled: word(1),
+            running: word(2),
+        })
+    }
+
+    /// Returns the counts since `then`, scaled up to the time the group was
+    /// enabled.
+    fn since(&self, then: &Count) -> [Option<u64>; 4] {
+        let enabled = self.enabled - then.enabled;
+        let running = self.running - then.running;
+        let mut counts = [None; 4];
+        for (c, (now, then)) in counts.iter_mut().zip(self.values.iter().zip(then.values)) {
+            if let (Some(now), Some(then)) = (now, then) {
+                *c = Some(match running {
+                    0 => 0,
+                    _ => ((now - then) as u128 * enabled as u128 / running as u128) as u64,
+                });
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(all(feature = "perf", target_os = "linux"))]
+mod sys {
+    use std::fs::File;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    use perf_event_open_sys::bindings::{
+        perf_event_attr, perf_event_ioc_flags_PERF_IOC_FLAG_GROUP as IOC_FLAG_GROUP,
+        perf_event_read_format_PERF_FORMAT_GROUP as GROUP,
+        perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED as TIME_ENABLED,
+        perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING as TIME_RUNNING,
+        perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES as BRANCH_MISSES,
+        perf_hw_id_PERF_COUNT_HW_CACHE_MISSES as CACHE_MISSES,
+        perf_hw_id_PERF_COUNT_HW_CPU_CYCLES as CPU_CYCLES,
+        perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS as INSTRUCTIONS,
+        perf_type_id_PERF_TYPE_HARDWARE as HARDWARE,
+    };
+    use perf_event_open_sys::{ioctls, perf_event_open};
+
+    /// Opens a counter of each event on the calling thread, as one group led
+    /// by the first counter that opens. The leader starts disabled and
+    /// enabling it enables the group.
+    pub fn open_events() -> [io::Result<File>; 4] {
+        let mut leader = None;
+        [CPU_CYCLES, INSTRUCTIONS, CACHE_MISSES, BRANCH_MISSES].map(|event| {
+            let mut attr = perf_event_attr {
+                type_: HARDWARE,
+                size: std::mem::size_of::<perf_event_attr>() as u32,
+                config: event as u64,
+                read_format: (GROUP | TIME_ENABLED | TIME_RUNNING) as u64,
+                ..Default::default()
+            };
+            attr.set_disabled(leader.is_none() as u64);
+            attr.set_exclude_kernel(1);
+            attr.set_exclude_hv(1);
+            let fd = unsafe { perf_event_open(&mut attr, 0, -1, leader.unwrap_or(-1), 0) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            leader.get_or_insert(fd);
+            Ok(unsafe { File::from_raw_fd(fd) })
+        })
+    }
+
+    /// Enables or disables the group `leader` leads.
+    pub fn enable(leader: &File, on: bool) {
+        unsafe {
+            match on {
+                true => ioctls::ENABLE(leader.as_raw_fd(), IOC_FLAG_GROUP),
+                false => ioctls::DISABLE(leader.as_raw_fd(), IOC_FLAG_GROUP),
+            };
+        }
+    }
+}
+
+#[cfg(not(all(feature = "perf", target_os = "linux")))]
+mod sys {
+    use std::fs::File;
+    use std::io;
+
+    pub fn open_events() -> [io::Result<File>; 4] {
+        [(); 4].map(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "perf counters are only supported on Linux",
+            ))
+        })
+    }
+
+    pub fn enable(_: &File, _: bool) {}
+}
+
+#[cfg(test)]
+mod perf_tests {
+    use super::*;
+
+    #[test]
+    fn merge_and_scale() {
+        let mut s = PerfStats {
+            cycles: Some(100),
+            instructions: None,
+            cache_misses: Some(4),
+            branch_misses: None,
+        };
+        s.merge(PerfStats {
+            cycles: Some(50),
+            instructions: Some(10),
+            ..PerfStats::default()
+        });
+        assert_eq!(s.cycles, Some(150));
+        assert_eq!(s.instructions, Some(10));
+        assert_eq!(s.branch_misses, None);
+        assert_eq!(s.divide(2).cycles, Some(75));
+        assert_eq!(s.to_string(), "150 cycles, 10 instructions, 4 cache misses");
+
+        // counting half the time it was enabled, the counts are doubled.
+        let then = Count {
+            values: [Some(10), Some(0), None, None],
+            enabled: 100,
+            running: 100,
+        };
+        let now = Count {
+            values: [Some(60), Some(5), None, None],
+            enabled: 300,
+            running: 200,
+        };
+        assert_eq!(now.since(&then), [Some(100), Some(10), None, None]);
+        assert_eq!(then.since(&then), [Some(0), Some(0), None, None]);
+    }
+}
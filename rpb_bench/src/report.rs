@@ -0,0 +1,485 @@
+use std::path::{Path, PathBuf};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::time::Duration;
+use std::{fs, io};
+
+use clap::error::ErrorKind;
+use clap::{Arg, ArgMatches, Command, FromArgMatches, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::AllocStats;
+use crate::perf::PerfStats;
+
+/// The times measured by a benchmark.
+#[derive(Clone, Debug, Default)]
+pub struct Samples {
+    /// the total time of the warm-up runs.
+    pub warmup: Duration,
+    /// the time of each timed round.
+    pub rounds: Vec<Duration>,
+    /// what the timed rounds allocated together, with the `count_alloc`
+    /// feature.
+    pub alloc: Option<AllocStats>,
+    /// the hardware events of the timed rounds together, with the `perf`
+    /// feature.
+    pub perf: Option<PerfStats>,
+}
+
+impl Samples {
+    pub fn mean(&self) -> Duration {
+        if self.rounds.is_empty() {
+            return Duration::ZERO;
+        }
+        self.rounds.iter().sum::<Duration>() / self.rounds.len() as u32
+    }
+
+    /// Adds a timed round, with what it allocated and counted if it was
+    /// measured.
+    pub fn record(&mut self, time: Duration, alloc: Option<AllocStats>, perf: Option<PerfStats>) {
+        self.rounds.push(time);
+        if let Some(a) = alloc {
+            self.alloc.get_or_insert_with(AllocStats::default).merge(a);
+        }
+        if let Some(p) = perf {
+            self.perf.get_or_insert_with(PerfStats::default).merge(p);
+        }
+    }
+
+    /// Returns the hardware events of one timed round on average.
+    pub fn perf_per_round(&self) -> Option<PerfStats> {
+        self.perf.map(|p| p.divide(self.rounds.len() as u64))
+    }
+
+    /// Returns what one timed round allocated on average, and the highest
+    /// peak of them.
+    pub fn alloc_per_round(&self) -> Option<AllocStats> {
+        let n = self.rounds.len().max(1);
+        self.alloc.map(|a| AllocStats {
+            allocations: a.allocations / n,
+            bytes: a.bytes / n,
+            peak_bytes: a.peak_bytes,
+        })
+    }
+
+    /// Returns the total time of the timed rounds.
+    pub fn total(&self) -> Duration {
+        self.rounds.iter().sum()
+    }
+
+    /// Returns the half-width of the 95% confidence interval of the mean,
+    /// relative to the mean. It is infinite with fewer than two rounds.
+    pub fn relative_error(&self) -> f64 {
+        let n = self.rounds.len();
+        if n < 2 {
+            return f64::INFINITY;
+        }
+        let times: Vec<f64> = self.rounds.iter().map(Duration::as_secs_f64).collect();
+        let (mean, stddev) = mean_stddev(&times);
+        t_975(n - 1) * stddev / (n as f64).sqrt() / mean
+    }
+}
+
+/// Returns the mean and the sample standard deviation of `times`.
+fn mean_stddev(times: &[f64]) -> (f64, f64) {
+    let n = times.len();
+    if n == 0 {
+        return (0.0, 0.0);
+    }
+    let mean = times.iter().sum::<f64>() / n as f64;
+    let var = if n > 1 {
+        times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+    } else {
+        0.0
+    };
+    (mean, var.sqrt())
+}
+
+/// The 0.975 quantile of Student's t-distribution with `df` degrees of
+/// freedom.
+fn t_975(df: usize) -> f64 {
+    const T: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+    match df {
+        0 => f64::INFINITY,
+        1..=30 => T[df - 1],
+        // the normal approximation, corrected for the tail
+        _ => 1.96 + 2.4 / df as f64,
+    }
+}
+
+/// The result of one run of a benchmark binary.
+/// Times are in seconds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub benchmark: String,
+    pub algorithm: String,
+    /// the cargo features the binary was built with.
+    pub features: Vec<String>,
+    pub threads: usize,
+    pub input: String,
+    /// the time of each timed round.
+    pub times: Vec<f64>,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    /// the sample standard deviation of `times`.
+    pub stddev: f64,
+    pub warmup: f64,
+    /// the verdict of the checker, `None` if the result was not checked.
+    pub check: Option<bool>,
+    /// the speedup over the same algorithm on one thread, in a thread sweep.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_speedup: Option<f64>,
+    /// the speedup over the serial algorithm, in a thread sweep.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial_speedup: Option<f64>,
+    /// what a timed round allocated, with the `count_alloc` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    /// the hardware events of a timed round, with the `perf` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perf: Option<PerfStats>,
+}
+
+impl BenchReport {
+    /// The columns of [`BenchReport::to_csv_row`].
+    pub const CSV_HEADER: &'static str =
+        "benchmark,algorithm,features,threads,input,times,min,median,mean,stddev,warmup,check,\
+         self_speedup,serial_speedup,allocations,alloc_bytes,peak_bytes,cycles,instructions,\
+         cache_misses,branch_misses";
+
+    /// Creates a report of `benchmark` with the statistics of `samples`.
+    /// The other fields are left empty.
+    pub fn new(benchmark: &str, samples: &Samples) -> Self {
+        let times: Vec<f64> = samples.rounds.iter().map(Duration::as_secs_f64).collect();
+        let mut sorted = times.clone();
+        sorted.sort_by(f64::total_cmp);
+        let n = times.len();
+        let (mean, stddev) = mean_stddev(&times);
+        let (min, median) = if n == 0 {
+            (0.0, 0.0)
+        } else if n % 2 == 1 {
+            (sorted[0], sorted[n / 2])
+        } else {
+            (sorted[0], (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0)
+        };
+        Self {
+            benchmark: benchmark.to_string(),
+            times,
+            min,
+            median,
+            mean,
+            stddev,
+            warmup: samples.warmup.as_secs_f64(),
+            alloc: samples.alloc_per_round(),
+            perf: samples.perf_per_round(),
+            ..Self::default()
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a report is always serializable")
+    }
+
+    /// Returns the report as one CSV line, without the header.
+    /// Lists (features and times) are separated by `;`.
+    pub fn to_csv_row(&self) -> String {
+        let check = match self.check {
+            None => "",
+            Some(true) => "ok",
+            Some(false) => "failed",
+        };
+        let mut row = String::new();
+        for field in [
+            csv_field(&self.benchmark),
+            csv_field(&self.algorithm),
+            csv_field(&self.features.join(";")),
+            self.threads.to_string(),
+            csv_field(&self.input),
+            self.times
+                .iter()
+                .map(f64::to_string)
+                .collect::<Vec<_>>()
+                .join(";"),
+            self.min.to_string(),
+            self.median.to_string(),
+            self.mean.to_string(),
+            self.stddev.to_string(),
+            self.warmup.to_string(),
+            check.to_string(),
+            self.self_speedup.map_or(String::new(), |s| s.to_string()),
+            self.serial_speedup.map_or(String::new(), |s| s.to_string()),
+            self.alloc
+                .map_or(String::new(), |a| a.allocations.to_string()),
+            self.alloc.map_or(String::new(), |a| a.bytes.to_string()),
+            self.alloc
+                .map_or(String::new(), |a| a.peak_bytes.to_string()),
+            count(self.perf.and_then(|p| p.cycles)),
+            count(self.perf.and_then(|p| p.instructions)),
+            count(self.perf.and_then(|p| p.cache_misses)),
+            count(self.perf.and_then(|p| p.branch_misses)),
+        ] {
+            if !row.is_empty() {
+                row.push(',');
+            }
+            row.push_str(&field);
+        }
+        row
+    }
+
+    /// Writes the report to `path`, replacing the file.
+    pub fn write<P: AsRef<Path>>(&self, format: ReportFormat, path: P) -> io::Result<()> {
+        let mut s = match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv => format!("{}\n{}", Self::CSV_HEADER, self.to_csv_row()),
+        };
+        s.push('\n');
+        fs::write(path, s)
+    }
+
+    /// Writes several reports to `path`, as a JSON array or one CSV row each.
+    pub fn write_all<P: AsRef<Path>>(
+        reports: &[BenchReport],
+        format: ReportFormat,
+        path: P,
+    ) -> io::Result<()> {
+        let mut s = match format {
+            ReportFormat::Json => {
+                serde_json::to_string_pretty(reports).expect("a report is always serializable")
+            }
+            ReportFormat::Csv => {
+                let mut s = Self::CSV_HEADER.to_string();
+                for r in reports {
+                    s.push('\n');
+                    s.push_str(&r.to_csv_row());
+                }
+                s
+            }
+        };
+        s.push('\n');
+        fs::write(path, s)
+    }
+}
+
+/// Formats a count that may be missing as a CSV field.
+fn count(c: Option<u64>) -> String {
+    c.map_or(String::new(), |c| c.to_string())
+}
+
+/// Quotes a CSV field if it has to be.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        let mut q = String::from("\"");
+        for c in s.chars() {
+            if c == '"' {
+                q.push('"');
+            }
+            q.push(c);
+        }
+        q.push('"');
+        q
+    } else {
+        s.to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+/// The `--report <FORMAT> <FILE>` option of the benchmark binaries.
+/// Flatten it into a clap `Parser` with `#[command(flatten)]`.
+#[derive(Clone, Debug, Default)]
+pub struct ReportArgs {
+    /// the format and the file to write the report to, if any.
+    pub target: Option<(ReportFormat, PathBuf)>,
+}
+
+impl ReportArgs {
+    /// Writes `report` to the requested file, if any.
+    /// Exits the process if the file can not be written.
+    pub fn write(&self, report: &BenchReport) {
+        if let Some((format, path)) = &self.target {
+            exit_on_error(report.write(*format, path), path);
+        }
+    }
+
+    /// Like [`ReportArgs::write`], for the reports of a thread sweep.
+    pub fn write_all(&self, reports: &[BenchReport]) {
+        if let Some((format, path)) = &self.target {
+            exit_on_error(BenchReport::write_all(reports, *format, path), path);
+        }
+    }
+}
+
+fn exit_on_error(res: io::Result<()>, path: &Path) {
+    if let Err(e) = res {
+        eprintln!("cannot write the report to {}: {e}", path.display());
+        std::process::exit(1);
+    }
+}
+
+impl FromArgMatches for ReportArgs {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        let mut values = matches.get_many::<String>("report").into_iter().flatten();
+        let target = match (values.next(), values.next()) {
+            (Some(format), Some(path)) => {
+                let format = ReportFormat::from_str(format, true).map_err(|_| {
+                    clap::Error::raw(
+                        ErrorKind::InvalidValue,
+                        format!("invalid report format '{format}' [possible values: json, csv]"),
+                    )
+                })?;
+                Some((format, PathBuf::from(path)))
+            }
+            _ => None,
+        };
+        Ok(Self { target })
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl clap::Args for ReportArgs {
+    fn augment_args(cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("report")
+                .long("report")
+                .num_args(2)
+                .value_names(["FORMAT", "FILE"])
+                .help("write a report of the run to FILE as `json` or `csv`"),
+        )
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        Self::augment_args(cmd)
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Args {
+        #[command(flatten)]
+        report: ReportArgs,
+    }
+
+    fn samples(ms: &[u64]) -> Samples {
+        Samples {
+            warmup: Duration::from_millis(500),
+            rounds: ms.iter().map(|&m| Duration::from_millis(m)).collect(),
+            ..Samples::default()
+        }
+    }
+
+    #[test]
+    fn statistics() {
+        let r = BenchReport::new("sort", &samples(&[40, 10, 30, 20]));
+        assert_eq!(r.times, [0.04, 0.01, 0.03, 0.02]);
+        assert_eq!(r.min, 0.01);
+        assert!((r.median - 0.025).abs() < 1e-12);
+        assert!((r.mean - 0.025).abs() < 1e-12);
+        assert!((r.stddev - 0.0129099).abs() < 1e-6);
+        assert_eq!(r.warmup, 0.5);
+
+        let s = samples(&[40, 10, 30, 20]);
+        assert!((s.relative_error() - 3.182 * 0.0129099 / 2.0 / 0.025).abs() < 1e-4);
+        assert_eq!(samples(&[10]).relative_error(), f64::INFINITY);
+
+        let r = BenchReport::new("sort", &samples(&[10]));
+        assert_eq!((r.median, r.stddev), (0.01, 0.0));
+        assert_eq!(BenchReport::new("sort", &samples(&[])).mean, 0.0);
+    }
+
+    #[test]
+    fn csv_and_json() {
+        let r = BenchReport {
+            algorithm: "quick".to_string(),
+            features: vec!["a".to_string(), "b".to_string()],
+            threads: 4,
+            input: "in,\"x\"".to_string(),
+            check: Some(true),
+            ..BenchReport::new("sort", &samples(&[10, 20]))
+        };
+        let row = r.to_csv_row();
+        assert_eq!(
+            row,
+            "sort,quick,a;b,4,\"in,\"\"x\"\"\",0.01;0.02,0.01,0.015,0.015,0.007071067811865475,0.5,ok,,,,,,,,,"
+        );
+        assert_eq!(
+            row.split(',').count(),
+            BenchReport::CSV_HEADER.split(',').count() + 1
+        );
+        assert_eq!(
+            serde_json::from_str::<BenchReport>(&r.to_json()).unwrap(),
+            r
+        );
+        assert!(!r.to_json().contains("speedup"));
+        assert!(!r.to_json().contains("alloc"));
+        assert!(!r.to_json().contains("perf"));
+
+        let s = Samples {
+            alloc: Some(AllocStats {
+                allocations: 10,
+                bytes: 4096,
+                peak_bytes: 1024,
+            }),
+            ..samples(&[10, 20])
+        };
+        let r = BenchReport::new("sort", &s);
+        assert!(r.to_csv_row().ends_with(",5,2048,1024,,,,"));
+        assert_eq!(
+            serde_json::from_str::<BenchReport>(&r.to_json()).unwrap(),
+            r
+        );
+    }
+
+    #[test]
+    fn report_option() {
+        let args = Args::try_parse_from(["bin"]).unwrap();
+        assert!(args.report.target.is_none());
+        let args = Args::try_parse_from(["bin", "--report", "CSV", "out.csv"]).unwrap();
+        assert_eq!(
+            args.report.target,
+            Some((ReportFormat::Csv, PathBuf::from("out.csv")))
+        );
+        assert!(Args::try_parse_from(["bin", "--report", "xml", "out"]).is_err());
+        assert!(Args::try_parse_from(["bin", "--report", "json"]).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+
+use crate::{Samples, TimeOptions, Warmup};
+
+/// A benchmark that the `rpb` driver can run in-process.
+pub struct Benchmark {
+    pub name: &'static str,
+    /// the names of its algorithms, the default one first.
+    pub algorithms: Vec<String>,
+    /// the names of its own options (e.g. `bits` of `isort`).
+    pub params: &'static [&'static str],
+    /// the enabled features of the crate it is part of.
+    pub features: fn() -> Vec<String>,
+    /// the warm-up of a run whose manifest sets none.
+    pub warmup: Warmup,
+    /// times the benchmark, in the thread pool the driver installed.
+    pub run: fn(&RunSpec) -> Result<RunOutcome, RunError>,
+}
+
+/// Returns the names of the algorithms of `A`, `default` first.
+pub fn algorithm_names<A: ValueEnum>(default: A) -> Vec<String> {
+    let name = |a: &A| a.to_possible_value().unwrap().get_name().to_string();
+    let default = name(&default);
+    let mut names = vec![default.clone()];
+    names.extend(
+        A::value_variants()
+            .iter()
+            .map(name)
+            .filter(|n| *n != default),
+    );
+    names
+}
+
+/// One run of a benchmark as a suite manifest describes it.
+#[derive(Clone, Debug, Default)]
+pub struct RunSpec {
+    /// the algorithm, or the benchmark's default one.
+    pub algorithm: Option<String>,
+    pub input: String,
+    pub time: TimeOptions,
+    /// the number of threads of the pool the run is installed in.
+    pub threads: usize,
+    /// validate the result after timing.
+    pub check: bool,
+    /// the benchmark's own options, unparsed.
+    pub params: BTreeMap<String, String>,
+}
+
+impl RunSpec {
+    /// Parses the algorithm as one of `A`, or returns `default` if there is
+    /// none.
+    pub fn algorithm<A: ValueEnum>(&self, default: A) -> Result<A, RunError> {
+        match &self.algorithm {
+            None => Ok(default),
+            Some(name) => A::from_str(name, true).map_err(|_| RunError::Algorithm {
+                found: name.clone(),
+                expected: algorithm_names(default),
+            }),
+        }
+    }
+
+    /// Parses the option `name`, or returns `default` if it is not given.
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> Result<T, RunError> {
+        match self.params.get(name) {
+            None => Ok(default),
+            Some(_) => self.required_param(name),
+        }
+    }
+
+    /// Parses the option `name`, which must be given.
+    pub fn required_param<T: FromStr>(&self, name: &str) -> Result<T, RunError> {
+        let err = |reason: String| RunError::Param {
+            name: name.to_string(),
+            reason,
+        };
+        let value = self
+            .params
+            .get(name)
+            .ok_or_else(|| err("it is required".to_string()))?;
+        value
+            .parse()
+            .map_err(|_| err(format!("can not parse `{value}`")))
+    }
+}
+
+/// The result of one run of a benchmark.
+#[derive(Clone, Debug)]
+pub struct RunOutcome {
+    /// the name the algorithm is reported with.
+    pub algorithm: String,
+    pub samples: Samples,
+    /// the verdict of the check, `None` if it was not checked.
+    pub check: Option<bool>,
+}
+
+/// Why a benchmark could not be run.
+pub enum RunError {
+    /// the algorithm is not one of the benchmark's.
+    Algorithm {
+        found: String,
+        expected: Vec<String>,
+    },
+    /// an option of the benchmark is missing or malformed.
+    Param { name: String, reason: String },
+    /// the input file can not be read.
+    Input(String),
+}
+
+impl RunError {
+    /// Shorthand for an unreadable input.
+    pub fn input<E: fmt::Display>(e: E) -> Self {
+        Self::Input(e.to_string())
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Algorithm { found, expected } => write!(
+                f,
+                "unknown algorithm '{found}' [possible values: {}]",
+                expected.join(", ")
+            ),
+            Self::Param { name, reason } => write!(f, "option '{name}': {reason}"),
+            Self::Input(e) => write!(f, "cannot read input file: {e}"),
+        }
+    }
+}
+
+impl fmt::Debug for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for RunError {}
+
+#[cfg(test)]
+mod suite_tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::{PerfStats, Warmup};
+
+    #[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+    enum Algs {
+        Serial,
+        ParRange,
+    }
+
+    #[test]
+    fn algorithm_and_params() {
+        let mut spec = RunSpec::default();
+        assert_eq!(algorithm_names(Algs::ParRange), ["par-range", "serial"]);
+        assert_eq!(spec.algorithm(Algs::Serial).unwrap(), Algs::Serial);
+        spec.algorithm = Some("Par-Range".to_string());
+        assert_eq!(spec.algorithm(Algs::Serial).unwrap(), Algs::ParRange);
+        spec.algorithm = Some("quick".to_string());
+        assert_eq!(
+            spec.algorithm(Algs::Serial).unwrap_err().to_string(),
+            "unknown algorithm 'quick' [possible values: serial, par-range]"
+        );
+
+        spec.params.insert("bits".to_string(), "12".to_string());
+        spec.params.insert("stable".to_string(), "yes".to_string());
+        assert_eq!(spec.param("bits", 0usize).unwrap(), 12);
+        assert_eq!(spec.param("dimension", 2usize).unwrap(), 2);
+        assert_eq!(
+            spec.param("stable", false).unwrap_err().to_string(),
+            "option 'stable': can not parse `yes`"
+        );
+        assert_eq!(
+            spec.required_param::<usize>("buckets")
+                .unwrap_err()
+                .to_string(),
+            "option 'buckets': it is required"
+        );
+    }
+
+    #[test]
+    fn run_rounds() {
+        let opts = TimeOptions {
+            rounds: 3,
+            warmup: Warmup::Rounds(2),
+            ..TimeOptions::default()
+        };
+        let mut calls = 0;
+        let samples = opts.run_rounds(|| {
+            calls += 1;
+            let perf = PerfStats {
+                cycles: Some(calls),
+                ..PerfStats::default()
+            };
+            (Duration::from_millis(calls), Some(perf))
+        });
+        assert_eq!(calls, 5);
+        assert_eq!(samples.warmup, Duration::from_millis(3));
+        assert_eq!(samples.rounds.len(), 3);
+        // only the timed rounds count.
+        assert_eq!(samples.perf.unwrap().cycles, Some(3 + 4 + 5));
+    }
+}
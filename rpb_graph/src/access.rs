@@ -0,0 +1,118 @@
+use std::iter::Copied;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::slice::Iter;
+
+use crate::{Graph, Idx, MappedGraph, Weight};
+
+/// Read access to the neighbors of the vertices of a graph, whatever its
+/// representation. Algorithms written against it run on plain and
+/// compressed graphs alike.
+pub trait NeighborAccess: Sync {
+    type Neighbors<'a>: Iterator<Item = usize>
+    where
+        Self: 'a;
+
+    fn num_vertices(&self) -> usize;
+
+    fn num_edges(&self) -> usize;
+
+    fn degree(&self, u: usize) -> usize;
+
+    /// The neighbors of `u`, in the order the representation stores them.
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_>;
+}
+
+/// The neighbors of a vertex of an uncompressed graph.
+pub struct SliceNeighbors<'a, I>(Copied<Iter<'a, I>>);
+
+impl<I: Idx> Iterator for SliceNeighbors<'_, I> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<usize> {
+        self.0.next().map(|v| v.as_())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I: Idx> NeighborAccess for Graph<I> {
+    type Neighbors<'a>
+        = SliceNeighbors<'a, I>
+    where
+        I: 'a;
+
+    fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    fn num_edges(&self) -> usize {
+        Graph::num_edges(self)
+    }
+
+    #[inline(always)]
+    fn degree(&self, u: usize) -> usize {
+        self.index(u).degree
+    }
+
+    #[inline(always)]
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_> {
+        SliceNeighbors(self.neighbor_slice(u).iter().copied())
+    }
+}
+
+impl<I: Idx, W: Weight> NeighborAccess for MappedGraph<I, W> {
+    type Neighbors<'a>
+        = SliceNeighbors<'a, I>
+    where
+        I: 'a,
+        W: 'a;
+
+    fn num_vertices(&self) -> usize {
+        MappedGraph::num_vertices(self)
+    }
+
+    fn num_edges(&self) -> usize {
+        MappedGraph::num_edges(self)
+    }
+
+    #[inline(always)]
+    fn degree(&self, u: usize) -> usize {
+        let offsets = self.offsets();
+        offsets[u + 1].as_() - offsets[u].as_()
+    }
+
+    #[inline(always)]
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_> {
+        let offsets = self.offsets();
+        let ns = &self.edges()[offsets[u].as_()..offsets[u + 1].as_()];
+        SliceNeighbors(ns.iter().copied())
+    }
+}
@@ -0,0 +1,386 @@
+use std::fs::File;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+// The RPB binary graph format: a fixed 32 byte header followed by the raw
+// little-endian CSR arrays, so a graph can be memory mapped and used in
+// place.
+//
+// ```text
+// offset  size       field
+// 0       8          magic, `RPBCSR01`
+// 8       4          the width of an index (vertex id or offset) in bytes
+// 12      4          the width of a weight in bytes (0 if unweighted), plus
+//                    256 if the weights are floats
+// 16      8          n, the number of vertices
+// 24      8          m, the number of edges
+// 32      (n+1)*w    offsets, the last one is m
+// ...     m*w        edges
+// ...                zero padding to a multiple of 8 bytes
+// ...     m*ww       weights
+// ```
+
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::path::Path;
+
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use crate::{Graph, GraphIoError, Idx, Weight, WghGraph};
+
+// the arrays are written and mapped as they are in memory.
+#[cfg(target_endian = "big")]
+compile_error!("the binary graph format needs a little-endian target");
+
+pub const BIN_GRAPH_MAGIC: &[u8; 8] = b"RPBCSR01";
+const HEADER_LEN: usize = 32;
+const FLOAT_FLAG: u32 = 256;
+
+fn weight_kind<W: Weight>() -> u32 {
+    size_of::<W>() as u32 + if W::FLOAT { FLOAT_FLAG } else { 0 }
+}
+
+fn kind_name(kind: u32) -> String {
+    let ty = if kind >= FLOAT_FLAG {
+        "float"
+    } else {
+        "integer"
+    };
+    format!("{} byte {ty}", kind % FLOAT_FLAG)
+}
+
+const fn align8(x: usize) -> usize {
+    x.div_ceil(8) * 8
+}
+
+/// Returns where the edges and the weights start and where the file ends,
+/// for `n` vertices, `m` edges and weights of `weight_width` bytes (0 if
+/// unweighted), or `None` if a size overflows.
+fn layout<I>(n: usize, m: usize, weight_width: usize) -> Option<(usize, usize, usize)> {
+    let edges_start = n
+        .checked_add(1)?
+        .checked_mul(size_of::<I>())?
+        .checked_add(HEADER_LEN)?;
+    let edges_end = m.checked_mul(size_of::<I>())?.checked_add(edges_start)?;
+    if weight_width == 0 {
+        return Some((edges_start, edges_end, edges_end));
+    }
+    let weights_start = edges_end.checked_next_multiple_of(8)?;
+    let end = m.checked_mul(weight_width)?.checked_add(weights_start)?;
+    Some((edges_start, weights_start, end))
+}
+
+/// The raw bytes of a slice of numbers.
+fn as_bytes<T: Copy>(xs: &[T]) -> &[u8] {
+    // SAFETY: only used with the primitive numbers behind `Idx` and
+    // `Weight`, which have no padding or invalid bit patterns.
+    unsafe { std::slice::from_raw_parts(xs.as_ptr() as *const u8, std::mem::size_of_val(xs)) }
+}
+
+/// Views `len` numbers at `bytes[start..]`.
+fn view<T: Copy>(bytes: &[u8], start: usize, len: usize) -> &[T] {
+    let b = &bytes[start..][..len.checked_mul(size_of::<T>()).unwrap()];
+    assert_eq!(b.as_ptr() as usize % std::mem::align_of::<T>(), 0);
+    // SAFETY: as in `as_bytes`, and `b` is aligned and holds exactly the
+    // numbers of the returned slice.
+    unsafe { std::slice::from_raw_parts(b.as_ptr() as *const T, b.len() / size_of::<T>()) }
+}
+
+fn bin_error(reason: impl Into<String>) -> GraphIoError {
+    GraphIoError::Binary(reason.into())
+}
+
+/// Whether `path` starts with the magic of a binary graph.
+pub fn is_bin_file<P: AsRef<Path>>(path: P) -> std::io::Result<bool> {
+    let mut magic = [0u8; 8];
+    let mut file = File::open(path)?;
+    Ok(std::io::Read::read_exact(&mut file, &mut magic).is_ok() && &magic == BIN_GRAPH_MAGIC)
+}
+
+fn write_bin_file<I: Idx, W: Weight, P: AsRef<Path>>(
+    path: P,
+    offsets: &[I],
+    edges: &[I],
+    weights: Option<&[W]>,
+) -> std::io::Result<()> {
+    let (n, m) = (offsets.len() - 1, edges.len());
+    let mut f = BufWriter::new(File::create(path)?);
+    f.write_all(BIN_GRAPH_MAGIC)?;
+    f.write_all(&(size_of::<I>() as u32).to_le_bytes())?;
+    f.write_all(&weights.map_or(0, |_| weight_kind::<W>()).to_le_bytes())?;
+    f.write_all(&(n as u64).to_le_bytes())?;
+    f.write_all(&(m as u64).to_le_bytes())?;
+    f.write_all(as_bytes(offsets))?;
+    f.write_all(as_bytes(edges))?;
+    if let Some(weights) = weights {
+        let end = HEADER_LEN + (n + 1 + m) * size_of::<I>();
+        f.write_all(&vec![0u8; align8(end) - end])?;
+        f.write_all(as_bytes(weights))?;
+    }
+    f.flush()
+}
+
+/// A binary graph mapped into memory. The offsets, edges and weights are
+/// views into the file, nothing is copied.
+pub struct MappedGraph<I, W = u32> {
+    map: Mmap,
+    n: usize,
+    m: usize,
+    edges_start: usize,
+    weights_start: usize,
+    weighted: bool,
+    _marker: PhantomData<(I, W)>,
+}
+
+impl<I: Idx, W: Weight> MappedGraph<I, W> {
+    /// Maps a binary graph. Fails if the file was written with a different
+    /// index or weight type.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        Self::map(path, true)
+    }
+
+    /// Maps a binary graph, skipping the check of the weight type if the
+    /// weights are not going to be read.
+    fn map<P: AsRef<Path>>(path: P, read_weights: bool) -> Result<Self, GraphIoError> {
+        let file = File::open(path)?;
+        // SAFETY: the file must not be modified while it is mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        if map.len() < HEADER_LEN || &map[..8] != BIN_GRAPH_MAGIC {
+            return Err(bin_error("missing magic"));
+        }
+        let word = |i: usize| u32::from_le_bytes(map[i..i + 4].try_into().unwrap());
+        let long = |i: usize| {
+            let x = u64::from_le_bytes(map[i..i + 8].try_into().unwrap());
+            usize::try_from(x).map_err(|_| bin_error(format!("{x} is not a valid size")))
+        };
+        let (index_width, weight) = (word(8), word(12));
+        let (n, m) = (long(16)?, long(24)?);
+
+        if index_width as usize != size_of::<I>() {
+            return Err(bin_error(format!(
+                "the file has {index_width} byte indices, expected {}",
+                size_of::<I>()
+            )));
+        }
+        if read_weights && weight != 0 && weight != weight_kind::<W>() {
+            return Err(bin_error(format!(
+                "the file has {} weights, expected {}",
+                kind_name(weight),
+                kind_name(weight_kind::<W>())
+            )));
+        }
+        let (edges_start, weights_start, expected) =
+            layout::<I>(n, m, (weight % FLOAT_FLAG) as usize)
+                .ok_or_else(|| bin_error(format!("the sizes overflow (n={n}, m={m})")))?;
+        if map.len() != expected {
+            return Err(bin_error(format!(
+                "expected {expected} bytes, found {}",
+                map.len()
+            )));
+        }
+        Ok(Self {
+            map,
+            n,
+            m,
+            edges_start,
+            weights_start,
+            weighted: read_weights && weight != 0,
+            _marker: PhantomData,
+        })
+    }
+
+    pub const fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    pub const fn num_edges(&self) -> usize {
+        self.m
+    }
+
+    pub fn offsets(&self) -> &[I] {
+        view(&self.map, HEADER_LEN, self.n + 1)
+    }
+
+    pub fn edges(&self) -> &[I] {
+        view(&self.map, self.edges_start, self.m)
+    }
+
+    pub fn weights(&self) -> Option<&[W]> {
+        self.weighted
+            .then(|| view(&self.map, self.weights_start, self.m))
+    }
+
+    /// Checks that the offsets are monotone and end at `m`, and that all the
+    /// edges point to vertices.
+    pub fn validate(&self) -> Result<(), GraphIoError> {
+        let (offsets, edges) = (self.offsets(), self.edges());
+        if offsets[0] != I::zero()
+            || offsets[self.n].as_() != self.m
+            || offsets.par_windows(2).any(|w| w[0] > w[1])
+        {
+            return Err(bin_error("the offsets are out of order"));
+        }
+        if let Some(&t) = edges.par_iter().find_any(|t| t.as_() >= self.n) {
+            return Err(bin_error(format!("target {t} is not a vertex")));
+        }
+        Ok(())
+    }
+
+    /// Copies the graph out of the file.
+    pub fn to_graph(&self) -> Graph<I> {
+        Graph {
+            offsets: self.offsets().to_vec(),
+            edges: self.edges().to_vec(),
+            degrees: vec![],
+            n: self.n,
+            m: self.m,
+        }
+    }
+
+    /// Copies the graph out of the file. All the edges of an unweighted
+    /// graph weigh one.
+    pub fn to_wgh_graph(&self) -> WghGraph<I, W> {
+        WghGraph {
+            offsets: self.offsets().to_vec(),
+            edges: self.edges().to_vec(),
+            weights: match self.weights() {
+                Some(w) => w.to_vec(),
+                None => vec![W::one(); self.m],
+            },
+            n: self.n,
+            m: self.m,
+        }
+    }
+}
+
+impl<I: Idx> Graph<I> {
+    /// Reads a binary graph. The weights of a weighted graph are ignored.
+    pub fn from_bin_file<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        let g = MappedGraph::<I>::map(path, false)?;
+        g.validate()?;
+        Ok(g.to_graph())
+    }
+
+    /// Writes the graph in the binary format.
+    pub fn to_bin_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        write_bin_file::<I, u32, P>(path, &self.offsets, &self.edges, None)
+    }
+
+    /// Reads a text or a binary graph, told apart by the magic of the latter.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        if is_bin_file(&path)? {
+            Self::from_bin_file(path)
+        } else {
+            Self::from_file(path)
+        }
+    }
+}
+
+impl<I: Idx, W: Weight> WghGraph<I, W> {
+    /// Reads a binary graph. All the edges of an unweighted graph weigh one.
+    pub fn from_bin_file<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        let g = MappedGraph::<I, W>::open(path)?;
+        g.validate()?;
+        if g.weights().is_none() {
+            eprintln!("Warning: graph is unweighted, using 1 for all edges");
+        }
+        Ok(g.to_wgh_graph())
+    }
+
+    /// Writes the graph in the binary format.
+    pub fn to_bin_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        write_bin_file(path, &self.offsets, &self.edges, Some(&self.weights))
+    }
+
+    /// Reads a text or a binary graph, told apart by the magic of the latter.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        if is_bin_file(&path)? {
+            Self::from_bin_file(path)
+        } else {
+            Self::from_file(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use super::*;
+
+    #[test]
+    fn write_and_map() {
+        let path = std::env::temp_dir().join(format!("rpb_graph_{}_bin", std::process::id()));
+        // an odd number of 4 byte words before the weights needs padding.
+        let edges = vec![(0, 1, 1.5), (1, 0, 2.5), (1, 2, 3.5), (2, 1, 4.5)];
+        let g = WghGraph::<u32, f64>::from_edges(3, edges);
+        g.to_bin_file(&path).unwrap();
+
+        let mapped = MappedGraph::<u32, f64>::open(&path).unwrap();
+        assert!(mapped.validate().is_ok());
+        assert_eq!(mapped.offsets(), &g.offsets[..]);
+        assert_eq!(mapped.edges(), &g.edges[..]);
+        assert_eq!(mapped.weights(), Some(&g.weights[..]));
+        assert!(is_bin_file(&path).unwrap());
+
+        let e = MappedGraph::<u32, u32>::open(&path).err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "invalid binary graph: the file has 8 byte float weights, expected 4 byte integer"
+        );
+        assert!(MappedGraph::<u64, f64>::open(&path).is_err());
+
+        let h = Graph::<u32>::read(&path).unwrap();
+        assert_eq!((&h.offsets, &h.edges), (&g.offsets, &g.edges));
+
+        // an unweighted file reads with unit weights.
+        h.to_bin_file(&path).unwrap();
+        let h = WghGraph::<u32, f32>::read(&path).unwrap();
+        assert_eq!(h.edges, g.edges);
+        assert_eq!(h.weights, vec![1.0; 4]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn overflowing_header() {
+        let path = std::env::temp_dir().join(format!("rpb_graph_{}_ovf", std::process::id()));
+        let header = |n: u64, m: u64| {
+            let mut h = BIN_GRAPH_MAGIC.to_vec();
+            h.extend(4u32.to_le_bytes());
+            h.extend(0u32.to_le_bytes());
+            h.extend(n.to_le_bytes());
+            h.extend(m.to_le_bytes());
+            h
+        };
+        // (n + 1) * 4 wraps to 0, which would match the length of the file.
+        for (n, m) in [((1 << 62) - 1, 0), (0, 1 << 62), (u64::MAX, 0)] {
+            std::fs::write(&path, header(n, m)).unwrap();
+            let e = MappedGraph::<u32>::open(&path).err().unwrap();
+            assert!(e.to_string().contains("overflow"), "{e}");
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::marker::PhantomData;
+
+use parlay::internal::sequence_ops::scan_inplace;
+use parlay::primitives::flatten_by_val;
+
+use crate::{Graph, Idx, MappedGraph, NeighborAccess, Weight};
+
+// **************************************************************
+//    BYTE-CODED ADJACENCY ARRAY REPRESENTATION
+// **************************************************************
+
+// The neighbors of every vertex are sorted and stored as differences, each
+// in as few bytes as it needs (the byte code of Ligra+). The first neighbor
+// is stored relative to the vertex itself: its first byte holds a
+// continuation bit, a sign bit and six bits of the difference. The others
+// are stored relative to the previous neighbor, seven bits per byte with
+// the top bit set if more bytes follow. Graphs with local ids compress to
+// about a byte per edge.
+
+const MORE: u8 = 0x80;
+const SIGN: u8 = 0x40;
+
+fn encode_varint(out: &mut Vec<u8>, mut x: usize) {
+    while x >= MORE as usize {
+        out.push(x as u8 | MORE);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+fn encode_neighbors<I: Idx>(u: usize, neighbors: &[I]) -> Vec<u8> {
+    let mut ns: Vec<usize> = neighbors.iter().map(|v| v.as_()).collect();
+    ns.sort_unstable();
+    let mut out = Vec::with_capacity(ns.len() + 4);
+    if let Some(&first) = ns.first() {
+        let (diff, sign) = match first >= u {
+            true => (first - u, 0),
+            false => (u - first, SIGN),
+        };
+        let low = (diff & 0x3f) as u8 | sign;
+        if diff < 0x40 {
+            out.push(low);
+        } else {
+            out.push(low | MORE);
+            encode_varint(&mut out, diff >> 6);
+        }
+    }
+    for w in ns.windows(2) {
+        encode_varint(&mut out, w[1] - w[0]);
+    }
+    out
+}
+
+/// A graph whose adjacency lists are difference encoded and byte coded. It
+/// takes a fraction of the memory of a [`Graph`], at the cost of decoding
+/// the neighbors on every visit.
+pub struct CompressedGraph<I> {
+    /// the start of the neighbors of every vertex in `bytes`.
+    pub offsets: Vec<usize>,
+    pub degrees: Vec<I>,
+    pub bytes: Vec<u8>,
+    pub n: usize,
+    pub m: usize,
+}
+
+impl<I: Idx> CompressedGraph<I> {
+    /// Compresses the graph whose vertex `u` has the neighbors `neighbors(u)`.
+    fn encode<'a>(n: usize, neighbors: impl Fn(usize) -> &'a [I] + Sync) -> Self
+    where
+        I: 'a,
+    {
+        let lists: Vec<Vec<u8>> = (0..n)
+            .into_par_iter()
+            .map(|u| encode_neighbors(u, neighbors(u)))
+            .collect();
+        let degrees: Vec<I> = (0..n)
+            .into_par_iter()
+            .map(|u| I::from(neighbors(u).len()).unwrap())
+            .collect();
+        let mut offsets: Vec<usize> = lists.par_iter().map(|l| l.len()).chain([0]).collect();
+        scan_inplace(&mut offsets, false, |a, b| a + b);
+        let mut bytes = vec![];
+        flatten_by_val(&lists, &mut bytes);
+
+        Self {
+            offsets,
+            m: degrees.par_iter().map(|d| d.as_()).sum(),
+            degrees,
+            bytes,
+            n,
+        }
+    }
+
+    pub const fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    pub const fn num_edges(&self) -> usize {
+        self.m
+    }
+
+    /// The size of the compressed adjacency lists in bytes.
+    pub fn num_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Decompresses the graph. The neighbors of every vertex come out sorted.
+    pub fn to_graph(&self) -> Graph<I> {
+        let edges = (0..self.n)
+            .into_par_iter()
+            .flat_map_iter(|u| {
+                let s = I::from(u).unwrap();
+                self.neighbors(u).map(move |v| (s, I::from(v).unwrap()))
+            })
+            .collect();
+        Graph::from_edges(self.n, edges)
+    }
+}
+
+impl<I: Idx> From<&Graph<I>> for CompressedGraph<I> {
+    fn from(g: &Graph<I>) -> Self {
+        Self::encode(g.n, |u| g.neighbor_slice(u))
+    }
+}
+
+impl<I: Idx, W: Weight> From<&MappedGraph<I, W>> for CompressedGraph<I> {
+    /// Compresses a mapped graph without loading all of it in memory.
+    fn from(g: &MappedGraph<I, W>) -> Self {
+        let (offsets, edges) = (g.offsets(), g.edges());
+        Self::encode(g.num_vertices(), |u| {
+            &edges[offsets[u].as_()..offsets[u + 1].as_()]
+        })
+    }
+}
+
+/// Decodes the neighbors of a vertex of a [`CompressedGraph`].
+pub struct ByteNeighbors<'a, I> {
+    bytes: &'a [u8],
+    pos: usize,
+    remaining: usize,
+    /// the previous neighbor, or the vertex before the first one.
+    prev: usize,
+    first: bool,
+    _marker: PhantomData<I>,
+}
+
+impl<I> ByteNeighbors<'_, I> {
+    #[inline(always)]
+    fn byte(&mut self) -> u8 {
+        let b = self.bytes[self.pos];
+        self.pos += 1;
+        b
+    }
+
+    #[inline(always)]
+    fn varint(&mut self, mut x: usize, mut shift: u32) -> usize {
+        loop {
+            let b = self.byte();
+            x |= ((b & !MORE) as usize) << shift;
+            if b & MORE == 0 {
+                return x;
+            }
+            shift += 7;
+        }
+    }
+}
+
+impl<I> Iterator for ByteNeighbors<'_, I> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.prev = if self.first {
+            self.first = false;
+            let b = self.byte();
+            let low = (b & 0x3f) as usize;
+            let diff = match b & MORE {
+                0 => low,
+                _ => self.varint(low, 6),
+            };
+            match b & SIGN {
+                0 => self.prev + diff,
+                _ => self.prev - diff,
+            }
+        } else {
+            self.prev + self.varint(0, 0)
+        };
+        Some(self.prev)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I: Idx> NeighborAccess for CompressedGraph<I> {
+    type Neighbors<'a>
+        = ByteNeighbors<'a, I>
+    where
+        I: 'a;
+
+    fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    fn num_edges(&self) -> usize {
+        self.m
+    }
+
+    #[inline(always)]
+    fn degree(&self, u: usize) -> usize {
+        self.degrees[u].as_()
+    }
+
+    #[inline(always)]
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_> {
+        ByteNeighbors {
+            bytes: &self.bytes,
+            pos: self.offsets[u],
+            remaining: self.degrees[u].as_(),
+            prev: u,
+            first: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod compressed_tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let edges = vec![
+            (0u32, 5u32),
+            (0, 1),
+            (0, 1),
+            (3, 0),
+            (3, 70_000),
+            (3, 2),
+            (70_000, 3),
+            (70_000, 69_999),
+            (70_000, 0),
+        ];
+        let g = Graph::from_edges(70_001, edges);
+        let c = CompressedGraph::from(&g);
+        assert_eq!(c.num_edges(), 9);
+        assert_eq!(c.degree(3), 3);
+        assert_eq!(c.neighbors(0).collect::<Vec<_>>(), [1, 1, 5]);
+        assert_eq!(c.neighbors(3).collect::<Vec<_>>(), [0, 2, 70_000]);
+        assert_eq!(c.neighbors(70_000).collect::<Vec<_>>(), [0, 3, 69_999]);
+        assert_eq!(c.neighbors(1).count(), 0);
+
+        let mut sorted = g.to_edges();
+        sorted.sort_unstable();
+        assert_eq!(c.to_graph().to_edges(), sorted);
+    }
+}
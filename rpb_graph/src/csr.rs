@@ -0,0 +1,257 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::{Idx, Weight};
+
+// **************************************************************
+//    ADJACENCY ARRAY REPRESENTATION
+// **************************************************************
+
+pub struct Vertex<'a, I> {
+    pub neighbors: &'a [I],
+    pub degree: usize,
+}
+
+impl<'a, I> Vertex<'a, I> {
+    pub fn new(n: &'a [I], d: usize) -> Self {
+        Self {
+            neighbors: n,
+            degree: d,
+        }
+    }
+}
+
+impl<I> Default for Vertex<'_, I> {
+    fn default() -> Self {
+        Self {
+            neighbors: &[],
+            degree: 0,
+        }
+    }
+}
+
+/// A graph in compressed sparse row (CSR) form: the neighbors of vertex `i`
+/// are `edges[offsets[i]..offsets[i + 1]]`.
+pub struct Graph<I> {
+    pub offsets: Vec<I>,
+    pub edges: Vec<I>,
+    pub degrees: Vec<I>,
+    pub n: usize,
+    pub m: usize,
+}
+
+impl<I: Idx> Graph<I> {
+    pub const fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    pub fn num_edges(&self) -> usize {
+        if self.degrees.is_empty() {
+            self.m
+        } else {
+            self.degrees.par_iter().map(|d| d.as_()).sum()
+        }
+    }
+
+    pub const fn get_offsets(&self) -> &Vec<I> {
+        &self.offsets
+    }
+
+    pub fn add_degrees(&mut self) {
+        debug_assert!(self.degrees.is_empty());
+        self.degrees = (0..self.n)
+            .into_par_iter()
+            .map(|i| self.offsets[i + 1] - self.offsets[i])
+            .collect();
+    }
+
+    pub fn new(offsets: &[I], edges: &[I], n: usize) -> Self {
+        debug_assert_eq!(n + 1, offsets.len());
+        debug_assert_eq!(edges.len(), offsets[n].as_());
+
+        Self {
+            offsets: offsets.to_vec(),
+            edges: edges.to_vec(),
+            n,
+            m: edges.len(),
+            degrees: vec![],
+        }
+    }
+
+    #[inline(always)]
+    #[allow(mismatched_lifetime_syntaxes)]
+    pub fn index(&self, i: usize) -> Vertex<I> {
+        debug_assert!(i < self.n);
+
+        let (of, of_next) = (self.offsets[i].as_(), self.offsets[i + 1].as_());
+
+        let d = match self.degrees.len() {
+            0 => of_next - of,
+            _ => self.degrees[i].as_(),
+        };
+        let n = &self.edges[of..of_next];
+
+        Vertex {
+            neighbors: n,
+            degree: d,
+        }
+    }
+
+    /// The neighbors of `u`, without the ones past its degree.
+    pub(crate) fn neighbor_slice(&self, u: usize) -> &[I] {
+        let v = self.index(u);
+        &v.neighbors[..v.degree]
+    }
+
+    /// Builds the graph of the `(source, target)` edges on `n` vertices.
+    /// The edges of a vertex keep their relative order.
+    pub fn from_edges(n: usize, mut edges: Vec<(I, I)>) -> Self {
+        debug_assert!(edges.par_iter().all(|e| e.0.as_() < n && e.1.as_() < n));
+        edges.par_sort_by_key(|e| e.0);
+
+        let offsets = csr_offsets(n, &edges, |e| e.0);
+        let edges: Vec<I> = edges.into_par_iter().map(|e| e.1).collect();
+
+        Self {
+            offsets,
+            m: edges.len(),
+            edges,
+            degrees: vec![],
+            n,
+        }
+    }
+
+    /// Lists the edges as `(source, target)`, ordered by source.
+    pub fn to_edges(&self) -> Vec<(I, I)> {
+        (0..self.n)
+            .into_par_iter()
+            .flat_map_iter(|u| {
+                let ns = self.neighbor_slice(u);
+                let u = I::from(u).unwrap();
+                ns.iter().map(move |&t| (u, t))
+            })
+            .collect()
+    }
+}
+
+/// The offsets of `n` vertices given the edges sorted by source.
+fn csr_offsets<I: Idx, E: Sync>(n: usize, edges: &[E], source: impl Fn(&E) -> I + Sync) -> Vec<I> {
+    (0..=n)
+        .into_par_iter()
+        .map(|v| I::from(edges.partition_point(|e| source(e).as_() < v)).unwrap())
+        .collect()
+}
+
+// **************************************************************
+//    WEIGHTED ADJACENCY ARRAY REPRESENTATION
+// **************************************************************
+
+pub struct WghVertex<'a, I, W> {
+    pub neighbors: &'a [I],
+    pub weights: &'a [W],
+    pub degree: usize,
+}
+
+/// A weighted graph in CSR form: the edge `edges[j]` weighs `weights[j]`.
+pub struct WghGraph<I, W> {
+    pub offsets: Vec<I>,
+    pub edges: Vec<I>,
+    pub weights: Vec<W>,
+    pub n: usize,
+    pub m: usize,
+}
+
+impl<I: Idx, W: Weight> WghGraph<I, W> {
+    pub const fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    pub const fn num_edges(&self) -> usize {
+        self.m
+    }
+
+    pub fn new(offsets: &[I], edges: &[I], weights: &[W], n: usize) -> Self {
+        debug_assert_eq!(n + 1, offsets.len());
+        debug_assert_eq!(edges.len(), offsets[n].as_());
+        debug_assert_eq!(edges.len(), weights.len());
+
+        Self {
+            offsets: offsets.to_vec(),
+            edges: edges.to_vec(),
+            weights: weights.to_vec(),
+            n,
+            m: edges.len(),
+        }
+    }
+
+    #[inline(always)]
+    #[allow(mismatched_lifetime_syntaxes)]
+    pub fn index(&self, i: usize) -> WghVertex<I, W> {
+        debug_assert!(i < self.n);
+
+        let (of, of_next) = (self.offsets[i].as_(), self.offsets[i + 1].as_());
+
+        WghVertex {
+            neighbors: &self.edges[of..of_next],
+            weights: &self.weights[of..of_next],
+            degree: of_next - of,
+        }
+    }
+
+    /// Builds the graph of the `(source, target, weight)` edges on `n`
+    /// vertices. The edges of a vertex keep their relative order.
+    pub fn from_edges(n: usize, mut edges: Vec<(I, I, W)>) -> Self {
+        debug_assert!(edges.par_iter().all(|e| e.0.as_() < n && e.1.as_() < n));
+        edges.par_sort_by_key(|e| e.0);
+
+        let offsets = csr_offsets(n, &edges, |e| e.0);
+        let (edges, weights): (Vec<I>, Vec<W>) = edges.into_par_iter().map(|e| (e.1, e.2)).unzip();
+
+        Self {
+            offsets,
+            m: edges.len(),
+            edges,
+            weights,
+            n,
+        }
+    }
+
+    /// Lists the edges as `(source, target, weight)`, ordered by source.
+    pub fn to_edges(&self) -> Vec<(I, I, W)> {
+        (0..self.n)
+            .into_par_iter()
+            .flat_map_iter(|u| {
+                let v = self.index(u);
+                let u = I::from(u).unwrap();
+                v.neighbors
+                    .iter()
+                    .zip(v.weights)
+                    .map(move |(&t, &w)| (u, t, w))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::io;
+
+/// An error while reading a graph file. Line numbers start at one.
+pub enum GraphIoError {
+    Io(io::Error),
+    /// the file does not start with an expected header.
+    Header {
+        found: String,
+    },
+    /// a token is not a valid number.
+    Parse {
+        line: usize,
+        token: String,
+    },
+    /// a number is not a whole number in the range of the integer weights.
+    Weight {
+        line: usize,
+        token: String,
+    },
+    /// a line has the wrong number of fields.
+    Fields {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// the number of values does not match the counts in the header.
+    Count {
+        expected: usize,
+        found: usize,
+    },
+    /// an offset is smaller than the previous one or past the last edge.
+    Offset {
+        line: usize,
+        offset: usize,
+    },
+    /// the vertex ids do not fit the index type.
+    Overflow {
+        n: usize,
+    },
+    /// the edge offsets do not fit the index type.
+    EdgeOverflow {
+        m: usize,
+    },
+    /// a binary graph is malformed or of other types.
+    Binary(String),
+    /// an edge points past the last vertex.
+    Target {
+        line: usize,
+        target: usize,
+        n: usize,
+    },
+}
+
+impl fmt::Display for GraphIoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Header { found } => write!(f, "line 1: unknown header {found:?}"),
+            Self::Parse { line, token } => write!(f, "line {line}: can not parse {token:?}"),
+            Self::Weight { line, token } => {
+                write!(f, "line {line}: {token:?} is not a valid integer weight")
+            }
+            Self::Fields {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} fields, found {found}"),
+            Self::Count { expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} values after the header, found {found}"
+                )
+            }
+            Self::Offset { line, offset } => write!(f, "line {line}: offset {offset} out of order"),
+            Self::Overflow { n } => write!(f, "{n} vertices do not fit the index type"),
+            Self::EdgeOverflow { m } => write!(f, "{m} edges do not fit the index type"),
+            Self::Binary(reason) => write!(f, "invalid binary graph: {reason}"),
+            Self::Target { line, target, n } => {
+                write!(f, "line {line}: target {target} is not a vertex (n={n})")
+            }
+        }
+    }
+}
+
+impl fmt::Debug for GraphIoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for GraphIoError {}
+
+impl From<io::Error> for GraphIoError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -0,0 +1,228 @@
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::io::{BufRead, BufReader};
+
+use rpb_graph::*;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// (Weighted)AdjacencyGraph text
+    Adj,
+    /// (Weighted)EdgeArray text
+    Edges,
+    /// the RPB binary CSR format
+    Bin,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum WeightType {
+    U32,
+    F32,
+}
+
+/// Converts graphs between the PBBS text formats and the binary format, and
+/// imports SNAP edge lists and MatrixMarket files. The input format is
+/// detected from the file. Weighted inputs give weighted outputs; the edges
+/// are kept as they are unless the options below are given.
+#[derive(Parser)]
+struct Args {
+    /// the input filename
+    input: String,
+    /// the output filename
+    output: String,
+    /// the output format
+    #[arg(short, long)]
+    to: Format,
+    /// the type of the weights (integer weights reject fractional ones)
+    #[arg(short, long, value_enum, default_value = "u32")]
+    weights: WeightType,
+    /// drop the weights of a weighted input
+    #[arg(long)]
+    unweighted: bool,
+    /// add the reverse of every edge
+    #[arg(long)]
+    symmetrize: bool,
+    /// drop the self-loops
+    #[arg(long)]
+    remove_self_loops: bool,
+    /// keep one of the parallel edges
+    #[arg(long)]
+    remove_duplicates: bool,
+    /// (SNAP and MatrixMarket inputs) map the vertex ids to a dense range
+    #[arg(long)]
+    relabel: bool,
+    /// (unweighted graphs) rename the vertices by decreasing degree
+    #[arg(long)]
+    degree_order: bool,
+}
+
+impl Args {
+    fn import_options(&self) -> ImportOptions {
+        ImportOptions {
+            symmetrize: self.symmetrize,
+            remove_self_loops: self.remove_self_loops,
+            remove_duplicates: self.remove_duplicates,
+            relabel: self.relabel,
+        }
+    }
+}
+
+enum Input<W> {
+    Graph(Graph<u32>),
+    Wgh(WghGraph<u32, W>),
+}
+
+fn num_vertices<I: Idx>(edges: impl Iterator<Item = (I, I)>) -> usize {
+    edges.map(|(u, v)| u.max(v).as_() + 1).max().unwrap_or(0)
+}
+
+/// Reads the graph in `path`, and whether it is already cleaned up (as an
+/// imported edge list is, by `import_edge_list`).
+fn read<W: Weight>(path: &str, options: &ImportOptions) -> Result<(Input<W>, bool), GraphIoError> {
+    if is_bin_file(path)? {
+        let g = MappedGraph::<u32, W>::open(path)?;
+        g.validate()?;
+        return Ok((
+            match g.weights() {
+                Some(_) => Input::Wgh(g.to_wgh_graph()),
+                None => Input::Graph(g.to_graph()),
+            },
+            false,
+        ));
+    }
+
+    let mut header = String::new();
+    BufReader::new(std::fs::File::open(path)?).read_line(&mut header)?;
+    let g = match header.trim() {
+        ADJ_GRAPH_HEADER => Input::Graph(Graph::from_file(path)?),
+        WGH_ADJ_GRAPH_HEADER => Input::Wgh(WghGraph::from_file(path)?),
+        EDGE_ARRAY_HEADER => {
+            let es = read_edge_array(path)?;
+            Input::Graph(Graph::from_edges(num_vertices(es.iter().copied()), es))
+        }
+        WGH_EDGE_ARRAY_HEADER => {
+            let es = read_wgh_edge_array(path)?;
+            let n = num_vertices(es.iter().map(|e| (e.0, e.1)));
+            Input::Wgh(WghGraph::from_edges(n, es))
+        }
+        _ => {
+            let es = import_edge_list(path, options)?;
+            let g = match es.weighted {
+                true => Input::Wgh(es.to_wgh_graph()),
+                false => Input::Graph(es.to_graph()),
+            };
+            return Ok((g, true));
+        }
+    };
+    Ok((g, false))
+}
+
+fn write<W: Weight>(g: &Input<W>, to: Format, path: &str) -> std::io::Result<()> {
+    match (g, to) {
+        (Input::Graph(g), Format::Adj) => g.to_file(path),
+        (Input::Graph(g), Format::Edges) => write_edge_array(path, &g.to_edges()),
+        (Input::Graph(g), Format::Bin) => g.to_bin_file(path),
+        (Input::Wgh(g), Format::Adj) => g.to_file(path),
+        (Input::Wgh(g), Format::Edges) => write_wgh_edge_array(path, &g.to_edges()),
+        (Input::Wgh(g), Format::Bin) => g.to_bin_file(path),
+    }
+}
+
+/// Reports an error about the file `path` and exits.
+fn fail(path: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("{path}: {e}");
+    std::process::exit(1);
+}
+
+/// Applies the clean up options to a graph that was not imported.
+fn clean_up<W: Weight>(g: Input<W>, args: &Args) -> Input<W> {
+    match g {
+        Input::Graph(mut g) => {
+            if args.remove_self_loops {
+                g = g.remove_self_loops();
+            }
+            if args.symmetrize {
+                g = g.symmetrize();
+            } else if args.remove_duplicates {
+                g = g.remove_multi_edges();
+            }
+            Input::Graph(g)
+        }
+        Input::Wgh(g) => {
+            let options = ImportOptions {
+                relabel: false,
+                ..args.import_options()
+            };
+            let es = EdgeList::new(g.n, g.to_edges(), true, &options)
+                .unwrap_or_else(|e| fail(&args.input, e));
+            Input::Wgh(es.to_wgh_graph())
+        }
+    }
+}
+
+fn convert<W: Weight>(args: &Args) {
+    let (mut g, imported) =
+        read::<W>(&args.input, &args.import_options()).unwrap_or_else(|e| fail(&args.input, e));
+    if let (true, Input::Wgh(w)) = (args.unweighted, &mut g) {
+        g = Input::Graph(Graph {
+            offsets: std::mem::take(&mut w.offsets),
+            edges: std::mem::take(&mut w.edges),
+            degrees: vec![],
+            n: w.n,
+            m: w.m,
+        });
+    }
+    if !imported && (args.symmetrize || args.remove_self_loops || args.remove_duplicates) {
+        g = clean_up(g, args);
+    }
+    if args.degree_order {
+        match g {
+            Input::Graph(ref h) => g = Input::Graph(h.relabel_by_degree().0),
+            Input::Wgh(_) => Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--degree-order needs an unweighted graph (add --unweighted)",
+                )
+                .exit(),
+        }
+    }
+    let (n, m) = match &g {
+        Input::Graph(g) => (g.n, g.m),
+        Input::Wgh(g) => (g.n, g.m),
+    };
+    write(&g, args.to, &args.output).unwrap_or_else(|e| fail(&args.output, e));
+    println!("wrote {} (n={n}, m={m})", args.output);
+}
+
+fn main() {
+    let args = Args::parse();
+    match args.weights {
+        WeightType::U32 => convert::<u32>(&args),
+        WeightType::F32 => convert::<f32>(&args),
+    }
+}
@@ -0,0 +1,370 @@
+use std::path::Path;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::group_by::remove_duplicates;
+use parlay::internal::integer_sort::integer_sort;
+use rayon::prelude::*;
+
+use crate::io::{numbered_lines, parse, parse_all, parse_weight, Token};
+use crate::{Graph, GraphIoError, Idx, Weight, WghGraph};
+
+pub const MATRIX_MARKET_HEADER: &str = "%%MatrixMarket";
+
+/// How to clean up an imported edge list. Everything is off by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ImportOptions {
+    /// add the reverse of every edge.
+    pub symmetrize: bool,
+    /// drop the edges from a vertex to itself.
+    pub remove_self_loops: bool,
+    /// keep one of the parallel edges, the lightest.
+    pub remove_duplicates: bool,
+    /// map the vertex ids that occur to `0..n`, keeping their order.
+    pub relabel: bool,
+}
+
+/// An imported list of edges on `n` vertices. The edges of an unweighted
+/// input weigh one.
+pub struct EdgeList<I, W> {
+    pub n: usize,
+    pub edges: Vec<(I, I, W)>,
+    pub weighted: bool,
+}
+
+impl<I: Idx, W: Weight> EdgeList<I, W> {
+    /// Cleans up the `edges` of a graph with (at least) `n` vertices as the
+    /// `options` ask, like an imported file.
+    pub fn new(
+        n: usize,
+        edges: Vec<(I, I, W)>,
+        weighted: bool,
+        options: &ImportOptions,
+    ) -> Result<Self, GraphIoError> {
+        let edges = edges
+            .into_par_iter()
+            .map(|(u, v, w)| (u.as_() as u64, v.as_() as u64, w))
+            .collect();
+        let raw = RawEdges {
+            n: Some(n),
+            edges,
+            weighted,
+            symmetric: false,
+        };
+        clean_up(raw, options)
+    }
+
+    pub fn to_pairs(&self) -> Vec<(I, I)> {
+        self.edges.par_iter().map(|e| (e.0, e.1)).collect()
+    }
+
+    pub fn to_graph(&self) -> Graph<I> {
+        Graph::from_edges(self.n, self.to_pairs())
+    }
+
+    pub fn to_wgh_graph(&self) -> WghGraph<I, W> {
+        WghGraph::from_edges(self.n, self.edges.clone())
+    }
+}
+
+/// The edges as read from the file, before the clean up.
+struct RawEdges<W> {
+    /// the number of vertices the file declares, if it does.
+    n: Option<usize>,
+    edges: Vec<(u64, u64, W)>,
+    weighted: bool,
+    /// the file only lists one direction of every edge.
+    symmetric: bool,
+}
+
+fn parse_edge_lines<W: Weight>(
+    lines: &[Token],
+    weighted: bool,
+    base: u64,
+) -> Result<Vec<(u64, u64, W)>, GraphIoError> {
+    let fields = if weighted { 3 } else { 2 };
+    parse_all(lines, |(line, l)| {
+        let w = l.split_whitespace().collect::<Vec<_>>();
+        if w.len() < fields {
+            return Err(GraphIoError::Fields {
+                line,
+                expected: fields,
+                found: w.len(),
+            });
+        }
+        let id = |t: &str| -> Result<u64, GraphIoError> {
+            let x: u64 = parse((line, t))?;
+            x.checked_sub(base).ok_or_else(|| GraphIoError::Parse {
+                line,
+                token: t.to_string(),
+            })
+        };
+        let wgh = if weighted {
+            parse_weight((line, w[2]))?
+        } else {
+            W::one()
+        };
+        Ok((id(w[0])?, id(w[1])?, wgh))
+    })
+}
+
+/// Reads a SNAP edge list: `#` comments and one `u v` pair per line. A third
+/// column, if every line has one, is read as the weight.
+fn read_snap<W: Weight>(contents: &str) -> Result<RawEdges<W>, GraphIoError> {
+    let lines = numbered_lines(contents, 1)
+        .into_par_iter()
+        .filter(|(_, l)| !l.starts_with('#'))
+        .collect::<Vec<_>>();
+    let weighted = !lines.is_empty()
+        && lines
+            .par_iter()
+            .all(|(_, l)| l.split_whitespace().count() > 2);
+    Ok(RawEdges {
+        n: None,
+        edges: parse_edge_lines(&lines, weighted, 0)?,
+        weighted,
+        symmetric: false,
+    })
+}
+
+/// Reads a MatrixMarket coordinate matrix. Entry `(i, j)` is the edge from
+/// vertex `i - 1` to `j - 1`.
+fn read_matrix_market<W: Weight>(contents: &str) -> Result<RawEdges<W>, GraphIoError> {
+    let (header, body) = contents.split_once('\n').unwrap_or((contents, ""));
+    let banner = header.split_whitespace().collect::<Vec<_>>();
+    let unsupported = || GraphIoError::Header {
+        found: header.trim().to_string(),
+    };
+    if banner.len() != 5 || banner[1] != "matrix" || banner[2] != "coordinate" {
+        return Err(unsupported());
+    }
+    let weighted = match banner[3] {
+        "pattern" => false,
+        "real" | "integer" => true,
+        _ => return Err(unsupported()),
+    };
+    let symmetric = match banner[4] {
+        "general" => false,
+        "symmetric" => true,
+        _ => return Err(unsupported()),
+    };
+
+    let lines = numbered_lines(body, 2)
+        .into_par_iter()
+        .filter(|(_, l)| !l.starts_with('%'))
+        .collect::<Vec<_>>();
+    let Some((&(line, size), entries)) = lines.split_first() else {
+        return Err(GraphIoError::Count {
+            expected: 1,
+            found: 0,
+        });
+    };
+    let size = size
+        .split_whitespace()
+        .map(|t| parse::<usize>((line, t)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if size.len() != 3 {
+        return Err(GraphIoError::Fields {
+            line,
+            expected: 3,
+            found: size.len(),
+        });
+    }
+    if entries.len() != size[2] {
+        return Err(GraphIoError::Count {
+            expected: size[2],
+            found: entries.len(),
+        });
+    }
+    Ok(RawEdges {
+        n: Some(size[0].max(size[1])),
+        edges: parse_edge_lines(entries, weighted, 1)?,
+        weighted,
+        symmetric,
+    })
+}
+
+/// Maps the ids that occur in `edges` to `0..n`, keeping their order, and
+/// returns `n`.
+fn relabel<W: Weight>(edges: &mut [(u64, u64, W)]) -> usize {
+    let ids = edges
+        .par_iter()
+        .flat_map_iter(|e| [e.0, e.1])
+        .collect::<Vec<_>>();
+    let mut unique = vec![];
+    remove_duplicates(&ids, &mut unique);
+    if unique.par_iter().all(|&id| id <= u32::MAX as u64) {
+        let mut sorted = vec![];
+        integer_sort(&unique, &|id| id as u32, 0, &mut sorted);
+        unique = sorted;
+    } else {
+        unique.par_sort_unstable();
+    }
+
+    let label = |id| unique.binary_search(&id).unwrap() as u64;
+    edges.par_iter_mut().for_each(|e| {
+        e.0 = label(e.0);
+        e.1 = label(e.1);
+    });
+    unique.len()
+}
+
+fn clean_up<I: Idx, W: Weight>(
+    raw: RawEdges<W>,
+    options: &ImportOptions,
+) -> Result<EdgeList<I, W>, GraphIoError> {
+    let mut edges = raw.edges;
+    if options.remove_self_loops {
+        edges.retain(|e| e.0 != e.1);
+    }
+    let n = if options.relabel {
+        relabel(&mut edges)
+    } else {
+        let max = edges.par_iter().map(|e| e.0.max(e.1) + 1).max();
+        raw.n.unwrap_or(0).max(max.unwrap_or(0) as usize)
+    };
+    if n > 0 && I::from(n - 1).is_none() {
+        return Err(GraphIoError::Overflow { n });
+    }
+
+    if options.symmetrize || raw.symmetric {
+        let reversed = edges
+            .par_iter()
+            .filter(|e| e.0 != e.1)
+            .map(|&(u, v, w)| (v, u, w))
+            .collect::<Vec<_>>();
+        edges.extend(reversed);
+    }
+    if options.remove_duplicates {
+        edges.par_sort_unstable_by(|a, b| {
+            (a.0, a.1)
+                .cmp(&(b.0, b.1))
+                .then(a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        edges = (0..edges.len())
+            .into_par_iter()
+            .filter(|&i| i == 0 || (edges[i - 1].0, edges[i - 1].1) != (edges[i].0, edges[i].1))
+            .map(|i| edges[i])
+            .collect();
+    }
+
+    let to_idx = |x: u64| I::from(x).unwrap();
+    Ok(EdgeList {
+        n,
+        edges: edges
+            .into_par_iter()
+            .map(|(u, v, w)| (to_idx(u), to_idx(v), w))
+            .collect(),
+        weighted: raw.weighted,
+    })
+}
+
+/// Imports a SNAP edge list, or a MatrixMarket file if it starts with the
+/// `%%MatrixMarket` banner. The entries of a symmetric matrix are always
+/// symmetrized.
+pub fn import_edge_list<I, W, P>(
+    path: P,
+    options: &ImportOptions,
+) -> Result<EdgeList<I, W>, GraphIoError>
+where
+    I: Idx,
+    W: Weight,
+    P: AsRef<Path>,
+{
+    let contents = std::fs::read_to_string(path)?;
+    let raw = if contents.starts_with(MATRIX_MARKET_HEADER) {
+        read_matrix_market(&contents)?
+    } else {
+        read_snap(&contents)?
+    };
+    clean_up(raw, options)
+}
+
+#[cfg(test)]
+mod import_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn import(contents: &str, options: ImportOptions) -> EdgeList<u32, f32> {
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+        let id = FILES.fetch_add(1, Ordering::Relaxed);
+        let path =
+            std::env::temp_dir().join(format!("rpb_graph_{}_import_{id}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let es = import_edge_list(&path, &options).unwrap();
+        std::fs::remove_file(path).unwrap();
+        es
+    }
+
+    #[test]
+    fn snap() {
+        let snap = "# Directed graph\n# FromNodeId\tToNodeId\n10\t30\n30\t10\n10\t10\n10\t30\n";
+        let es = import(snap, ImportOptions::default());
+        assert_eq!((es.n, es.edges.len(), es.weighted), (31, 4, false));
+
+        let es = import(
+            snap,
+            ImportOptions {
+                remove_self_loops: true,
+                remove_duplicates: true,
+                relabel: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(es.n, 2);
+        assert_eq!(es.to_pairs(), vec![(0, 1), (1, 0)]);
+
+        let relabel = ImportOptions {
+            relabel: true,
+            ..Default::default()
+        };
+        assert_eq!(import("# empty\n", relabel).n, 0);
+        assert_eq!(import("7 7\n", relabel).to_pairs(), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn matrix_market() {
+        let mtx = "%%MatrixMarket matrix coordinate real symmetric\n% comment\n3 3 3\n2 1 0.5\n3 1 2\n3 3 1\n";
+        let es = import(mtx, ImportOptions::default());
+        assert_eq!(es.n, 3);
+        assert!(es.weighted);
+        let g = es.to_wgh_graph();
+        assert_eq!(g.offsets, vec![0, 2, 3, 5]);
+        assert_eq!(g.edges, vec![1, 2, 0, 0, 2]);
+        assert_eq!(g.weights, vec![0.5, 2.0, 0.5, 2.0, 1.0]);
+
+        let mtx = "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 2\n1 2\n";
+        let es = import(
+            mtx,
+            ImportOptions {
+                symmetrize: true,
+                remove_duplicates: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(es.to_pairs(), vec![(0, 1), (1, 0)]);
+    }
+}
@@ -0,0 +1,492 @@
+use std::fmt::Display;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::{Graph, GraphIoError, Idx, Weight, WghGraph};
+
+pub const ADJ_GRAPH_HEADER: &str = "AdjacencyGraph";
+pub const WGH_ADJ_GRAPH_HEADER: &str = "WeightedAdjacencyGraph";
+pub const EDGE_ARRAY_HEADER: &str = "EdgeArray";
+pub const WGH_EDGE_ARRAY_HEADER: &str = "WeightedEdgeArray";
+
+/// A token (or line) of a file with its line number.
+pub(crate) type Token<'a> = (usize, &'a str);
+
+/// Splits `contents` into its header (the first line) and the numbered lines
+/// after it. Blank lines are dropped.
+pub(crate) fn split_lines(contents: &str) -> (&str, Vec<Token<'_>>) {
+    let (header, body) = contents.split_once('\n').unwrap_or((contents, ""));
+    (header.trim(), numbered_lines(body, 2))
+}
+
+/// The trimmed, non-blank lines of `body`, numbered from `first`.
+pub(crate) fn numbered_lines(body: &str, first: usize) -> Vec<Token<'_>> {
+    let lines = body.par_lines().collect::<Vec<_>>();
+    lines
+        .into_par_iter()
+        .enumerate()
+        .map(|(i, l)| (i + first, l.trim()))
+        .filter(|(_, l)| !l.is_empty())
+        .collect()
+}
+
+/// Parses every item with `f`. On failure, reports the error of the first
+/// bad item.
+pub(crate) fn parse_all<T, F>(items: &[Token], f: F) -> Result<Vec<T>, GraphIoError>
+where
+    T: Send,
+    F: Fn(Token) -> Result<T, GraphIoError> + Sync,
+{
+    items
+        .par_iter()
+        .map(|&t| f(t).ok())
+        .collect::<Option<Vec<T>>>()
+        .ok_or_else(|| items.par_iter().find_map_first(|&t| f(t).err()).unwrap())
+}
+
+pub(crate) fn parse<T: std::str::FromStr>((line, token): Token) -> Result<T, GraphIoError> {
+    token.parse().map_err(|_| GraphIoError::Parse {
+        line,
+        token: token.to_string(),
+    })
+}
+
+pub(crate) fn parse_weight<W: Weight>((line, token): Token) -> Result<W, GraphIoError> {
+    W::parse(token).ok_or_else(|| {
+        let token = token.to_string();
+        // a number that integer weights can not hold exactly.
+        match !W::FLOAT && token.parse::<f64>().is_ok() {
+            true => GraphIoError::Weight { line, token },
+            false => GraphIoError::Parse { line, token },
+        }
+    })
+}
+
+/// The sections of a PBBS (weighted) adjacency graph file.
+struct AdjacencyFile<'a> {
+    n: usize,
+    m: usize,
+    offsets: Vec<Token<'a>>,
+    edges: Vec<Token<'a>>,
+    weights: Option<Vec<Token<'a>>>,
+}
+
+fn split_adjacency_file(contents: &str) -> Result<AdjacencyFile<'_>, GraphIoError> {
+    let (header, lines) = split_lines(contents);
+    if header != ADJ_GRAPH_HEADER && header != WGH_ADJ_GRAPH_HEADER {
+        return Err(GraphIoError::Header {
+            found: header.to_string(),
+        });
+    }
+    if lines.len() < 2 {
+        return Err(GraphIoError::Count {
+            expected: 2,
+            found: lines.len(),
+        });
+    }
+    let n: usize = parse(lines[0])?;
+    let m: usize = parse(lines[1])?;
+
+    // a line may hold several values.
+    let mut words = lines[2..]
+        .par_iter()
+        .flat_map_iter(|&(i, l)| l.split_whitespace().map(move |w| (i, w)))
+        .collect::<Vec<_>>();
+    let weighted = header == WGH_ADJ_GRAPH_HEADER;
+    let expected = if weighted { n + 2 * m } else { n + m };
+    if words.len() != expected {
+        return Err(GraphIoError::Count {
+            expected,
+            found: words.len(),
+        });
+    }
+    let weights = weighted.then(|| words.split_off(n + m));
+    let edges = words.split_off(n);
+
+    Ok(AdjacencyFile {
+        n,
+        m,
+        offsets: words,
+        edges,
+        weights,
+    })
+}
+
+/// Checks that the offsets are monotone and in range, and that all the edges
+/// point to vertices.
+fn validate<I: Idx>(file: &AdjacencyFile, offsets: &[I], edges: &[I]) -> Result<(), GraphIoError> {
+    let (n, m) = (file.n, file.m);
+    if let Some(i) = (0..n).into_par_iter().position_first(|i| {
+        let lo = if i == 0 { I::zero() } else { offsets[i - 1] };
+        offsets[i] < lo || offsets[i].as_() > m
+    }) {
+        return Err(GraphIoError::Offset {
+            line: file.offsets[i].0,
+            offset: offsets[i].as_(),
+        });
+    }
+    if let Some(j) = edges.par_iter().position_first(|t| t.as_() >= n) {
+        return Err(GraphIoError::Target {
+            line: file.edges[j].0,
+            target: edges[j].as_(),
+            n,
+        });
+    }
+    Ok(())
+}
+
+/// The offset past the last edge, which must fit the index type.
+fn end_offset<I: Idx>(m: usize) -> Result<I, GraphIoError> {
+    I::from(m).ok_or(GraphIoError::EdgeOverflow { m })
+}
+
+/// Formats `xs` in parallel, one per line.
+fn to_lines<T: Display + Sync>(xs: &[T]) -> String {
+    xs.par_iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_adjacency_file<P: AsRef<Path>>(
+    path: P,
+    header: &str,
+    n: usize,
+    m: usize,
+    sections: &[String],
+) -> std::io::Result<()> {
+    let mut contents = format!("{header}\n{n}\n{m}\n");
+    for s in sections.iter().filter(|s| !s.is_empty()) {
+        contents.push_str(s);
+        contents.push('\n');
+    }
+    std::fs::write(path, contents)
+}
+
+impl<I: Idx> Graph<I> {
+    /// Reads a PBBS `AdjacencyGraph`. The weights of a `WeightedAdjacencyGraph`
+    /// are ignored.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        let contents = std::fs::read_to_string(path)?;
+        let file = split_adjacency_file(&contents)?;
+        if file.weights.is_some() {
+            eprintln!("Warning: graph is weighted, ignoring weights");
+        }
+
+        let mut offsets = parse_all(&file.offsets, parse)?;
+        let edges = parse_all(&file.edges, parse)?;
+        validate(&file, &offsets, &edges)?;
+        offsets.push(end_offset(file.m)?);
+
+        Ok(Self {
+            offsets,
+            edges,
+            degrees: vec![],
+            n: file.n,
+            m: file.m,
+        })
+    }
+
+    /// Writes the graph as a PBBS `AdjacencyGraph`.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let sections = [to_lines(&self.offsets[..self.n]), to_lines(&self.edges)];
+        write_adjacency_file(path, ADJ_GRAPH_HEADER, self.n, self.m, &sections)
+    }
+}
+
+impl<I: Idx, W: Weight> WghGraph<I, W> {
+    /// Reads a PBBS `WeightedAdjacencyGraph`. All the edges of an
+    /// `AdjacencyGraph` weigh one.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, GraphIoError> {
+        let contents = std::fs::read_to_string(path)?;
+        let file = split_adjacency_file(&contents)?;
+
+        let mut offsets = parse_all(&file.offsets, parse)?;
+        let edges = parse_all(&file.edges, parse)?;
+        validate(&file, &offsets, &edges)?;
+        offsets.push(end_offset(file.m)?);
+
+        let weights = match &file.weights {
+            Some(weights) => parse_all(weights, parse_weight)?,
+            None => {
+                eprintln!("Warning: graph is unweighted, using 1 for all edges");
+                vec![W::one(); file.m]
+            }
+        };
+
+        Ok(Self {
+            offsets,
+            edges,
+            weights,
+            n: file.n,
+            m: file.m,
+        })
+    }
+
+    /// Writes the graph as a PBBS `WeightedAdjacencyGraph`.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let sections = [
+            to_lines(&self.offsets[..self.n]),
+            to_lines(&self.edges),
+            to_lines(&self.weights),
+        ];
+        write_adjacency_file(path, WGH_ADJ_GRAPH_HEADER, self.n, self.m, &sections)
+    }
+}
+
+/// Reads the lines of an edge array with header `expected`, each of
+/// `fields` whitespace separated values.
+fn read_edge_lines<T, P, F>(
+    path: P,
+    expected: &str,
+    fields: usize,
+    f: F,
+) -> Result<Vec<T>, GraphIoError>
+where
+    T: Send,
+    P: AsRef<Path>,
+    F: Fn(usize, &[&str]) -> Result<T, GraphIoError> + Sync,
+{
+    let contents = std::fs::read_to_string(path)?;
+    let (header, lines) = split_lines(&contents);
+    if header != expected {
+        return Err(GraphIoError::Header {
+            found: header.to_string(),
+        });
+    }
+    parse_all(&lines, |(line, l)| {
+        let w = l.split_whitespace().collect::<Vec<_>>();
+        if w.len() != fields {
+            return Err(GraphIoError::Fields {
+                line,
+                expected: fields,
+                found: w.len(),
+            });
+        }
+        f(line, &w)
+    })
+}
+
+/// Reads a PBBS `EdgeArray` as `(u, v)` pairs.
+pub fn read_edge_array<I: Idx, P: AsRef<Path>>(path: P) -> Result<Vec<(I, I)>, GraphIoError> {
+    read_edge_lines(path, EDGE_ARRAY_HEADER, 2, |line, w| {
+        Ok((parse((line, w[0]))?, parse((line, w[1]))?))
+    })
+}
+
+/// Reads a PBBS `WeightedEdgeArray` as `(u, v, w)` triples.
+pub fn read_wgh_edge_array<I: Idx, W: Weight, P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<(I, I, W)>, GraphIoError> {
+    read_edge_lines(path, WGH_EDGE_ARRAY_HEADER, 3, |line, w| {
+        Ok((
+            parse((line, w[0]))?,
+            parse((line, w[1]))?,
+            parse_weight((line, w[2]))?,
+        ))
+    })
+}
+
+/// Writes `(u, v)` pairs as a PBBS `EdgeArray`.
+pub fn write_edge_array<I: Idx, P: AsRef<Path>>(path: P, edges: &[(I, I)]) -> std::io::Result<()> {
+    let lines = edges
+        .par_iter()
+        .map(|(u, v)| format!("{u} {v}\n"))
+        .collect::<String>();
+    std::fs::write(path, format!("{EDGE_ARRAY_HEADER}\n{lines}"))
+}
+
+/// Writes `(u, v, w)` triples as a PBBS `WeightedEdgeArray`.
+pub fn write_wgh_edge_array<I: Idx, W: Weight, P: AsRef<Path>>(
+    path: P,
+    edges: &[(I, I, W)],
+) -> std::io::Result<()> {
+    let lines = edges
+        .par_iter()
+        .map(|(u, v, w)| format!("{u} {v} {w}\n"))
+        .collect::<String>();
+    std::fs::write(path, format!("{WGH_EDGE_ARRAY_HEADER}\n{lines}"))
+}
+
+#[cfg(test)]
+mod io_tests {
+    use super::*;
+
+    fn write_tmp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("rpb_graph_{}_{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn adjacency_graph() {
+        let path = write_tmp("adj", "AdjacencyGraph\n3\n4\n0\n2\n3\n1\n2\n2\n0\n");
+        let g = Graph::<u32>::from_file(&path).unwrap();
+        assert_eq!((g.num_vertices(), g.num_edges()), (3, 4));
+        assert_eq!(g.index(0).neighbors, &[1, 2]);
+        assert_eq!(g.index(2).degree, 1);
+
+        let g = WghGraph::<u32, f32>::from_file(&path).unwrap();
+        assert_eq!(g.index(1).weights, &[1.0]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn weighted_adjacency_graph() {
+        let path = write_tmp("wadj", "WeightedAdjacencyGraph\n2\n2\n0 1\n1 0\n5 2e3\n");
+        let g = WghGraph::<u32, u64>::from_file(&path).unwrap();
+        assert_eq!(g.index(0).neighbors, &[1]);
+        assert_eq!(g.weights, vec![5, 2000]);
+
+        let g = Graph::<usize>::from_file(&path).unwrap();
+        assert_eq!(g.edges, vec![1, 0]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn edge_arrays() {
+        let path = write_tmp("ea", "EdgeArray\n0 1\n\n2 0\n");
+        assert_eq!(
+            read_edge_array::<u32, _>(&path).unwrap(),
+            vec![(0, 1), (2, 0)]
+        );
+
+        std::fs::write(&path, "WeightedEdgeArray\n0 1 0.5\n1 2 3\n").unwrap();
+        let es = read_wgh_edge_array::<u32, f64, _>(&path).unwrap();
+        assert_eq!(es, vec![(0, 1, 0.5), (1, 2, 3.0)]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn errors() {
+        let err = |contents: &str| {
+            let path = write_tmp("err", contents);
+            let e = Graph::<u32>::from_file(&path).err().unwrap();
+            std::fs::remove_file(path).unwrap();
+            e.to_string()
+        };
+        assert_eq!(err("Graph\n1\n0\n0\n"), "line 1: unknown header \"Graph\"");
+        assert_eq!(
+            err("AdjacencyGraph\n2\n2\n0\n1\n1\n"),
+            "expected 4 values after the header, found 3"
+        );
+        assert_eq!(
+            err("AdjacencyGraph\n2\n2\n0\n1\nx\n0\n"),
+            "line 6: can not parse \"x\""
+        );
+        assert_eq!(
+            err("AdjacencyGraph\n2\n2\n1\n0\n1\n0\n"),
+            "line 5: offset 0 out of order"
+        );
+        assert_eq!(
+            err("AdjacencyGraph\n2\n2\n0\n1\n1\n2\n"),
+            "line 7: target 2 is not a vertex (n=2)"
+        );
+
+        // the weights of a weighted graph are not optional, and an unweighted
+        // graph has none.
+        let path = write_tmp("wadj_err", "WeightedAdjacencyGraph\n2\n2\n0\n1\n1\n0\n");
+        let e = WghGraph::<u32, u32>::from_file(&path).err().unwrap();
+        assert_eq!(e.to_string(), "expected 6 values after the header, found 4");
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            err("AdjacencyGraph\n2\n2\n0\n1\n1\n0\n5\n7\n"),
+            "expected 4 values after the header, found 6"
+        );
+
+        let edges = (0..256).map(|_| "0").collect::<Vec<_>>().join("\n");
+        let path = write_tmp("overflow", &format!("AdjacencyGraph\n1\n256\n0\n{edges}\n"));
+        let e = Graph::<u8>::from_file(&path).err().unwrap();
+        assert_eq!(e.to_string(), "256 edges do not fit the index type");
+        std::fs::remove_file(path).unwrap();
+
+        // integer weights are neither truncated nor saturated.
+        let weight_err = |w: &str| {
+            let path = write_tmp(
+                "wadj_w",
+                &format!("WeightedAdjacencyGraph\n1\n1\n0\n0\n{w}\n"),
+            );
+            let e = WghGraph::<u32, u32>::from_file(&path).err().unwrap();
+            std::fs::remove_file(path).unwrap();
+            e.to_string()
+        };
+        assert_eq!(
+            weight_err("2.5"),
+            "line 6: \"2.5\" is not a valid integer weight"
+        );
+        assert_eq!(
+            weight_err("-1"),
+            "line 6: \"-1\" is not a valid integer weight"
+        );
+        assert_eq!(
+            weight_err("1e10"),
+            "line 6: \"1e10\" is not a valid integer weight"
+        );
+        assert_eq!(
+            weight_err("inf"),
+            "line 6: \"inf\" is not a valid integer weight"
+        );
+        assert_eq!(weight_err("w"), "line 6: can not parse \"w\"");
+
+        let path = write_tmp("ea_err", "EdgeArray\n0 1\n2\n");
+        let e = read_edge_array::<u32, _>(&path).err().unwrap();
+        assert_eq!(e.to_string(), "line 3: expected 2 fields, found 1");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        let edges = vec![(2, 0, 0.5), (0, 1, 1.5), (0, 2, 2.0), (1, 2, 4.0)];
+        let g = WghGraph::<u32, f32>::from_edges(4, edges.clone());
+        assert_eq!(g.offsets, vec![0, 2, 3, 4, 4]);
+        assert_eq!(g.index(3).degree, 0);
+
+        let path = write_tmp("rw", "");
+        g.to_file(&path).unwrap();
+        let h = WghGraph::<u32, f32>::from_file(&path).unwrap();
+        assert_eq!(
+            (&h.offsets, &h.edges, &h.weights),
+            (&g.offsets, &g.edges, &g.weights)
+        );
+        let mut sorted = edges;
+        sorted.sort_by_key(|e| e.0);
+        assert_eq!(h.to_edges(), sorted);
+
+        Graph::<u32>::from_file(&path)
+            .unwrap()
+            .to_file(&path)
+            .unwrap();
+        assert_eq!(
+            Graph::<u32>::from_file(&path).unwrap().edges,
+            vec![1, 2, 2, 0]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
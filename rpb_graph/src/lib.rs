@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use num_traits::{AsPrimitive, PrimInt};
+
+mod access;
+mod binary;
+mod compressed;
+mod csr;
+mod error;
+mod import;
+mod io;
+mod transform;
+
+pub use access::{NeighborAccess, SliceNeighbors};
+pub use binary::{is_bin_file, MappedGraph, BIN_GRAPH_MAGIC};
+pub use compressed::{ByteNeighbors, CompressedGraph};
+pub use csr::{Graph, Vertex, WghGraph, WghVertex};
+pub use error::GraphIoError;
+pub use import::{import_edge_list, EdgeList, ImportOptions, MATRIX_MARKET_HEADER};
+pub use io::{
+    read_edge_array, read_wgh_edge_array, write_edge_array, write_wgh_edge_array, ADJ_GRAPH_HEADER,
+    EDGE_ARRAY_HEADER, WGH_ADJ_GRAPH_HEADER, WGH_EDGE_ARRAY_HEADER,
+};
+
+/// The integer type of vertex ids and edge offsets.
+/// `u32` halves the memory of a graph compared to `usize`.
+pub trait Idx:
+    PrimInt + AsPrimitive<usize> + FromStr + Display + Default + Send + Sync + Debug
+{
+}
+
+impl<T> Idx for T where
+    T: PrimInt + AsPrimitive<usize> + FromStr + Display + Default + Send + Sync + Debug
+{
+}
+
+/// The type of edge weights.
+pub trait Weight: Copy + Default + PartialOrd + Display + Send + Sync + Debug {
+    /// whether the weight is a floating point number (for binary files).
+    const FLOAT: bool;
+
+    /// the weight of the edges of an unweighted graph.
+    fn one() -> Self;
+
+    /// Parses a weight. Integer weights accept floats too if they are whole
+    /// numbers in range, such as `3.0` or `1e6`, but reject `2.5` rather
+    /// than truncate it.
+    fn parse(s: &str) -> Option<Self>;
+}
+
+macro_rules! int_weight {
+    ($($t: ty),*) => {$(
+        impl Weight for $t {
+            const FLOAT: bool = false;
+
+            fn one() -> Self {
+                1
+            }
+
+            fn parse(s: &str) -> Option<Self> {
+                s.parse().ok().or_else(|| {
+                    let w = s.parse::<f64>().ok()?;
+                    // `cast` fails out of range instead of saturating.
+                    (w.fract() == 0.0).then(|| num_traits::cast(w)).flatten()
+                })
+            }
+        }
+    )*}
+}
+
+macro_rules! float_weight {
+    ($($t: ty),*) => {$(
+        impl Weight for $t {
+            const FLOAT: bool = true;
+
+            fn one() -> Self {
+                1.0
+            }
+
+            fn parse(s: &str) -> Option<Self> {
+                s.parse().ok()
+            }
+        }
+    )*}
+}
+
+int_weight!(u32, u64, usize, i32, i64);
+float_weight!(f32, f64);
@@ -0,0 +1,197 @@
+use rayon::prelude::*;
+// ============================================================================
+// This code is part of RPB.
+// ----------------------------------------------------------------------------
+// MIT License
+//
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::sequence_ops::scan_inplace;
+use parlay::primitives::flatten_by_val;
+
+use crate::{Graph, Idx};
+
+// **************************************************************
+//    GRAPH TRANSFORMATIONS
+// **************************************************************
+
+/// Builds a graph from the neighbor lists of its vertices.
+fn from_neighbor_lists<I: Idx>(lists: Vec<Vec<I>>) -> Graph<I> {
+    let n = lists.len();
+    let mut offsets: Vec<I> = lists
+        .par_iter()
+        .map(|l| I::from(l.len()).unwrap())
+        .chain([I::zero()])
+        .collect();
+    let m = scan_inplace(&mut offsets, false, |a, b| a + b).as_();
+    let mut edges = vec![];
+    flatten_by_val(&lists, &mut edges);
+
+    Graph {
+        offsets,
+        edges,
+        degrees: vec![],
+        n,
+        m,
+    }
+}
+
+impl<I: Idx> Graph<I> {
+    fn map_neighbors(&self, f: impl Fn(usize, &[I]) -> Vec<I> + Sync) -> Self {
+        from_neighbor_lists(
+            (0..self.n)
+                .into_par_iter()
+                .map(|u| f(u, self.neighbor_slice(u)))
+                .collect(),
+        )
+    }
+
+    /// The graph of the vertices `old_ids[i]`, renamed to `i`. The edges to
+    /// the vertices that `new_id` maps to `None` are dropped.
+    fn remap(&self, old_ids: &[I], new_id: impl Fn(I) -> Option<I> + Sync) -> Self {
+        from_neighbor_lists(
+            old_ids
+                .par_iter()
+                .map(|&u| {
+                    let ns = self.neighbor_slice(u.as_());
+                    ns.iter().filter_map(|&v| new_id(v)).collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// The graph with every edge reversed.
+    pub fn transpose(&self) -> Self {
+        let edges = self
+            .to_edges()
+            .into_par_iter()
+            .map(|(u, v)| (v, u))
+            .collect();
+        Self::from_edges(self.n, edges)
+    }
+
+    /// The graph with the reverse of every edge added and without parallel
+    /// edges, as the undirected benchmarks expect.
+    pub fn symmetrize(&self) -> Self {
+        let mut edges = self.to_edges();
+        let reversed: Vec<_> = edges.par_iter().map(|&(u, v)| (v, u)).collect();
+        edges.extend(reversed);
+        Self::from_edges(self.n, edges).remove_multi_edges()
+    }
+
+    pub fn remove_self_loops(&self) -> Self {
+        self.map_neighbors(|u, ns| ns.iter().copied().filter(|v| v.as_() != u).collect())
+    }
+
+    /// Keeps one of the parallel edges. The neighbors of every vertex end up
+    /// sorted.
+    pub fn remove_multi_edges(&self) -> Self {
+        self.map_neighbors(|_, ns| {
+            let mut ns = ns.to_vec();
+            ns.sort_unstable();
+            ns.dedup();
+            ns
+        })
+    }
+
+    /// The subgraph induced by the vertices that `keep` accepts, renamed to
+    /// `0..k` in their order. Also returns the old id of every new vertex.
+    pub fn induced_subgraph(&self, keep: impl Fn(usize) -> bool + Sync) -> (Self, Vec<I>) {
+        let flags: Vec<bool> = (0..self.n).into_par_iter().map(&keep).collect();
+        let mut new_ids: Vec<I> = flags
+            .par_iter()
+            .map(|&f| if f { I::one() } else { I::zero() })
+            .collect();
+        scan_inplace(&mut new_ids, false, |a, b| a + b);
+        let old_ids: Vec<I> = (0..self.n)
+            .into_par_iter()
+            .filter(|&u| flags[u])
+            .map(|u| I::from(u).unwrap())
+            .collect();
+
+        let g = self.remap(&old_ids, |v| flags[v.as_()].then(|| new_ids[v.as_()]));
+        (g, old_ids)
+    }
+
+    /// The graph with the vertices renamed in the order of decreasing
+    /// degree, ties broken by id. Also returns the old id of every new
+    /// vertex.
+    pub fn relabel_by_degree(&self) -> (Self, Vec<I>) {
+        let mut old_ids: Vec<I> = (0..self.n)
+            .into_par_iter()
+            .map(|u| I::from(u).unwrap())
+            .collect();
+        old_ids.par_sort_by_key(|&u| std::cmp::Reverse(self.neighbor_slice(u.as_()).len()));
+
+        let mut ranks: Vec<(I, I)> = old_ids
+            .par_iter()
+            .enumerate()
+            .map(|(new, &old)| (old, I::from(new).unwrap()))
+            .collect();
+        ranks.par_sort_unstable_by_key(|r| r.0);
+
+        let g = self.remap(&old_ids, |v| Some(ranks[v.as_()].1));
+        (g, old_ids)
+    }
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+
+    fn graph(n: usize, edges: &[(u32, u32)]) -> Graph<u32> {
+        Graph::from_edges(n, edges.to_vec())
+    }
+
+    #[test]
+    fn symmetrize_and_clean_up() {
+        let g = graph(3, &[(0, 1), (0, 1), (1, 1), (2, 0)]);
+        assert_eq!(g.transpose().to_edges(), [(0, 2), (1, 0), (1, 0), (1, 1)]);
+        assert_eq!(
+            g.symmetrize().to_edges(),
+            [(0, 1), (0, 2), (1, 0), (1, 1), (2, 0)]
+        );
+        assert_eq!(g.remove_self_loops().to_edges(), [(0, 1), (0, 1), (2, 0)]);
+        assert_eq!(g.remove_multi_edges().num_edges(), 3);
+    }
+
+    #[test]
+    fn subgraphs_and_relabeling() {
+        let g = graph(4, &[(0, 3), (1, 2), (2, 1), (2, 3), (3, 2)]);
+        let (h, old) = g.induced_subgraph(|u| u != 1);
+        assert_eq!(old, [0, 2, 3]);
+        assert_eq!(h.to_edges(), [(0, 2), (1, 2), (2, 1)]);
+
+        let (h, old) = g.relabel_by_degree();
+        assert_eq!(old, [2, 0, 1, 3]);
+        assert_eq!(h.to_edges(), [(0, 2), (0, 3), (1, 3), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn num_edges_with_degrees() {
+        let mut g = graph(3, &[(0, 1), (0, 2), (1, 2)]);
+        g.add_degrees();
+        g.degrees[0] = 1;
+        assert_eq!(g.num_edges(), 2);
+        assert_eq!(g.remove_multi_edges().to_edges(), [(0, 1), (1, 2)]);
+    }
+}